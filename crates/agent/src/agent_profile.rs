@@ -45,8 +45,10 @@ impl AgentProfile {
                 .map(|profile| profile.enable_all_context_servers)
                 .unwrap_or_default(),
             context_servers: base_profile
-                .map(|profile| profile.context_servers)
+                .as_ref()
+                .map(|profile| profile.context_servers.clone())
                 .unwrap_or_default(),
+            response_language: base_profile.and_then(|profile| profile.response_language),
         };
 
         update_settings_file(fs, cx, {
@@ -85,6 +87,11 @@ impl AgentProfile {
             .collect()
     }
 
+    /// Returns the name of the language to respond in with this profile, if any.
+    pub fn response_language(&self, cx: &App) -> Option<String> {
+        AgentSettings::get_global(cx).response_language(Some(&self.id))
+    }
+
     pub fn is_tool_enabled(&self, source: ToolSource, tool_name: String, cx: &App) -> bool {
         let Some(settings) = AgentSettings::get_global(cx).profiles.get(&self.id) else {
             return false;
@@ -113,8 +120,9 @@ mod tests {
     use gpui::SharedString;
     use gpui::{AppContext, TestAppContext};
     use http_client::FakeHttpClient;
+    use localization::Language;
     use project::Project;
-    use settings::{Settings, SettingsStore};
+    use settings::{Settings, SettingsStore, UiLanguagePreference};
 
     use super::*;
 
@@ -251,12 +259,31 @@ mod tests {
                     tools: IndexMap::default(),
                     enable_all_context_servers: false,
                     context_servers: IndexMap::from_iter([("mcp".into(), context_server_preset())]),
+                    response_language: Some(UiLanguagePreference::Locale("ja".into())),
                 },
             );
             AgentSettings::override_global(agent_settings, cx);
         })
     }
 
+    #[gpui::test]
+    async fn test_response_language_for_profile(cx: &mut TestAppContext) {
+        init_test_settings(cx);
+        let tool_set = default_tool_set(cx);
+
+        let _language = localization::set_language_for_test(Language::ENGLISH);
+        let profile = AgentProfile::new(AgentProfileId("custom_mcp".into()), tool_set.clone());
+        assert_eq!(
+            cx.read(|cx| profile.response_language(cx)),
+            Some("Japanese (日本語)".to_string())
+        );
+
+        // Profiles without an override follow the interface language, which needs no
+        // instruction in English.
+        let profile = AgentProfile::new(AgentProfileId::default(), tool_set);
+        assert_eq!(cx.read(|cx| profile.response_language(cx)), None);
+    }
+
     fn context_server_preset() -> ContextServerPreset {
         ContextServerPreset {
             tools: IndexMap::from_iter([
@@ -1360,6 +1360,7 @@ impl Thread {
 
         let model_context = &ModelContext {
             available_tools: available_tool_names,
+            response_language: self.profile.response_language(cx),
         };
 
         if let Some(project_context) = self.project_context.borrow().as_ref() {
@@ -38,6 +38,7 @@ pub struct SystemPromptTemplate<'a> {
     #[serde(flatten)]
     pub project: &'a prompt_store::ProjectContext,
     pub available_tools: Vec<SharedString>,
+    pub response_language: Option<String>,
 }
 
 impl Template for SystemPromptTemplate<'_> {
@@ -79,9 +80,19 @@ mod tests {
         let template = SystemPromptTemplate {
             project: &project,
             available_tools: vec!["echo".into()],
+            response_language: None,
         };
         let templates = Templates::new();
         let rendered = template.render(&templates).unwrap();
         assert!(rendered.contains("## Fixing Diagnostics"));
+        assert!(!rendered.contains("## Response Language"));
+
+        let template = SystemPromptTemplate {
+            project: &project,
+            available_tools: vec!["echo".into()],
+            response_language: Some("Korean (한국어)".into()),
+        };
+        let rendered = template.render(&templates).unwrap();
+        assert!(rendered.contains("Respond to the user in Korean (한국어)"));
     }
 }
@@ -1952,6 +1952,8 @@ impl Thread {
         let system_prompt = SystemPromptTemplate {
             project: self.project_context.read(cx),
             available_tools: self.tools.keys().cloned().collect(),
+            response_language: AgentSettings::get_global(cx)
+                .response_language(Some(&self.profile_id)),
         }
         .render(&self.templates)
         .context("failed to build system prompt")
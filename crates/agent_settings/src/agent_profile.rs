@@ -7,7 +7,7 @@ use fs::Fs;
 use gpui::{App, SharedString};
 use settings::{
     AgentProfileContent, ContextServerPresetContent, Settings as _, SettingsContent,
-    update_settings_file,
+    UiLanguagePreference, update_settings_file,
 };
 use util::ResultExt as _;
 
@@ -64,8 +64,10 @@ impl AgentProfile {
                 .map(|profile| profile.enable_all_context_servers)
                 .unwrap_or_default(),
             context_servers: base_profile
-                .map(|profile| profile.context_servers)
+                .as_ref()
+                .map(|profile| profile.context_servers.clone())
                 .unwrap_or_default(),
+            response_language: base_profile.and_then(|profile| profile.response_language),
         };
 
         update_settings_file(fs, cx, {
@@ -96,6 +98,8 @@ pub struct AgentProfileSettings {
     pub tools: IndexMap<Arc<str>, bool>,
     pub enable_all_context_servers: bool,
     pub context_servers: IndexMap<Arc<str>, ContextServerPreset>,
+    /// The language to respond in, overriding [`AgentSettings::response_language`].
+    pub response_language: Option<UiLanguagePreference>,
 }
 
 impl AgentProfileSettings {
@@ -144,6 +148,7 @@ impl AgentProfileSettings {
                         )
                     })
                     .collect(),
+                response_language: self.response_language.clone(),
             },
         );
 
@@ -162,6 +167,7 @@ impl From<AgentProfileContent> for AgentProfileSettings {
                 .into_iter()
                 .map(|(server_id, preset)| (server_id, preset.into()))
                 .collect(),
+            response_language: content.response_language,
         }
     }
 }
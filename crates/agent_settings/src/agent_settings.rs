@@ -5,12 +5,13 @@ use std::sync::Arc;
 use collections::IndexMap;
 use gpui::{App, Pixels, px};
 use language_model::LanguageModel;
+use localization::Language;
 use project::DisableAiSettings;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{
     DefaultAgentView, DockPosition, LanguageModelParameters, LanguageModelSelection,
-    NotifyWhenAgentWaiting, Settings, SettingsContent,
+    NotifyWhenAgentWaiting, Settings, SettingsContent, UiLanguagePreference,
 };
 
 pub use crate::agent_profile::*;
@@ -51,6 +52,7 @@ pub struct AgentSettings {
     pub expand_terminal_card: bool,
     pub use_modifier_to_send: bool,
     pub message_editor_min_lines: usize,
+    pub response_language: UiLanguagePreference,
 }
 
 impl AgentSettings {
@@ -100,6 +102,26 @@ impl AgentSettings {
     pub fn set_message_editor_max_lines(&self) -> usize {
         self.message_editor_min_lines * 2
     }
+
+    /// Returns the name of the language to respond in when using the given profile, or
+    /// `None` to leave it to the model, as when following an English interface.
+    pub fn response_language(&self, profile_id: Option<&AgentProfileId>) -> Option<String> {
+        let preference = profile_id
+            .and_then(|profile_id| self.profiles.get(profile_id))
+            .and_then(|profile| profile.response_language.as_ref())
+            .unwrap_or(&self.response_language);
+        let language = match preference {
+            UiLanguagePreference::Auto => {
+                let language = localization::current_language();
+                if language == Language::ENGLISH || language == Language::PSEUDO {
+                    return None;
+                }
+                language
+            }
+            UiLanguagePreference::Locale(tag) => Language::from_code(tag)?,
+        };
+        Some(localization::language_name_for_prompt(&language))
+    }
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
@@ -184,6 +206,7 @@ impl Settings for AgentSettings {
             expand_terminal_card: agent.expand_terminal_card.unwrap(),
             use_modifier_to_send: agent.use_modifier_to_send.unwrap(),
             message_editor_min_lines: agent.message_editor_min_lines.unwrap(),
+            response_language: agent.response_language.unwrap(),
         }
     }
 
@@ -8,6 +8,7 @@ use gpui::{
     App, Entity, EventEmitter, FocusHandle, Focusable, ScrollStrategy, Task,
     UniformListScrollHandle, WeakEntity, Window, uniform_list,
 };
+use localization::t;
 use std::{fmt::Display, ops::Range};
 use text::Bias;
 use time::{OffsetDateTime, UtcOffset};
@@ -729,13 +730,14 @@ impl TimeBucket {
 
 impl Display for TimeBucket {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            TimeBucket::Today => write!(f, "Today"),
-            TimeBucket::Yesterday => write!(f, "Yesterday"),
-            TimeBucket::ThisWeek => write!(f, "This Week"),
-            TimeBucket::PastWeek => write!(f, "Past Week"),
-            TimeBucket::All => write!(f, "All"),
-        }
+        let label = match self {
+            TimeBucket::Today => t!("thread_history.today", "Today"),
+            TimeBucket::Yesterday => t!("thread_history.yesterday", "Yesterday"),
+            TimeBucket::ThisWeek => t!("thread_history.this_week", "This Week"),
+            TimeBucket::PastWeek => t!("thread_history.past_week", "Past Week"),
+            TimeBucket::All => t!("thread_history.all", "All"),
+        };
+        f.write_str(&label)
     }
 }
 
@@ -294,6 +294,7 @@ impl PickerDelegate for ToolPickerDelegate {
                                 )
                             })
                             .collect(),
+                        response_language: default_profile.response_language,
                     });
 
                 if let Some(server_id) = server_id {
@@ -429,9 +429,16 @@ impl CodegenAlternative {
             anyhow::bail!("invalid transformation range");
         };
 
+        let response_language = AgentSettings::get_global(cx).response_language(None);
         let prompt = self
             .builder
-            .generate_inline_transformation_prompt(user_prompt, language_name, buffer, range)
+            .generate_inline_transformation_prompt(
+                user_prompt,
+                language_name,
+                buffer,
+                range,
+                response_language,
+            )
             .context("generating content prompt")?;
 
         let context_task = self.context_store.as_ref().map(|context_store| {
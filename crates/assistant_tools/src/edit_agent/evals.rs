@@ -1584,6 +1584,7 @@ impl EditAgentTest {
             &project_context,
             &ModelContext {
                 available_tools: tool_names,
+                response_language: None,
             },
         )?;
 
@@ -464,6 +464,20 @@ impl Server {
             .add_request_handler(forward_mutating_project_request::<proto::GitCreateBranch>)
             .add_request_handler(forward_mutating_project_request::<proto::GitChangeBranch>)
             .add_request_handler(forward_mutating_project_request::<proto::CheckForPushedCommits>)
+            .add_message_handler(
+                broadcast_project_message_from_host::<proto::UpdateJujutsuRepository>,
+            )
+            .add_message_handler(
+                broadcast_project_message_from_host::<proto::RemoveJujutsuRepository>,
+            )
+            .add_request_handler(forward_read_only_project_request::<proto::JujutsuGetBookmarks>)
+            .add_request_handler(forward_read_only_project_request::<proto::JujutsuLog>)
+            .add_request_handler(forward_read_only_project_request::<proto::JujutsuLoadCommitDiff>)
+            .add_request_handler(forward_read_only_project_request::<proto::JujutsuOperationLog>)
+            .add_request_handler(
+                forward_read_only_project_request::<proto::JujutsuLoadOperationDiff>,
+            )
+            .add_request_handler(forward_mutating_project_request::<proto::JujutsuMutate>)
             .add_message_handler(broadcast_project_message_from_host::<proto::AdvertiseContexts>)
             .add_message_handler(update_context)
             .add_request_handler(forward_mutating_project_request::<proto::ToggleLspLogs>)
@@ -2,7 +2,9 @@ mod persistence;
 
 use std::{
     cmp::{self, Reverse},
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    iter,
+    rc::Rc,
     sync::Arc,
     time::Duration,
 };
@@ -21,7 +23,10 @@ use persistence::COMMAND_PALETTE_HISTORY;
 use picker::{Picker, PickerDelegate};
 use postage::{sink::Sink, stream::Stream};
 use settings::Settings;
-use ui::{HighlightedLabel, KeyBinding, ListItem, ListItemSpacing, h_flex, prelude::*, v_flex};
+use ui::{
+    DocumentationAside, DocumentationEdge, DocumentationSide, HighlightedLabel, KeyBinding,
+    ListItem, ListItemSpacing, h_flex, prelude::*, v_flex,
+};
 use util::ResultExt;
 use workspace::{ModalView, Workspace, WorkspaceSettings};
 use zed_actions::{OpenZedUrl, command_palette::Toggle};
@@ -102,8 +107,12 @@ impl CommandPalette {
                     return None;
                 }
 
+                let name = humanize_action_name(action.name());
+                let localized_name = localization::action_display_name(action.name(), &name)
+                    .map(|localized_name| localized_name.to_string());
                 Some(Command {
-                    name: humanize_action_name(action.name()),
+                    name,
+                    localized_name,
                     action,
                 })
             })
@@ -159,6 +168,9 @@ pub struct CommandPaletteDelegate {
 
 struct Command {
     name: String,
+    /// The name in the interface language, when the action has been translated.
+    /// Queries are matched against both names.
+    localized_name: Option<String>,
     action: Box<dyn Action>,
 }
 
@@ -166,6 +178,7 @@ impl Clone for Command {
     fn clone(&self) -> Self {
         Self {
             name: self.name.clone(),
+            localized_name: self.localized_name.clone(),
             action: self.action.boxed_clone(),
         }
     }
@@ -227,6 +240,7 @@ impl CommandPaletteDelegate {
             }
             commands.push(Command {
                 name: string.clone(),
+                localized_name: None,
                 action,
             });
             new_matches.push(StringMatch {
@@ -265,7 +279,9 @@ impl PickerDelegate for CommandPaletteDelegate {
     type ListItem = ListItem;
 
     fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
-        "Execute a command...".into()
+        localization::t!("command_palette.placeholder", "Execute a command...")
+            .as_ref()
+            .into()
     }
 
     fn match_count(&self) -> usize {
@@ -312,10 +328,16 @@ impl PickerDelegate for CommandPaletteDelegate {
                 let candidates = commands
                     .iter()
                     .enumerate()
-                    .map(|(ix, command)| StringMatchCandidate::new(ix, &command.name))
+                    .flat_map(|(ix, command)| {
+                        iter::once(StringMatchCandidate::new(ix, &command.name)).chain(
+                            command.localized_name.as_ref().map(|localized_name| {
+                                StringMatchCandidate::new(ix, localized_name)
+                            }),
+                        )
+                    })
                     .collect::<Vec<_>>();
 
-                let matches = fuzzy::match_strings(
+                let mut matches = fuzzy::match_strings(
                     &candidates,
                     &query,
                     true,
@@ -325,6 +347,9 @@ impl PickerDelegate for CommandPaletteDelegate {
                     executor,
                 )
                 .await;
+                // Keep only the best scoring of a command's names.
+                let mut matched_commands = HashSet::new();
+                matches.retain(|string_match| matched_commands.insert(string_match.candidate_id));
 
                 tx.send((commands, matches)).await.log_err();
             }
@@ -415,6 +440,30 @@ impl PickerDelegate for CommandPaletteDelegate {
     ) -> Option<Self::ListItem> {
         let matching_command = self.matches.get(ix)?;
         let command = self.commands.get(matching_command.candidate_id)?;
+        let positions = matching_command.positions.clone();
+        let name = match &command.localized_name {
+            Some(localized_name) => {
+                let matched_localized_name = *localized_name == matching_command.string;
+                let (localized_positions, positions) = if matched_localized_name {
+                    (positions, Vec::new())
+                } else {
+                    (Vec::new(), positions)
+                };
+                h_flex()
+                    .gap_2()
+                    .child(HighlightedLabel::new(
+                        localized_name.clone(),
+                        localized_positions,
+                    ))
+                    .child(
+                        HighlightedLabel::new(command.name.clone(), positions)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .into_any_element()
+            }
+            None => HighlightedLabel::new(command.name.clone(), positions).into_any_element(),
+        };
         Some(
             ListItem::new(ix)
                 .inset(true)
@@ -425,10 +474,7 @@ impl PickerDelegate for CommandPaletteDelegate {
                         .w_full()
                         .py_px()
                         .justify_between()
-                        .child(HighlightedLabel::new(
-                            command.name.clone(),
-                            matching_command.positions.clone(),
-                        ))
+                        .child(name)
                         .children(KeyBinding::for_action_in(
                             &*command.action,
                             &self.previous_focus_handle,
@@ -438,6 +484,26 @@ impl PickerDelegate for CommandPaletteDelegate {
                 ),
         )
     }
+
+    fn documentation_aside(
+        &self,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<DocumentationAside> {
+        let matching_command = self.matches.get(self.selected_ix)?;
+        let action_name = self
+            .commands
+            .get(matching_command.candidate_id)?
+            .action
+            .name();
+        let documentation = cx.action_documentation().get(action_name)?;
+        let documentation = localization::action_documentation(action_name, documentation)?;
+        Some(DocumentationAside::new(
+            DocumentationSide::Left,
+            DocumentationEdge::Bottom,
+            Rc::new(move |_| Label::new(documentation.clone()).into_any_element()),
+        ))
+    }
 }
 
 pub fn humanize_action_name(name: &str) -> String {
@@ -468,6 +534,7 @@ impl std::fmt::Debug for Command {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Command")
             .field("name", &self.name)
+            .field("localized_name", &self.localized_name)
             .finish_non_exhaustive()
     }
 }
@@ -636,6 +703,72 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_localized_matches(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        let _language =
+            localization::set_language_for_test(localization::Language::SIMPLIFIED_CHINESE);
+        let project = Project::test(app_state.fs.clone(), [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let editor = cx.new_window_entity(|window, cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_text("abc", window, cx);
+            editor
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+            editor.update(cx, |editor, cx| window.focus(&editor.focus_handle(cx)))
+        });
+
+        cx.simulate_keystrokes("cmd-shift-p");
+
+        let palette = workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<CommandPalette>(cx)
+                .unwrap()
+                .read(cx)
+                .picker
+                .clone()
+        });
+
+        cx.simulate_input("退格");
+        palette.read_with(cx, |palette, _| {
+            assert_eq!(palette.delegate.matches[0].string, "编辑器: 退格");
+        });
+
+        palette.update_in(cx, |palette, window, cx| {
+            palette.set_query("editor: backspace", window, cx);
+        });
+        cx.run_until_parked();
+        palette.read_with(cx, |palette, _| {
+            let matching_command = &palette.delegate.matches[0];
+            assert_eq!(matching_command.string, "editor: backspace");
+            assert_eq!(
+                palette.delegate.commands[matching_command.candidate_id]
+                    .localized_name
+                    .as_deref(),
+                Some("编辑器: 退格")
+            );
+            let candidate_ids = palette
+                .delegate
+                .matches
+                .iter()
+                .map(|string_match| string_match.candidate_id)
+                .collect::<HashSet<_>>();
+            assert_eq!(candidate_ids.len(), palette.delegate.matches.len());
+        });
+
+        cx.simulate_keystrokes("enter");
+
+        workspace.update(cx, |workspace, cx| {
+            assert!(workspace.active_modal::<CommandPalette>(cx).is_none());
+            assert_eq!(editor.read(cx).text(cx), "ab")
+        });
+    }
+
     #[gpui::test]
     async fn test_go_to_line(cx: &mut TestAppContext) {
         let app_state = init_test(cx);
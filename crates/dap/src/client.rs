@@ -174,6 +174,11 @@ impl DebugAdapterClient {
         self.transport_delegate.pending_requests.lock().shutdown();
     }
 
+    /// The OS process id of the debug adapter, if Zed spawned it.
+    pub fn process_id(&self) -> Option<u32> {
+        self.transport_delegate.transport.lock().process_id()
+    }
+
     pub fn has_adapter_logs(&self) -> bool {
         self.transport_delegate.has_adapter_logs()
     }
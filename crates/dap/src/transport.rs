@@ -69,6 +69,7 @@ pub trait Transport: Send + Sync {
         )>,
     >;
     fn kill(&mut self);
+    fn process_id(&self) -> Option<u32>;
     #[cfg(any(test, feature = "test-support"))]
     fn as_fake(&self) -> &FakeTransport {
         unreachable!()
@@ -582,6 +583,10 @@ impl Transport for TcpTransport {
         }
     }
 
+    fn process_id(&self) -> Option<u32> {
+        self.process.lock().as_ref().map(|process| process.id())
+    }
+
     fn tcp_arguments(&self) -> Option<TcpArguments> {
         Some(TcpArguments {
             host: self.host,
@@ -710,6 +715,10 @@ impl Transport for StdioTransport {
         }
     }
 
+    fn process_id(&self) -> Option<u32> {
+        self.process.lock().as_ref().map(|process| process.id())
+    }
+
     fn connect(
         &mut self,
     ) -> Task<
@@ -1026,6 +1035,10 @@ impl Transport for FakeTransport {
         self.message_handler.take();
     }
 
+    fn process_id(&self) -> Option<u32> {
+        None
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     fn as_fake(&self) -> &FakeTransport {
         self
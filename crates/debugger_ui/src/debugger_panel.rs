@@ -1457,9 +1457,9 @@ impl Panel for DebugPanel {
         Some(IconName::Debug)
     }
 
-    fn icon_tooltip(&self, _window: &Window, cx: &App) -> Option<&'static str> {
+    fn icon_tooltip(&self, _window: &Window, cx: &App) -> Option<SharedString> {
         if DebuggerSettings::get_global(cx).button {
-            Some("Debug Panel")
+            Some(localization::t!("debugger_panel.tooltip", "Debug Panel"))
         } else {
             None
         }
@@ -895,10 +895,11 @@ impl Render for BufferDiagnosticsEditor {
                         ),
                 )
                 .when(self.summary.warning_count > 0, |div| {
-                    let label = match self.summary.warning_count {
-                        1 => "Show 1 warning".into(),
-                        warning_count => format!("Show {} warnings", warning_count),
-                    };
+                    let label = localization::t!(
+                        "diagnostics.show_warnings",
+                        "{count, plural, one {Show # warning} other {Show # warnings}}",
+                        count = self.summary.warning_count,
+                    );
 
                     div.child(
                         Button::new("diagnostics-show-warning-label", label).on_click(cx.listener(
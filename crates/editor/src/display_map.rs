@@ -121,6 +121,8 @@ pub struct DisplayMap {
     pub clip_at_line_ends: bool,
     pub(crate) masked: bool,
     pub(crate) diagnostics_max_severity: DiagnosticSeverity,
+    /// Whether word movements split CJK text into words.
+    pub(crate) cjk_word_segmentation: bool,
 }
 
 impl DisplayMap {
@@ -163,6 +165,7 @@ impl DisplayMap {
             inlay_highlights: Default::default(),
             clip_at_line_ends: false,
             masked: false,
+            cjk_word_segmentation: false,
         }
     }
 
@@ -191,6 +194,7 @@ impl DisplayMap {
             inlay_highlights: self.inlay_highlights.clone(),
             clip_at_line_ends: self.clip_at_line_ends,
             masked: self.masked,
+            cjk_word_segmentation: self.cjk_word_segmentation,
             fold_placeholder: self.fold_placeholder.clone(),
         }
     }
@@ -773,6 +777,7 @@ pub struct DisplaySnapshot {
     inlay_highlights: InlayHighlights,
     clip_at_line_ends: bool,
     masked: bool,
+    pub(crate) cjk_word_segmentation: bool,
     diagnostics_max_severity: DiagnosticSeverity,
     pub(crate) fold_placeholder: FoldPlaceholder,
 }
@@ -1825,7 +1825,7 @@ impl Editor {
         };
         let display_map = display_map.unwrap_or_else(|| {
             cx.new(|cx| {
-                DisplayMap::new(
+                let mut display_map = DisplayMap::new(
                     buffer.clone(),
                     style.font(),
                     font_size,
@@ -1835,7 +1835,10 @@ impl Editor {
                     fold_placeholder,
                     diagnostics_max_severity,
                     cx,
-                )
+                );
+                display_map.cjk_word_segmentation =
+                    EditorSettings::get_global(cx).cjk_word_segmentation;
+                display_map
             })
         });
 
@@ -3556,7 +3559,7 @@ impl Editor {
                 let position = display_map
                     .clip_point(position, Bias::Left)
                     .to_offset(&display_map, Bias::Left);
-                let (range, _) = buffer.surrounding_word(position, None);
+                let (range, _) = movement::surrounding_word(&display_map, position);
                 start = buffer.anchor_before(range.start);
                 end = buffer.anchor_before(range.end);
                 mode = SelectMode::Word(start..end);
@@ -3716,10 +3719,10 @@ impl Editor {
                         .to_offset(&display_map, Bias::Left);
                     let original_range = original_range.to_offset(buffer);
 
-                    let head_offset = if buffer.is_inside_word(offset, None)
+                    let head_offset = if movement::is_inside_word(&display_map, offset)
                         || original_range.contains(&offset)
                     {
-                        let (word_range, _) = buffer.surrounding_word(offset, None);
+                        let (word_range, _) = movement::surrounding_word(&display_map, offset);
                         if word_range.start < original_range.start {
                             word_range.start
                         } else {
@@ -12736,7 +12739,7 @@ impl Editor {
         self.change_selections(Default::default(), window, cx, |s| {
             s.move_with(|map, selection| {
                 let cursor = if selection.is_empty() {
-                    movement::left(map, selection.start)
+                    movement::visual_left(map, selection.start)
                 } else {
                     selection.start
                 };
@@ -12748,7 +12751,9 @@ impl Editor {
     pub fn select_left(&mut self, _: &SelectLeft, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
         self.change_selections(Default::default(), window, cx, |s| {
-            s.move_heads_with(|map, head, _| (movement::left(map, head), SelectionGoal::None));
+            s.move_heads_with(|map, head, _| {
+                (movement::visual_left(map, head), SelectionGoal::None)
+            });
         })
     }
 
@@ -12757,7 +12762,7 @@ impl Editor {
         self.change_selections(Default::default(), window, cx, |s| {
             s.move_with(|map, selection| {
                 let cursor = if selection.is_empty() {
-                    movement::right(map, selection.end)
+                    movement::visual_right(map, selection.end)
                 } else {
                     selection.end
                 };
@@ -12769,7 +12774,9 @@ impl Editor {
     pub fn select_right(&mut self, _: &SelectRight, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
         self.change_selections(Default::default(), window, cx, |s| {
-            s.move_heads_with(|map, head, _| (movement::right(map, head), SelectionGoal::None));
+            s.move_heads_with(|map, head, _| {
+                (movement::visual_right(map, head), SelectionGoal::None)
+            });
         })
     }
 
@@ -14277,8 +14284,8 @@ impl Editor {
                         start_offset + query_match.start()..start_offset + query_match.end();
 
                     if !select_next_state.wordwise
-                        || (!buffer.is_inside_word(offset_range.start, None)
-                            && !buffer.is_inside_word(offset_range.end, None))
+                        || (!movement::is_inside_word(display_map, offset_range.start)
+                            && !movement::is_inside_word(display_map, offset_range.end))
                     {
                         // TODO: This is n^2, because we might check all the selections
                         if !selections
@@ -14342,7 +14349,7 @@ impl Editor {
 
             if only_carets {
                 for selection in &mut selections {
-                    let (word_range, _) = buffer.surrounding_word(selection.start, None);
+                    let (word_range, _) = movement::surrounding_word(display_map, selection.start);
                     selection.start = word_range.start;
                     selection.end = word_range.end;
                     selection.goal = SelectionGoal::None;
@@ -14427,8 +14434,8 @@ impl Editor {
             };
 
             if !select_next_state.wordwise
-                || (!buffer.is_inside_word(offset_range.start, None)
-                    && !buffer.is_inside_word(offset_range.end, None))
+                || (!movement::is_inside_word(&display_map, offset_range.start)
+                    && !movement::is_inside_word(&display_map, offset_range.end))
             {
                 new_selections.push(offset_range.start..offset_range.end);
             }
@@ -14502,8 +14509,8 @@ impl Editor {
                         end_offset - query_match.end()..end_offset - query_match.start();
 
                     if !select_prev_state.wordwise
-                        || (!buffer.is_inside_word(offset_range.start, None)
-                            && !buffer.is_inside_word(offset_range.end, None))
+                        || (!movement::is_inside_word(&display_map, offset_range.start)
+                            && !movement::is_inside_word(&display_map, offset_range.end))
                     {
                         next_selected_range = Some(offset_range);
                         break;
@@ -14561,7 +14568,7 @@ impl Editor {
 
             if only_carets {
                 for selection in &mut selections {
-                    let (word_range, _) = buffer.surrounding_word(selection.start, None);
+                    let (word_range, _) = movement::surrounding_word(&display_map, selection.start);
                     selection.start = word_range.start;
                     selection.end = word_range.end;
                     selection.goal = SelectionGoal::None;
@@ -20798,6 +20805,11 @@ impl Editor {
             self.hide_mouse_mode = editor_settings.hide_mouse.unwrap_or_default();
         }
 
+        let cjk_word_segmentation = EditorSettings::get_global(cx).cjk_word_segmentation;
+        self.display_map.update(cx, |display_map, _| {
+            display_map.cjk_word_segmentation = cjk_word_segmentation;
+        });
+
         if old_cursor_shape != self.cursor_shape {
             cx.emit(EditorEvent::CursorShapeChanged);
         }
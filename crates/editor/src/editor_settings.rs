@@ -45,6 +45,7 @@ pub struct EditorSettings {
     pub excerpt_context_lines: u32,
     pub middle_click_paste: bool,
     pub double_click_in_multibuffer: DoubleClickInMultibuffer,
+    pub cjk_word_segmentation: bool,
     pub search_wrap: bool,
     pub search: SearchSettings,
     pub auto_signature_help: bool,
@@ -73,6 +74,10 @@ pub struct StatusBar {
     ///
     /// Default: true
     pub active_language_button: bool,
+    /// Whether to show the active buffer's encoding button in the status bar.
+    ///
+    /// Default: true
+    pub active_encoding_button: bool,
     /// Whether to show the cursor position button in the status bar.
     ///
     /// Default: true
@@ -210,6 +215,7 @@ impl Settings for EditorSettings {
             hover_popover_delay: editor.hover_popover_delay.unwrap(),
             status_bar: StatusBar {
                 active_language_button: status_bar.active_language_button.unwrap(),
+                active_encoding_button: status_bar.active_encoding_button.unwrap(),
                 cursor_position_button: status_bar.cursor_position_button.unwrap(),
             },
             toolbar: Toolbar {
@@ -262,6 +268,7 @@ impl Settings for EditorSettings {
             excerpt_context_lines: editor.excerpt_context_lines.unwrap(),
             middle_click_paste: editor.middle_click_paste.unwrap(),
             double_click_in_multibuffer: editor.double_click_in_multibuffer.unwrap(),
+            cjk_word_segmentation: editor.cjk_word_segmentation.unwrap(),
             search_wrap: editor.search_wrap.unwrap(),
             search: SearchSettings {
                 button: search.button.unwrap(),
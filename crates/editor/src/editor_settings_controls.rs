@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use gpui::{App, FontFeatures, FontWeight};
-use localization::{self, shared};
+use localization::{self, setting_title, shared};
 use project::project_settings::ProjectSettings;
 use settings::{EditableSettingControl, Settings, SettingsContent};
 use theme::{FontFamilyCache, FontFamilyName, ThemeSettings};
@@ -65,7 +65,7 @@ impl EditableSettingControl for BufferFontFamilyControl {
     type Value = SharedString;
 
     fn name(&self) -> SharedString {
-        shared("setting.buffer_font_family", "Buffer Font Family")
+        setting_title(&["buffer_font_family"], "Buffer Font Family")
     }
 
     fn read(cx: &App) -> Self::Value {
@@ -119,7 +119,7 @@ impl EditableSettingControl for BufferFontSizeControl {
     type Value = Pixels;
 
     fn name(&self) -> SharedString {
-        shared("setting.buffer_font_size", "Buffer Font Size")
+        setting_title(&["buffer_font_size"], "Buffer Font Size")
     }
 
     fn read(cx: &App) -> Self::Value {
@@ -158,7 +158,7 @@ impl EditableSettingControl for BufferFontWeightControl {
     type Value = FontWeight;
 
     fn name(&self) -> SharedString {
-        shared("setting.buffer_font_weight", "Buffer Font Weight")
+        setting_title(&["buffer_font_weight"], "Buffer Font Weight")
     }
 
     fn read(cx: &App) -> Self::Value {
@@ -206,7 +206,7 @@ impl EditableSettingControl for BufferFontLigaturesControl {
     type Value = bool;
 
     fn name(&self) -> SharedString {
-        shared("setting.buffer_font_ligatures", "Buffer Font Ligatures")
+        setting_title(&["buffer_font_features", "calt"], "Buffer Font Ligatures")
     }
 
     fn read(cx: &App) -> Self::Value {
@@ -266,7 +266,7 @@ impl EditableSettingControl for InlineGitBlameControl {
     type Value = bool;
 
     fn name(&self) -> SharedString {
-        shared("setting.inline_git_blame", "Inline Git Blame")
+        setting_title(&["git", "inline_blame", "enabled"], "Inline Git Blame")
     }
 
     fn read(cx: &App) -> Self::Value {
@@ -312,7 +312,7 @@ impl EditableSettingControl for LineNumbersControl {
     type Value = bool;
 
     fn name(&self) -> SharedString {
-        shared("setting.line_numbers", "Line Numbers")
+        setting_title(&["gutter", "line_numbers"], "Line Numbers")
     }
 
     fn read(cx: &App) -> Self::Value {
@@ -353,7 +353,7 @@ impl EditableSettingControl for RelativeLineNumbersControl {
     type Value = bool;
 
     fn name(&self) -> SharedString {
-        shared("setting.relative_line_numbers", "Relative Line Numbers")
+        setting_title(&["relative_line_numbers"], "Relative Line Numbers")
     }
 
     fn read(cx: &App) -> Self::Value {
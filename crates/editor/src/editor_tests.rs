@@ -2076,6 +2076,57 @@ fn test_prev_next_word_bounds_with_soft_wrap(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_cjk_word_segmentation(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state("ˇ我们使用中文编辑器，调用get函数");
+    cx.update_editor(|e, window, cx| e.move_to_next_word_end(&MoveToNextWordEnd, window, cx));
+    cx.assert_editor_state("我们ˇ使用中文编辑器，调用get函数");
+    cx.update_editor(|e, window, cx| e.move_to_next_word_end(&MoveToNextWordEnd, window, cx));
+    cx.assert_editor_state("我们使用ˇ中文编辑器，调用get函数");
+
+    cx.set_state("我们使用中文编辑器，调用get函数ˇ");
+    cx.update_editor(|e, window, cx| {
+        e.move_to_previous_word_start(&MoveToPreviousWordStart, window, cx)
+    });
+    cx.assert_editor_state("我们使用中文编辑器，调用getˇ函数");
+    cx.update_editor(|e, window, cx| {
+        e.move_to_previous_word_start(&MoveToPreviousWordStart, window, cx)
+    });
+    cx.assert_editor_state("我们使用中文编辑器，调用ˇget函数");
+    cx.update_editor(|e, window, cx| {
+        e.move_to_previous_word_start(&MoveToPreviousWordStart, window, cx)
+    });
+    cx.assert_editor_state("我们使用中文编辑器，ˇ调用get函数");
+
+    cx.set_state("我们使用中文编ˇ辑器");
+    cx.update_editor(|e, window, cx| e.select_next(&SelectNext::default(), window, cx))
+        .unwrap();
+    cx.assert_editor_state("我们使用中文«编辑器ˇ»");
+
+    cx.set_state("ˇ使用 使用中文 使用");
+    cx.update_editor(|e, window, cx| e.select_next(&SelectNext::default(), window, cx))
+        .unwrap();
+    cx.update_editor(|e, window, cx| e.select_next(&SelectNext::default(), window, cx))
+        .unwrap();
+    cx.update_editor(|e, window, cx| e.select_next(&SelectNext::default(), window, cx))
+        .unwrap();
+    cx.assert_editor_state("«使用ˇ» «使用ˇ»中文 «使用ˇ»");
+
+    cx.update(|_, cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings.editor.cjk_word_segmentation = Some(false);
+            });
+        });
+    });
+    cx.set_state("ˇ我们使用中文编辑器");
+    cx.update_editor(|e, window, cx| e.move_to_next_word_end(&MoveToNextWordEnd, window, cx));
+    cx.assert_editor_state("我们使用中文编辑器ˇ");
+}
+
 #[gpui::test]
 async fn test_move_start_of_paragraph_end_of_paragraph(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -1602,9 +1602,16 @@ impl EditorElement {
                         [cursor_position.row().minus(visible_display_row_range.start) as usize];
                     let cursor_column = cursor_position.column() as usize;
 
-                    let cursor_character_x = cursor_row_layout.x_for_index(cursor_column);
-                    let mut block_width =
-                        cursor_row_layout.x_for_index(cursor_column + 1) - cursor_character_x;
+                    let mut cursor_character_x = cursor_row_layout.x_for_index(cursor_column);
+                    let next_character_x = cursor_row_layout.x_for_index(cursor_column + 1);
+                    let mut block_width = (next_character_x - cursor_character_x).abs();
+                    // Right-to-left characters extend to the left of their caret position.
+                    if matches!(
+                        selection.cursor_shape,
+                        CursorShape::Block | CursorShape::Hollow
+                    ) {
+                        cursor_character_x = cursor_character_x.min(next_character_x);
+                    }
                     if block_width == Pixels::ZERO {
                         block_width = em_advance;
                     }
@@ -6734,6 +6741,59 @@ impl EditorElement {
                     ..cmp::min(range.end.row().next_row(), end_row)
             };
 
+            // In bidirectional text, a range can cover several disjoint spans of a line,
+            // which are painted as separate highlights.
+            let row_spans = row_range
+                .iter_rows()
+                .map(|row| {
+                    let line_layout =
+                        &layout.position_map.line_layouts[row.minus(start_row) as usize];
+                    let start = if row == range.start.row() {
+                        range.start.column() as usize
+                    } else {
+                        0
+                    };
+                    let end = if row == range.end.row() {
+                        range.end.column() as usize
+                    } else {
+                        line_layout.len
+                    };
+                    line_layout.x_ranges_for_range(start..end)
+                })
+                .collect::<Vec<_>>();
+            if row_spans.iter().any(|spans| spans.len() > 1) {
+                for (row, mut spans) in row_range.iter_rows().zip(row_spans) {
+                    if row != range.end.row() {
+                        let line_width =
+                            layout.position_map.line_layouts[row.minus(start_row) as usize].width;
+                        spans.push(line_width..line_width + line_end_overshoot);
+                    }
+                    let start_y = layout.content_origin.y
+                        + row.as_f32() * layout.position_map.line_height
+                        - layout.position_map.scroll_pixel_position.y;
+                    let origin_x =
+                        layout.content_origin.x - layout.position_map.scroll_pixel_position.x;
+                    for span in spans {
+                        HighlightedRange {
+                            color,
+                            line_height: layout.position_map.line_height,
+                            corner_radius,
+                            start_y,
+                            lines: vec![HighlightedRangeLine {
+                                start_x: origin_x + span.start,
+                                end_x: origin_x + span.end,
+                            }],
+                        }
+                        .paint(
+                            fill,
+                            layout.position_map.text_hitbox.bounds,
+                            window,
+                        );
+                    }
+                }
+                return;
+            }
+
             let highlighted_range = HighlightedRange {
                 color,
                 line_height: layout.position_map.line_height,
@@ -8123,6 +8183,50 @@ impl LineWithInvisibles {
         fragment_start_x
     }
 
+    /// Returns the horizontal spans covered by the text in `range`, from left to right.
+    /// Text mixing left-to-right and right-to-left runs may cover several spans.
+    pub fn x_ranges_for_range(&self, range: Range<usize>) -> SmallVec<[Range<Pixels>; 1]> {
+        let mut ranges = SmallVec::<[Range<Pixels>; 1]>::new();
+        let mut fragment_start_x = Pixels::ZERO;
+        let mut fragment_start_index = 0;
+
+        for fragment in &self.fragments {
+            let (fragment_len, fragment_width) = match fragment {
+                LineFragment::Text(shaped_line) => (shaped_line.len, shaped_line.width),
+                LineFragment::Element { len, size, .. } => (*len, size.width),
+            };
+            let fragment_end_index = fragment_start_index + fragment_len;
+            let start = range.start.max(fragment_start_index);
+            let end = range.end.min(fragment_end_index);
+            if start < end {
+                let fragment_ranges = match fragment {
+                    LineFragment::Text(shaped_line) => shaped_line.x_ranges_for_range(
+                        start - fragment_start_index..end - fragment_start_index,
+                    ),
+                    LineFragment::Element { .. } => smallvec![Pixels::ZERO..fragment_width],
+                };
+                for fragment_range in fragment_ranges {
+                    let fragment_range = fragment_start_x + fragment_range.start
+                        ..fragment_start_x + fragment_range.end;
+                    match ranges.last_mut() {
+                        Some(last) if fragment_range.start <= last.end => {
+                            last.end = last.end.max(fragment_range.end)
+                        }
+                        _ => ranges.push(fragment_range),
+                    }
+                }
+            }
+            fragment_start_x += fragment_width;
+            fragment_start_index = fragment_end_index;
+        }
+
+        if ranges.is_empty() {
+            let x = self.x_for_index(range.start);
+            ranges.push(x..x);
+        }
+        ranges
+    }
+
     pub fn index_for_x(&self, x: Pixels) -> Option<usize> {
         let mut fragment_start_x = Pixels::ZERO;
         let mut fragment_start_index = 0;
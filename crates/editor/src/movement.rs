@@ -3,8 +3,8 @@
 
 use super::{Bias, DisplayPoint, DisplaySnapshot, SelectionGoal, ToDisplayPoint};
 use crate::{DisplayRow, EditorStyle, ToOffset, ToPoint, scroll::ScrollAnchor};
-use gpui::{Pixels, WindowTextSystem};
-use language::{CharClassifier, Point};
+use gpui::{BidiLine, Pixels, WindowTextSystem};
+use language::{CharClassifier, CharKind, Point, is_cjk, word_boundaries};
 use multi_buffer::{MultiBufferRow, MultiBufferSnapshot};
 use serde::Deserialize;
 use workspace::searchable::Direction;
@@ -77,6 +77,56 @@ pub fn saturating_right(map: &DisplaySnapshot, mut point: DisplayPoint) -> Displ
     map.clip_point(point, Bias::Right)
 }
 
+/// Returns the caret position displayed to the left of the current point, which
+/// follows it in right-to-left text. Wraps to the right edge of the previous line
+/// if that point is at the left edge of its line.
+pub fn visual_left(map: &DisplaySnapshot, mut point: DisplayPoint) -> DisplayPoint {
+    let line = map.line(point.row());
+    let bidi = BidiLine::new(&line);
+    if bidi.has_rtl() {
+        if let Some(column) = bidi.caret_left(&line, point.column() as usize) {
+            *point.column_mut() = column as u32;
+            return map.clip_point(point, Bias::Left);
+        }
+    } else if point.column() > 0 {
+        return left(map, point);
+    }
+
+    if point.row().0 == 0 {
+        return point;
+    }
+    *point.row_mut() -= 1;
+    let line = map.line(point.row());
+    let carets = BidiLine::new(&line).visual_carets(&line);
+    *point.column_mut() = carets.last().copied().unwrap_or(line.len()) as u32;
+    map.clip_point(point, Bias::Left)
+}
+
+/// Returns the caret position displayed to the right of the current point, which
+/// precedes it in right-to-left text. Wraps to the left edge of the next line if
+/// that point is at the right edge of its line.
+pub fn visual_right(map: &DisplaySnapshot, mut point: DisplayPoint) -> DisplayPoint {
+    let line = map.line(point.row());
+    let bidi = BidiLine::new(&line);
+    if bidi.has_rtl() {
+        if let Some(column) = bidi.caret_right(&line, point.column() as usize) {
+            *point.column_mut() = column as u32;
+            return map.clip_point(point, Bias::Right);
+        }
+    } else if point.column() < map.line_len(point.row()) {
+        return right(map, point);
+    }
+
+    if point.row() >= map.max_point().row() {
+        return point;
+    }
+    *point.row_mut() += 1;
+    let line = map.line(point.row());
+    let carets = BidiLine::new(&line).visual_carets(&line);
+    *point.column_mut() = carets.first().copied().unwrap_or(0) as u32;
+    map.clip_point(point, Bias::Right)
+}
+
 /// Returns a display point for the preceding displayed line (which might be a soft-wrapped line).
 pub fn up(
     map: &DisplaySnapshot,
@@ -263,9 +313,13 @@ pub fn line_end(
 pub fn previous_word_start(map: &DisplaySnapshot, point: DisplayPoint) -> DisplayPoint {
     let raw_point = point.to_point(map);
     let classifier = map.buffer_snapshot.char_classifier_at(raw_point);
+    let mut cjk_words = CjkWordBoundaries::backward(map, raw_point.to_offset(&map.buffer_snapshot));
 
     let mut is_first_iteration = true;
     find_preceding_boundary_display_point(map, point, FindRange::MultiLine, |left, right| {
+        if cjk_words.step(left, right) {
+            return true;
+        }
         // Make alt-left skip punctuation to respect VSCode behaviour. For example: hello.| goes to |hello.
         if is_first_iteration
             && classifier.is_punctuation(right)
@@ -287,9 +341,11 @@ pub fn previous_word_start(map: &DisplaySnapshot, point: DisplayPoint) -> Displa
 pub fn previous_word_start_or_newline(map: &DisplaySnapshot, point: DisplayPoint) -> DisplayPoint {
     let raw_point = point.to_point(map);
     let classifier = map.buffer_snapshot.char_classifier_at(raw_point);
+    let mut cjk_words = CjkWordBoundaries::backward(map, raw_point.to_offset(&map.buffer_snapshot));
 
     find_preceding_boundary_display_point(map, point, FindRange::MultiLine, |left, right| {
-        (classifier.kind(left) != classifier.kind(right) && !classifier.is_whitespace(right))
+        cjk_words.step(left, right)
+            || (classifier.kind(left) != classifier.kind(right) && !classifier.is_whitespace(right))
             || left == '\n'
             || right == '\n'
     })
@@ -422,8 +478,12 @@ pub fn is_subword_start(left: char, right: char, classifier: &CharClassifier) ->
 pub fn next_word_end(map: &DisplaySnapshot, point: DisplayPoint) -> DisplayPoint {
     let raw_point = point.to_point(map);
     let classifier = map.buffer_snapshot.char_classifier_at(raw_point);
+    let mut cjk_words = CjkWordBoundaries::forward(map, point.to_offset(map, Bias::Right));
     let mut is_first_iteration = true;
     find_boundary(map, point, FindRange::MultiLine, |left, right| {
+        if cjk_words.step(left, right) {
+            return true;
+        }
         // Make alt-right skip punctuation to respect VSCode behaviour. For example: |.hello goes to .hello|
         if is_first_iteration
             && classifier.is_punctuation(left)
@@ -445,15 +505,17 @@ pub fn next_word_end(map: &DisplaySnapshot, point: DisplayPoint) -> DisplayPoint
 pub fn next_word_end_or_newline(map: &DisplaySnapshot, point: DisplayPoint) -> DisplayPoint {
     let raw_point = point.to_point(map);
     let classifier = map.buffer_snapshot.char_classifier_at(raw_point);
+    let mut cjk_words = CjkWordBoundaries::forward(map, point.to_offset(map, Bias::Right));
 
     let mut on_starting_row = true;
     find_boundary(map, point, FindRange::MultiLine, |left, right| {
         if left == '\n' {
             on_starting_row = false;
         }
-        (classifier.kind(left) != classifier.kind(right)
-            && ((on_starting_row && !left.is_whitespace())
-                || (!on_starting_row && !right.is_whitespace())))
+        cjk_words.step(left, right)
+            || (classifier.kind(left) != classifier.kind(right)
+                && ((on_starting_row && !left.is_whitespace())
+                    || (!on_starting_row && !right.is_whitespace())))
             || right == '\n'
     })
 }
@@ -800,6 +862,142 @@ pub fn find_boundary_exclusive(
     find_boundary_point(map, from, find_range, is_boundary, true)
 }
 
+/// How many characters on either side of a position are considered when splitting the
+/// CJK text around it into words.
+const MAX_CJK_WORD_CONTEXT: usize = 64;
+
+/// Finds the boundaries between words of CJK text for the predicates passed to
+/// [`find_boundary`] and [`find_preceding_boundary_display_point`], which can't tell
+/// them apart by the characters on either side alone.
+///
+/// Finds nothing unless `cjk_word_segmentation` is enabled in the editor settings.
+pub struct CjkWordBoundaries<'a> {
+    buffer: &'a MultiBufferSnapshot,
+    enabled: bool,
+    offset: usize,
+    reversed: bool,
+    run: Range<usize>,
+    boundaries: Vec<usize>,
+}
+
+impl<'a> CjkWordBoundaries<'a> {
+    /// For a predicate scanning forward from the given buffer offset.
+    pub fn forward(map: &'a DisplaySnapshot, offset: usize) -> Self {
+        Self::new(map, offset, false)
+    }
+
+    /// For a predicate scanning backward from the given buffer offset.
+    pub fn backward(map: &'a DisplaySnapshot, offset: usize) -> Self {
+        Self::new(map, offset, true)
+    }
+
+    fn new(map: &'a DisplaySnapshot, offset: usize, reversed: bool) -> Self {
+        Self {
+            buffer: &map.buffer_snapshot,
+            enabled: map.cjk_word_segmentation,
+            offset,
+            reversed,
+            run: 0..0,
+            boundaries: Vec::new(),
+        }
+    }
+
+    /// Steps over the characters on either side of the predicate's next candidate
+    /// boundary, returning whether they belong to different words of CJK text. Must be
+    /// called every time the predicate is.
+    pub fn step(&mut self, left: char, right: char) -> bool {
+        if self.reversed {
+            self.offset -= right.len_utf8();
+        } else {
+            self.offset += left.len_utf8();
+        }
+
+        if !self.enabled
+            || !(is_cjk(left) || is_cjk(right))
+            || !is_word_char(left)
+            || !is_word_char(right)
+        {
+            return false;
+        }
+        self.is_boundary(self.offset)
+    }
+
+    fn is_boundary(&mut self, offset: usize) -> bool {
+        if offset <= self.run.start || offset >= self.run.end {
+            self.split_run_around(offset);
+        }
+        self.boundaries.binary_search(&offset).is_ok()
+    }
+
+    fn split_run_around(&mut self, offset: usize) {
+        let start = offset
+            - self
+                .buffer
+                .reversed_chars_at(offset)
+                .take(MAX_CJK_WORD_CONTEXT)
+                .take_while(|ch| is_word_char(*ch))
+                .map(char::len_utf8)
+                .sum::<usize>();
+        let end = offset
+            + self
+                .buffer
+                .chars_at(offset)
+                .take(MAX_CJK_WORD_CONTEXT)
+                .take_while(|ch| is_word_char(*ch))
+                .map(char::len_utf8)
+                .sum::<usize>();
+        let text = self.buffer.text_for_range(start..end).collect::<String>();
+        self.boundaries = word_boundaries(&text)
+            .into_iter()
+            .map(|boundary| start + boundary)
+            .collect();
+        self.run = start..end;
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// Returns the range and character kind of the word surrounding the given offset, like
+/// [`MultiBufferSnapshot::surrounding_word`], but only a single word of CJK text.
+pub fn surrounding_word(map: &DisplaySnapshot, offset: usize) -> (Range<usize>, Option<CharKind>) {
+    let (range, kind) = map.buffer_snapshot.surrounding_word(offset, None);
+    if !map.cjk_word_segmentation || kind != Some(CharKind::Word) {
+        return (range, kind);
+    }
+
+    let mut cjk_words = CjkWordBoundaries::forward(map, offset);
+    cjk_words.split_run_around(offset);
+    let start = cjk_words
+        .boundaries
+        .iter()
+        .rev()
+        .find(|boundary| **boundary <= offset)
+        .map_or(range.start, |boundary| range.start.max(*boundary));
+    let end = cjk_words
+        .boundaries
+        .iter()
+        .find(|boundary| **boundary > offset)
+        .map_or(range.end, |boundary| range.end.min(*boundary));
+    (start..end, kind)
+}
+
+/// Returns whether the given offset is between two characters of the same word, like
+/// [`MultiBufferSnapshot::is_inside_word`], treating each word of CJK text separately.
+pub fn is_inside_word(map: &DisplaySnapshot, offset: usize) -> bool {
+    if !map.buffer_snapshot.is_inside_word(offset, None) {
+        return false;
+    }
+    let (Some(left), Some(right)) = (
+        map.buffer_snapshot.reversed_chars_at(offset).next(),
+        map.buffer_snapshot.chars_at(offset).next(),
+    ) else {
+        return false;
+    };
+    !CjkWordBoundaries::forward(map, offset - left.len_utf8()).step(left, right)
+}
+
 /// Returns an iterator over the characters following a given offset in the [`DisplaySnapshot`].
 /// The returned value also contains a range of the start/end of a returned character in
 /// the [`DisplaySnapshot`]. The offsets are relative to the start of a buffer.
@@ -1168,6 +1366,51 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    fn test_visual_left_and_right(cx: &mut gpui::App) {
+        init_test(cx);
+
+        // "ab אב" is displayed as "ab בא", so the end of the line is drawn between the
+        // space and the last Hebrew letter.
+        let (snapshot, _) = marked_display_snapshot("ab \u{5d0}\u{5d1}\ncd", cx);
+        let point = |row, column| DisplayPoint::new(DisplayRow(row), column);
+        let expected = [
+            point(0, 0),
+            point(0, 1),
+            point(0, 2),
+            point(0, 7),
+            point(0, 5),
+            point(0, 3),
+            point(1, 0),
+            point(1, 1),
+            point(1, 2),
+        ];
+
+        let mut position = expected[0];
+        let mut visited = vec![position];
+        loop {
+            let next = visual_right(&snapshot, position);
+            if next == position {
+                break;
+            }
+            visited.push(next);
+            position = next;
+        }
+        assert_eq!(visited, expected);
+
+        let mut visited = vec![position];
+        loop {
+            let next = visual_left(&snapshot, position);
+            if next == position {
+                break;
+            }
+            visited.push(next);
+            position = next;
+        }
+        visited.reverse();
+        assert_eq!(visited, expected);
+    }
+
     #[gpui::test]
     async fn test_move_up_and_down_with_excerpts(cx: &mut gpui::TestAppContext) {
         cx.update(|cx| {
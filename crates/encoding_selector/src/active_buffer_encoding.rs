@@ -0,0 +1,138 @@
+use editor::{Editor, EditorSettings};
+use gpui::{
+    Action as _, Context, Corner, Entity, IntoElement, ParentElement, Render, Subscription, Window,
+    div,
+};
+use language::Encoding;
+use localization::t;
+use settings::Settings as _;
+use ui::{
+    Button, ButtonCommon, Color, ContextMenu, FluentBuilder, LabelSize, PopoverMenu, Tooltip,
+};
+use workspace::{StatusItemView, item::ItemHandle};
+
+use crate::{ReopenWithEncoding, SaveWithEncoding};
+
+pub struct ActiveBufferEncoding {
+    encoding: Option<Encoding>,
+    encoding_guessed: bool,
+    _observe_active_editor: Option<Subscription>,
+    _observe_active_buffer: Option<Subscription>,
+}
+
+impl ActiveBufferEncoding {
+    pub fn new() -> Self {
+        Self {
+            encoding: None,
+            encoding_guessed: false,
+            _observe_active_editor: None,
+            _observe_active_buffer: None,
+        }
+    }
+
+    fn update_encoding(&mut self, editor: Entity<Editor>, _: &mut Window, cx: &mut Context<Self>) {
+        self.encoding = None;
+        self.encoding_guessed = false;
+        self._observe_active_buffer = None;
+
+        let editor = editor.read(cx);
+        if let Some((_, buffer, _)) = editor.active_excerpt(cx)
+            && buffer.read(cx).file().is_some()
+        {
+            self.encoding = Some(buffer.read(cx).encoding());
+            self.encoding_guessed = buffer.read(cx).encoding_guessed();
+            self._observe_active_buffer = Some(cx.observe(&buffer, |this, buffer, cx| {
+                this.encoding = Some(buffer.read(cx).encoding());
+                this.encoding_guessed = buffer.read(cx).encoding_guessed();
+                cx.notify();
+            }));
+        }
+
+        cx.notify();
+    }
+}
+
+impl Default for ActiveBufferEncoding {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for ActiveBufferEncoding {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if !EditorSettings::get_global(cx)
+            .status_bar
+            .active_encoding_button
+        {
+            return div();
+        }
+
+        let guessed = self.encoding_guessed;
+        div().when_some(self.encoding, |el, encoding| {
+            // A guessed encoding may have garbled the text, which saving would then write
+            // back to the file, so make it stand out until the user confirms or changes it.
+            let (label, tooltip) = if guessed {
+                (
+                    t!(
+                        "encoding_selector.guessed",
+                        "{encoding} (Guessed)",
+                        encoding = encoding.to_string()
+                    ),
+                    t!(
+                        "encoding_selector.guessed_tooltip",
+                        "The encoding was guessed from the file's contents. If the text looks wrong, reopen the file with the right encoding."
+                    ),
+                )
+            } else {
+                (
+                    encoding.to_string().into(),
+                    t!("encoding_selector.tooltip", "Change Encoding"),
+                )
+            };
+            el.child(
+                PopoverMenu::new("change-encoding")
+                    .trigger_with_tooltip(
+                        Button::new("change-encoding-button", label)
+                            .label_size(LabelSize::Small)
+                            .when(guessed, |button| button.color(Color::Warning)),
+                        Tooltip::text(tooltip),
+                    )
+                    .menu(|window, cx| {
+                        Some(ContextMenu::build(window, cx, |menu, _, _| {
+                            menu.action(
+                                t!("encoding_selector.reopen", "Reopen with Encoding"),
+                                ReopenWithEncoding.boxed_clone(),
+                            )
+                            .action(
+                                t!("encoding_selector.save", "Save with Encoding"),
+                                SaveWithEncoding.boxed_clone(),
+                            )
+                        }))
+                    })
+                    .anchor(Corner::BottomRight),
+            )
+        })
+    }
+}
+
+impl StatusItemView for ActiveBufferEncoding {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(editor) = active_pane_item.and_then(|item| item.downcast::<Editor>()) {
+            self._observe_active_editor =
+                Some(cx.observe_in(&editor, window, Self::update_encoding));
+            self.update_encoding(editor, window, cx);
+        } else {
+            self.encoding = None;
+            self.encoding_guessed = false;
+            self._observe_active_editor = None;
+            self._observe_active_buffer = None;
+        }
+
+        cx.notify();
+    }
+}
@@ -0,0 +1,338 @@
+mod active_buffer_encoding;
+
+pub use active_buffer_encoding::ActiveBufferEncoding;
+use anyhow::Result;
+use editor::Editor;
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, ParentElement,
+    PromptLevel, Render, Styled, Task, WeakEntity, Window, actions,
+};
+use language::{Buffer, Encoding};
+use localization::t;
+use picker::{Picker, PickerDelegate};
+use project::Project;
+use std::sync::Arc;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr as _};
+
+actions!(
+    encoding_selector,
+    [
+        /// Reopens the active file, decoding it with a different encoding.
+        ReopenWithEncoding,
+        /// Saves the active file with a different encoding.
+        SaveWithEncoding
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(EncodingSelector::register).detach();
+}
+
+/// What the encoding selector does with the chosen encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Load the file again, decoding it with the encoding.
+    Reopen,
+    /// Save the buffer, encoding it with the encoding.
+    Save,
+}
+
+pub struct EncodingSelector {
+    picker: Entity<Picker<EncodingSelectorDelegate>>,
+}
+
+impl EncodingSelector {
+    fn register(
+        workspace: &mut Workspace,
+        _window: Option<&mut Window>,
+        _: &mut Context<Workspace>,
+    ) {
+        workspace.register_action(move |workspace, _: &ReopenWithEncoding, window, cx| {
+            Self::toggle(workspace, Mode::Reopen, window, cx);
+        });
+        workspace.register_action(move |workspace, _: &SaveWithEncoding, window, cx| {
+            Self::toggle(workspace, Mode::Save, window, cx);
+        });
+    }
+
+    pub fn toggle(
+        workspace: &mut Workspace,
+        mode: Mode,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Option<()> {
+        let (_, buffer, _) = workspace
+            .active_item(cx)?
+            .act_as::<Editor>(cx)?
+            .read(cx)
+            .active_excerpt(cx)?;
+        buffer.read(cx).file()?;
+        let project = workspace.project().clone();
+
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            EncodingSelector::new(buffer, project, mode, window, cx)
+        });
+        Some(())
+    }
+
+    fn new(
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        mode: Mode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate =
+            EncodingSelectorDelegate::new(cx.entity().downgrade(), buffer, project, mode, cx);
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for EncodingSelector {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("EncodingSelector")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl Focusable for EncodingSelector {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for EncodingSelector {}
+impl ModalView for EncodingSelector {}
+
+pub struct EncodingSelectorDelegate {
+    encoding_selector: WeakEntity<EncodingSelector>,
+    buffer: Entity<Buffer>,
+    project: Entity<Project>,
+    mode: Mode,
+    encoding: Encoding,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl EncodingSelectorDelegate {
+    fn new(
+        encoding_selector: WeakEntity<EncodingSelector>,
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        mode: Mode,
+        cx: &App,
+    ) -> Self {
+        let encoding = buffer.read(cx).encoding();
+        let candidates = Encoding::ALL
+            .iter()
+            .enumerate()
+            .map(|(candidate_id, encoding)| {
+                StringMatchCandidate::new(candidate_id, &encoding.to_string())
+            })
+            .collect::<Vec<_>>();
+        Self {
+            encoding_selector,
+            buffer,
+            project,
+            mode,
+            encoding,
+            candidates,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+
+    fn reopen(&self, encoding: Encoding, window: &mut Window, cx: &mut App) -> Task<Result<()>> {
+        let buffer = self.buffer.clone();
+        let project = self.project.clone();
+        let confirmation = buffer.read(cx).is_dirty().then(|| {
+            window.prompt(
+                PromptLevel::Warning,
+                &t!(
+                    "encoding_selector.discard_changes",
+                    "Discard unsaved changes and reopen the file?"
+                ),
+                None,
+                &[
+                    t!("encoding_selector.reopen_button", "Reopen").as_ref(),
+                    t!("encoding_selector.cancel_button", "Cancel").as_ref(),
+                ],
+                cx,
+            )
+        });
+
+        cx.spawn(async move |cx| {
+            if let Some(confirmation) = confirmation
+                && confirmation.await? != 0
+            {
+                return Ok(());
+            }
+            project
+                .update(cx, |project, cx| {
+                    project.reopen_buffer_with_encoding(buffer, encoding, cx)
+                })?
+                .await
+        })
+    }
+
+    fn save(&self, encoding: Encoding, cx: &mut App) -> Task<Result<()>> {
+        let buffer = self.buffer.clone();
+        let previous_encoding = self.encoding;
+        buffer.update(cx, |buffer, cx| buffer.set_encoding(encoding, cx));
+        let save = self
+            .project
+            .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx));
+
+        cx.spawn(async move |cx| {
+            if let Err(error) = save.await {
+                buffer.update(cx, |buffer, cx| buffer.set_encoding(previous_encoding, cx))?;
+                return Err(error);
+            }
+            Ok(())
+        })
+    }
+}
+
+impl PickerDelegate for EncodingSelectorDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        match self.mode {
+            Mode::Reopen => t!(
+                "encoding_selector.reopen_placeholder",
+                "Reopen with encoding…"
+            ),
+            Mode::Save => t!("encoding_selector.save_placeholder", "Save with encoding…"),
+        }
+        .as_ref()
+        .into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(mat) = self.matches.get(self.selected_index) {
+            let encoding = Encoding::ALL[mat.candidate_id];
+            let (task, message) = match self.mode {
+                Mode::Reopen => (
+                    self.reopen(encoding, window, cx),
+                    t!(
+                        "encoding_selector.reopen_failed",
+                        "Failed to reopen with encoding"
+                    ),
+                ),
+                Mode::Save => (
+                    self.save(encoding, cx),
+                    t!(
+                        "encoding_selector.save_failed",
+                        "Failed to save with encoding"
+                    ),
+                ),
+            };
+            task.detach_and_prompt_err(&message, window, cx, |_, _, _| None);
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.encoding_selector
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> gpui::Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                let selected_encoding = query.is_empty().then_some(delegate.encoding);
+                delegate.matches = matches;
+                delegate.selected_index = selected_encoding
+                    .and_then(|encoding| {
+                        delegate
+                            .matches
+                            .iter()
+                            .position(|mat| Encoding::ALL[mat.candidate_id] == encoding)
+                    })
+                    .unwrap_or(0);
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let mut list_item = ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected)
+            .child(HighlightedLabel::new(
+                mat.string.clone(),
+                mat.positions.clone(),
+            ));
+
+        if Encoding::ALL[mat.candidate_id] == self.encoding {
+            list_item = list_item.end_slot(Icon::new(IconName::Check).color(Color::Muted));
+        }
+
+        Some(list_item)
+    }
+}
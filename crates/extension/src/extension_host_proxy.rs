@@ -26,6 +26,7 @@ pub struct ExtensionHostProxy {
     language_proxy: RwLock<Option<Arc<dyn ExtensionLanguageProxy>>>,
     language_server_proxy: RwLock<Option<Arc<dyn ExtensionLanguageServerProxy>>>,
     snippet_proxy: RwLock<Option<Arc<dyn ExtensionSnippetProxy>>>,
+    translation_proxy: RwLock<Option<Arc<dyn ExtensionTranslationProxy>>>,
     slash_command_proxy: RwLock<Option<Arc<dyn ExtensionSlashCommandProxy>>>,
     context_server_proxy: RwLock<Option<Arc<dyn ExtensionContextServerProxy>>>,
     debug_adapter_provider_proxy: RwLock<Option<Arc<dyn ExtensionDebugAdapterProviderProxy>>>,
@@ -51,6 +52,7 @@ impl ExtensionHostProxy {
             language_proxy: RwLock::default(),
             language_server_proxy: RwLock::default(),
             snippet_proxy: RwLock::default(),
+            translation_proxy: RwLock::default(),
             slash_command_proxy: RwLock::default(),
             context_server_proxy: RwLock::default(),
             debug_adapter_provider_proxy: RwLock::default(),
@@ -77,6 +79,10 @@ impl ExtensionHostProxy {
         self.snippet_proxy.write().replace(Arc::new(proxy));
     }
 
+    pub fn register_translation_proxy(&self, proxy: impl ExtensionTranslationProxy) {
+        self.translation_proxy.write().replace(Arc::new(proxy));
+    }
+
     pub fn register_slash_command_proxy(&self, proxy: impl ExtensionSlashCommandProxy) {
         self.slash_command_proxy.write().replace(Arc::new(proxy));
     }
@@ -340,6 +346,52 @@ impl ExtensionSnippetProxy for ExtensionHostProxy {
     }
 }
 
+pub trait ExtensionTranslationProxy: Send + Sync + 'static {
+    fn load_translations(
+        &self,
+        extension_id: Arc<str>,
+        language: Arc<str>,
+        catalog_path: PathBuf,
+        fs: Arc<dyn Fs>,
+    ) -> Task<Result<()>>;
+
+    fn remove_translations(&self, extension_ids: &[Arc<str>]);
+
+    fn reload_translations(&self, cx: &mut App);
+}
+
+impl ExtensionTranslationProxy for ExtensionHostProxy {
+    fn load_translations(
+        &self,
+        extension_id: Arc<str>,
+        language: Arc<str>,
+        catalog_path: PathBuf,
+        fs: Arc<dyn Fs>,
+    ) -> Task<Result<()>> {
+        let Some(proxy) = self.translation_proxy.read().clone() else {
+            return Task::ready(Ok(()));
+        };
+
+        proxy.load_translations(extension_id, language, catalog_path, fs)
+    }
+
+    fn remove_translations(&self, extension_ids: &[Arc<str>]) {
+        let Some(proxy) = self.translation_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.remove_translations(extension_ids)
+    }
+
+    fn reload_translations(&self, cx: &mut App) {
+        let Some(proxy) = self.translation_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.reload_translations(cx)
+    }
+}
+
 pub trait ExtensionSlashCommandProxy: Send + Sync + 'static {
     fn register_slash_command(&self, extension: Arc<dyn Extension>, command: SlashCommand);
 
@@ -91,6 +91,9 @@ pub struct ExtensionManifest {
     pub debug_adapters: BTreeMap<Arc<str>, DebugAdapterManifestEntry>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub debug_locators: BTreeMap<Arc<str>, DebugLocatorManifestEntry>,
+    /// Interface translation catalogs, keyed by the BCP-47 tag of the language they translate to.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<Arc<str>, Vec<PathBuf>>,
 }
 
 impl ExtensionManifest {
@@ -270,6 +273,7 @@ fn manifest_from_old_manifest(
         capabilities: Vec::new(),
         debug_adapters: Default::default(),
         debug_locators: Default::default(),
+        translations: Default::default(),
     }
 }
 
@@ -302,6 +306,7 @@ mod tests {
             capabilities: vec![],
             debug_adapters: Default::default(),
             debug_locators: Default::default(),
+            translations: Default::default(),
         }
     }
 
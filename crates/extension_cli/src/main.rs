@@ -78,6 +78,7 @@ async fn main() -> Result<()> {
     let grammars = test_grammars(&manifest, &extension_path, &mut wasm_store)?;
     test_languages(&manifest, &extension_path, &grammars)?;
     test_themes(&manifest, &extension_path, fs.clone()).await?;
+    test_translations(&manifest, &extension_path)?;
 
     let archive_dir = output_dir.join("archive");
     fs::remove_dir_all(&archive_dir).ok();
@@ -303,6 +304,22 @@ async fn copy_extension_resources(
         .with_context(|| format!("failed to copy snippets from '{}'", snippets_path.display()))?;
     }
 
+    for catalog_path in manifest.translations.values().flatten() {
+        if let Some(parent) = catalog_path.parent() {
+            fs::create_dir_all(output_dir.join(parent))?;
+        }
+        fs::copy(
+            extension_path.join(catalog_path),
+            output_dir.join(catalog_path),
+        )
+        .with_context(|| {
+            format!(
+                "failed to copy translation catalog '{}'",
+                catalog_path.display()
+            )
+        })?;
+    }
+
     Ok(())
 }
 
@@ -398,3 +415,26 @@ async fn test_themes(
 
     Ok(())
 }
+
+fn test_translations(manifest: &ExtensionManifest, extension_path: &Path) -> Result<()> {
+    for (language, catalog_paths) in &manifest.translations {
+        if localization::Language::from_code(language).is_none() {
+            bail!("invalid language tag {language:?} in translations");
+        }
+
+        for relative_catalog_path in catalog_paths {
+            let catalog_path = extension_path.join(relative_catalog_path);
+            let content = fs::read_to_string(&catalog_path)
+                .with_context(|| format!("failed to read translation catalog {catalog_path:?}"))?;
+            let catalog = localization::Catalog::parse(&content)
+                .with_context(|| format!("failed to parse translation catalog {catalog_path:?}"))?;
+            log::info!(
+                "loaded {} {language} translations from {}",
+                catalog.len(),
+                relative_catalog_path.display()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -141,6 +141,7 @@ fn manifest() -> ExtensionManifest {
         )],
         debug_adapters: Default::default(),
         debug_locators: Default::default(),
+        translations: Default::default(),
     }
 }
 
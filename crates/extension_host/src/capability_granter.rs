@@ -112,6 +112,7 @@ mod tests {
             capabilities: vec![],
             debug_adapters: Default::default(),
             debug_locators: Default::default(),
+            translations: Default::default(),
         }
     }
 
@@ -18,7 +18,7 @@ use extension::{
     ExtensionContextServerProxy, ExtensionDebugAdapterProviderProxy, ExtensionEvents,
     ExtensionGrammarProxy, ExtensionHostProxy, ExtensionLanguageProxy,
     ExtensionLanguageServerProxy, ExtensionSlashCommandProxy, ExtensionSnippetProxy,
-    ExtensionThemeProxy,
+    ExtensionThemeProxy, ExtensionTranslationProxy,
 };
 use fs::{Fs, RemoveOptions};
 use futures::future::join_all;
@@ -1065,9 +1065,9 @@ impl ExtensionStore {
 
     /// Updates the set of installed extensions.
     ///
-    /// First, this unloads any themes, languages, or grammars that are
-    /// no longer in the manifest, or whose files have changed on disk.
-    /// Then it loads any themes, languages, or grammars that are newly
+    /// First, this unloads any themes, languages, grammars, or translations that
+    /// are no longer in the manifest, or whose files have changed on disk.
+    /// Then it loads any themes, languages, grammars, or translations that are newly
     /// added to the manifest, or whose files have changed on disk.
     fn extensions_updated(
         &mut self,
@@ -1211,6 +1211,7 @@ impl ExtensionStore {
             .retain(|(extension, _)| !extensions_to_unload.contains(&extension.id));
         self.proxy.remove_user_themes(themes_to_remove);
         self.proxy.remove_icon_themes(icon_themes_to_remove);
+        self.proxy.remove_translations(&extensions_to_unload);
         self.proxy
             .remove_languages(&languages_to_remove, &grammars_to_remove);
 
@@ -1218,6 +1219,7 @@ impl ExtensionStore {
         let mut themes_to_add = Vec::new();
         let mut icon_themes_to_add = Vec::new();
         let mut snippets_to_add = Vec::new();
+        let mut translations_to_add = Vec::new();
         for extension_id in &extensions_to_load {
             let Some(extension) = new_index.extensions.get(extension_id) else {
                 continue;
@@ -1251,6 +1253,13 @@ impl ExtensionStore {
                 path.extend([Path::new(extension_id.as_ref()), snippets_path.as_path()]);
                 path
             }));
+            for (language, catalog_paths) in &extension.manifest.translations {
+                translations_to_add.extend(catalog_paths.iter().map(|catalog_path| {
+                    let mut path = self.installed_dir.clone();
+                    path.extend([Path::new(extension_id.as_ref()), catalog_path.as_path()]);
+                    (extension_id.clone(), language.clone(), path)
+                }));
+            }
         }
 
         self.proxy.register_grammars(grammars_to_add);
@@ -1339,6 +1348,13 @@ impl ExtensionStore {
                             Err(e) => log::error!("Cannot load snippets: {e:#}"),
                         }
                     }
+
+                    for (extension_id, language, catalog_path) in translations_to_add {
+                        proxy
+                            .load_translations(extension_id, language, catalog_path, fs.clone())
+                            .await
+                            .log_err();
+                    }
                 }
             })
             .await;
@@ -1436,6 +1452,7 @@ impl ExtensionStore {
                 this.proxy.set_extensions_loaded();
                 this.proxy.reload_current_theme(cx);
                 this.proxy.reload_current_icon_theme(cx);
+                this.proxy.reload_translations(cx);
 
                 if let Some(events) = ExtensionEvents::try_global(cx) {
                     events.update(cx, |this, cx| {
@@ -164,6 +164,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         capabilities: Vec::new(),
                         debug_adapters: Default::default(),
                         debug_locators: Default::default(),
+                        translations: Default::default(),
                     }),
                     dev: false,
                 },
@@ -194,6 +195,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         capabilities: Vec::new(),
                         debug_adapters: Default::default(),
                         debug_locators: Default::default(),
+                        translations: Default::default(),
                     }),
                     dev: false,
                 },
@@ -373,6 +375,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                 capabilities: Vec::new(),
                 debug_adapters: Default::default(),
                 debug_locators: Default::default(),
+                translations: Default::default(),
             }),
             dev: false,
         },
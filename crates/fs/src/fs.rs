@@ -37,7 +37,7 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tempfile::TempDir;
-use text::LineEnding;
+use text::{Encoding, LineEnding};
 
 #[cfg(any(test, feature = "test-support"))]
 mod fake_git_repo;
@@ -112,8 +112,25 @@ pub trait Fs: Send + Sync {
         Ok(String::from_utf8(self.load_bytes(path).await?)?)
     }
     async fn load_bytes(&self, path: &Path) -> Result<Vec<u8>>;
+    /// Loads a text file, detecting its encoding.
+    async fn load_with_encoding(&self, path: &Path) -> Result<(String, Encoding)> {
+        Encoding::decode_detected(self.load_bytes(path).await?)
+    }
     async fn atomic_write(&self, path: PathBuf, text: String) -> Result<()>;
     async fn save(&self, path: &Path, text: &Rope, line_ending: LineEnding) -> Result<()>;
+    async fn save_with_encoding(
+        &self,
+        path: &Path,
+        text: &Rope,
+        line_ending: LineEnding,
+        encoding: Encoding,
+    ) -> Result<()> {
+        if encoding == Encoding::UTF_8 {
+            return self.save(path, text, line_ending).await;
+        }
+        let content = encoding.encode(&chunks(text, line_ending).collect::<String>())?;
+        self.write(path, &content).await
+    }
     async fn write(&self, path: &Path, content: &[u8]) -> Result<()>;
     async fn canonicalize(&self, path: &Path) -> Result<PathBuf>;
     async fn is_file(&self, path: &Path) -> bool;
@@ -566,6 +583,11 @@ impl Fs for RealFs {
         Ok(bytes)
     }
 
+    async fn load_with_encoding(&self, path: &Path) -> Result<(String, Encoding)> {
+        let path = path.to_path_buf();
+        smol::unblock(|| Encoding::decode_detected(std::fs::read(path)?)).await
+    }
+
     #[cfg(not(target_os = "windows"))]
     async fn atomic_write(&self, path: PathBuf, data: String) -> Result<()> {
         smol::unblock(move || {
@@ -3123,4 +3145,28 @@ mod tests {
         let content = std::fs::read_to_string(&file_to_be_replaced).unwrap();
         assert_eq!(content, "Hello");
     }
+
+    #[gpui::test]
+    async fn test_realfs_save_with_encoding(executor: BackgroundExecutor) {
+        let fs = RealFs {
+            git_binary_path: None,
+            executor,
+        };
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("readme.txt");
+
+        fs.save_with_encoding(&path, &"中文\n".into(), LineEnding::Windows, Encoding::GBK)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"\xD6\xD0\xCE\xC4\r\n");
+        assert_eq!(
+            fs.load_with_encoding(&path).await.unwrap(),
+            ("中文\r\n".to_string(), Encoding::GBK)
+        );
+
+        fs.save_with_encoding(&path, &"😀".into(), LineEnding::Unix, Encoding::GBK)
+            .await
+            .unwrap_err();
+        assert_eq!(std::fs::read(&path).unwrap(), b"\xD6\xD0\xCE\xC4\r\n");
+    }
 }
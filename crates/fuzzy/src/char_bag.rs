@@ -1,5 +1,7 @@
 use std::iter::FromIterator;
 
+use crate::pinyin;
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct CharBag(u64);
 
@@ -22,6 +24,13 @@ impl CharBag {
             self.0 |= 1 << (idx + 52);
         } else if c == '-' {
             self.0 |= 1 << 62;
+        } else if pinyin::is_han(c) {
+            // Han characters can be matched by the letters of their pinyin. Bags are
+            // kept with worktree entries, so they include readings even while pinyin
+            // matching is disabled, and enabling it doesn't require a rescan.
+            for reading in pinyin::readings(c) {
+                self.extend(reading.chars());
+            }
         }
     }
 }
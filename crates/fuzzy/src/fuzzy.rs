@@ -1,10 +1,12 @@
 mod char_bag;
 mod matcher;
 mod paths;
+mod pinyin;
 mod strings;
 
 pub use char_bag::CharBag;
 pub use paths::{
     PathMatch, PathMatchCandidate, PathMatchCandidateSet, match_fixed_path_set, match_path_sets,
 };
+pub use pinyin::{pinyin_matching_enabled, set_pinyin_matching};
 pub use strings::{StringMatch, StringMatchCandidate, match_strings};
@@ -4,11 +4,13 @@ use std::{
     sync::atomic::{self, AtomicBool},
 };
 
-use crate::CharBag;
+use crate::{CharBag, pinyin};
 
 const BASE_DISTANCE_PENALTY: f64 = 0.6;
 const ADDITIONAL_DISTANCE_PENALTY: f64 = 0.05;
 const MIN_DISTANCE_PENALTY: f64 = 0.2;
+/// Ranks matches through pinyin below equally good literal matches.
+const PINYIN_PENALTY: f64 = 0.9;
 
 // TODO:
 // Use `Path` instead of `&str` for paths.
@@ -18,8 +20,11 @@ pub struct Matcher<'a> {
     query_char_bag: CharBag,
     smart_case: bool,
     penalize_length: bool,
+    /// Whether Han characters in candidates can also be matched by their pinyin.
+    pinyin: bool,
     min_score: f64,
     match_positions: Vec<usize>,
+    pinyin_match_positions: Vec<usize>,
     last_positions: Vec<usize>,
     score_matrix: Vec<Option<f64>>,
     best_position_matrix: Vec<usize>,
@@ -42,9 +47,13 @@ impl<'a> Matcher<'a> {
             query,
             lowercase_query,
             query_char_bag,
+            pinyin: pinyin::pinyin_matching_enabled()
+                && lowercase_query.iter().all(char::is_ascii)
+                && lowercase_query.iter().any(char::is_ascii_alphabetic),
             min_score: 0.0,
             last_positions: vec![0; lowercase_query.len()],
             match_positions: vec![0; query.len()],
+            pinyin_match_positions: Vec::new(),
             score_matrix: Vec::new(),
             best_position_matrix: Vec::new(),
             smart_case,
@@ -80,44 +89,95 @@ impl<'a> Matcher<'a> {
                 break;
             }
 
-            candidate_chars.clear();
-            lowercase_candidate_chars.clear();
-            extra_lowercase_chars.clear();
-            for (i, c) in candidate.borrow().to_string().chars().enumerate() {
-                candidate_chars.push(c);
-                let mut char_lowercased = c.to_lowercase().collect::<Vec<_>>();
-                if char_lowercased.len() > 1 {
-                    extra_lowercase_chars.insert(i, char_lowercased.len() - 1);
+            let string = candidate.borrow().to_string();
+
+            let mut pinyin_score = 0.0;
+            if self.pinyin {
+                for expansion in pinyin::expansions(&string) {
+                    let score = self.score_candidate(
+                        expansion.text.chars(),
+                        prefix,
+                        lowercase_prefix,
+                        &mut candidate_chars,
+                        &mut lowercase_candidate_chars,
+                        &mut extra_lowercase_chars,
+                    ) * PINYIN_PENALTY;
+                    if score > pinyin_score {
+                        pinyin_score = score;
+                        expansion.map_positions(
+                            prefix,
+                            &self.match_positions,
+                            &mut self.pinyin_match_positions,
+                        );
+                    }
                 }
-                lowercase_candidate_chars.append(&mut char_lowercased);
             }
 
-            if !self.find_last_positions(lowercase_prefix, &lowercase_candidate_chars) {
-                continue;
-            }
-
-            let matrix_len = self.query.len() * (prefix.len() + candidate_chars.len());
-            self.score_matrix.clear();
-            self.score_matrix.resize(matrix_len, None);
-            self.best_position_matrix.clear();
-            self.best_position_matrix.resize(matrix_len, 0);
-
-            let score = self.score_match(
-                &candidate_chars,
-                &lowercase_candidate_chars,
+            let score = self.score_candidate(
+                string.chars(),
                 prefix,
                 lowercase_prefix,
-                &extra_lowercase_chars,
+                &mut candidate_chars,
+                &mut lowercase_candidate_chars,
+                &mut extra_lowercase_chars,
             );
 
-            if score > 0.0 {
+            if score > 0.0 && score >= pinyin_score {
                 results.push(build_match(
                     candidate.borrow(),
                     score,
                     &self.match_positions,
                 ));
+            } else if pinyin_score > 0.0 {
+                results.push(build_match(
+                    candidate.borrow(),
+                    pinyin_score,
+                    &self.pinyin_match_positions,
+                ));
+            }
+        }
+    }
+
+    /// Scores a single candidate, leaving the byte offsets of the matched characters in
+    /// `match_positions`. The buffers are passed in to be reused between candidates.
+    fn score_candidate(
+        &mut self,
+        candidate: impl Iterator<Item = char>,
+        prefix: &[char],
+        lowercase_prefix: &[char],
+        candidate_chars: &mut Vec<char>,
+        lowercase_candidate_chars: &mut Vec<char>,
+        extra_lowercase_chars: &mut BTreeMap<usize, usize>,
+    ) -> f64 {
+        candidate_chars.clear();
+        lowercase_candidate_chars.clear();
+        extra_lowercase_chars.clear();
+        for (i, c) in candidate.enumerate() {
+            candidate_chars.push(c);
+            let mut char_lowercased = c.to_lowercase().collect::<Vec<_>>();
+            if char_lowercased.len() > 1 {
+                extra_lowercase_chars.insert(i, char_lowercased.len() - 1);
             }
+            lowercase_candidate_chars.append(&mut char_lowercased);
+        }
+
+        if !self.find_last_positions(lowercase_prefix, lowercase_candidate_chars) {
+            return 0.0;
         }
+
+        let matrix_len = self.query.len() * (prefix.len() + candidate_chars.len());
+        self.score_matrix.clear();
+        self.score_matrix.resize(matrix_len, None);
+        self.best_position_matrix.clear();
+        self.best_position_matrix.resize(matrix_len, 0);
+
+        self.score_match(
+            candidate_chars,
+            lowercase_candidate_chars,
+            prefix,
+            lowercase_prefix,
+            extra_lowercase_chars,
+        )
     }
 
     fn find_last_positions(
@@ -593,10 +653,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_match_pinyin() {
+        let paths = vec![
+            "用户管理.rs",
+            "yonghu.rs",
+            "文档/银行.md",
+            "用户/设置.json",
+            "readme.md",
+        ];
+
+        assert_eq!(
+            match_path_query("yhgl", false, true, &paths),
+            vec![("用户管理.rs", vec![0, 3, 6, 9])]
+        );
+        assert_eq!(
+            match_path_query("guanli", false, true, &paths),
+            vec![("用户管理.rs", vec![6, 9])]
+        );
+        assert_eq!(
+            match_path_query("yonghu", false, true, &paths),
+            vec![
+                ("yonghu.rs", vec![0, 1, 2, 3, 4, 5]),
+                ("用户管理.rs", vec![0, 3]),
+                ("用户/设置.json", vec![0, 3]),
+            ]
+        );
+        assert_eq!(
+            match_path_query("yh/szjson", false, true, &paths),
+            vec![("用户/设置.json", vec![0, 3, 6, 7, 10, 14, 15, 16, 17])]
+        );
+        // Polyphonic characters match by any of their readings.
+        assert_eq!(
+            match_path_query("yinhang", false, true, &paths),
+            vec![("文档/银行.md", vec![7, 10])]
+        );
+        assert_eq!(
+            match_path_query("yinxing", false, true, &paths),
+            vec![("文档/银行.md", vec![7, 10])]
+        );
+
+        assert!(match_path_query("yhgl", false, false, &paths).is_empty());
+    }
+
     fn match_single_path_query<'a>(
         query: &str,
         smart_case: bool,
         paths: &[&'a str],
+    ) -> Vec<(&'a str, Vec<usize>)> {
+        match_path_query(query, smart_case, false, paths)
+    }
+
+    fn match_path_query<'a>(
+        query: &str,
+        smart_case: bool,
+        pinyin: bool,
+        paths: &[&'a str],
     ) -> Vec<(&'a str, Vec<usize>)> {
         let lowercase_query = query.to_lowercase().chars().collect::<Vec<_>>();
         let query = query.chars().collect::<Vec<_>>();
@@ -618,6 +730,7 @@ mod tests {
         }
 
         let mut matcher = Matcher::new(&query, &lowercase_query, query_chars, smart_case, true);
+        matcher.pinyin = pinyin;
 
         let cancel_flag = AtomicBool::new(false);
         let mut results = Vec::new();
@@ -0,0 +1,191 @@
+//! Matching Chinese text by its pinyin.
+//!
+//! When enabled, an ASCII query also matches Han characters through their toneless
+//! pinyin, either spelled out or by initials, so `yonghu` and `yhgl` both find
+//! `用户管理.rs`. Readings come from `pinyin.txt`, which lists the characters of each
+//! syllable; common polyphonic characters are listed under each of their readings, and
+//! `ü` is spelled `v` as in most input methods.
+
+use std::{
+    collections::HashMap,
+    iter,
+    sync::{
+        LazyLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+/// The most spellings tried for a single candidate. Polyphonic characters beyond this
+/// limit are only matched by their first reading.
+const MAX_EXPANSIONS: usize = 16;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static READINGS: LazyLock<HashMap<char, Vec<&'static str>>> = LazyLock::new(|| {
+    let mut readings = HashMap::<char, Vec<&'static str>>::new();
+    for line in include_str!("pinyin.txt").lines() {
+        let Some((syllable, characters)) = line.split_once(' ') else {
+            continue;
+        };
+        for character in characters.chars() {
+            readings.entry(character).or_default().push(syllable);
+        }
+    }
+    readings
+});
+
+/// Sets whether [`match_strings`](crate::match_strings) and
+/// [`match_path_sets`](crate::match_path_sets) match Han characters by their pinyin.
+pub fn set_pinyin_matching(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn pinyin_matching_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Returns the toneless pinyin readings of `ch`, which are empty unless it is a Han
+/// character.
+pub(crate) fn readings(ch: char) -> &'static [&'static str] {
+    if !is_han(ch) {
+        return &[];
+    }
+    READINGS.get(&ch).map_or(&[], Vec::as_slice)
+}
+
+/// Whether `ch` is a CJK ideograph (or `〇`), so other text skips the readings lookup.
+pub(crate) fn is_han(ch: char) -> bool {
+    matches!(
+        ch,
+        '〇' | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{323AF}'
+    )
+}
+
+/// A candidate string with its Han characters spelled out in pinyin.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Expansion {
+    pub text: String,
+    /// For each byte of `text`, the byte offset in the original string of the
+    /// character it was spelled from, if it starts one.
+    origins: Vec<Option<usize>>,
+}
+
+impl Expansion {
+    fn push(&mut self, ch: char, origin: Option<usize>) {
+        self.text.push(ch);
+        self.origins.push(origin);
+        self.origins.extend(iter::repeat_n(None, ch.len_utf8() - 1));
+    }
+
+    /// Maps the byte offsets of a match against `prefix` followed by this expansion
+    /// to byte offsets in `prefix` followed by the original string. Letters spelled
+    /// from the same character are highlighted once, and the separators between
+    /// syllables not at all.
+    pub fn map_positions(&self, prefix: &[char], positions: &[usize], mapped: &mut Vec<usize>) {
+        let prefix_len = prefix.iter().map(|ch| ch.len_utf8()).sum::<usize>();
+        mapped.clear();
+        mapped.extend(positions.iter().filter_map(|&position| {
+            if position < prefix_len {
+                Some(position)
+            } else {
+                Some(prefix_len + (*self.origins.get(position - prefix_len)?)?)
+            }
+        }));
+        mapped.dedup();
+    }
+}
+
+/// Spells out the Han characters of `text` in pinyin, once per combination of the
+/// readings of its polyphonic characters. Syllables are separated by spaces so that
+/// matching their initials scores like matching the starts of words.
+///
+/// Returns nothing when `text` contains no Han characters.
+pub(crate) fn expansions(text: &str) -> Vec<Expansion> {
+    let characters = text
+        .char_indices()
+        .map(|(offset, ch)| (offset, ch, readings(ch)))
+        .collect::<Vec<_>>();
+    if characters
+        .iter()
+        .all(|(_, _, readings)| readings.is_empty())
+    {
+        return Vec::new();
+    }
+
+    let mut count = 1;
+    let reading_counts = characters
+        .iter()
+        .map(|(_, _, readings)| {
+            if readings.len() > 1 && count * readings.len() <= MAX_EXPANSIONS {
+                count *= readings.len();
+                readings.len()
+            } else {
+                1
+            }
+        })
+        .collect::<Vec<_>>();
+
+    (0..count)
+        .map(|mut combination| {
+            let mut expansion = Expansion::default();
+            for (&(offset, ch, readings), &reading_count) in characters.iter().zip(&reading_counts)
+            {
+                if readings.is_empty() {
+                    expansion.push(ch, Some(offset));
+                    continue;
+                }
+
+                let reading = readings[combination % reading_count];
+                combination /= reading_count;
+                if expansion.text.ends_with(char::is_alphabetic) {
+                    expansion.push(' ', None);
+                }
+                for letter in reading.chars() {
+                    expansion.push(letter, Some(offset));
+                }
+            }
+            expansion
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readings() {
+        assert_eq!(readings('用'), ["yong"]);
+        assert_eq!(readings('绿'), ["lv"]);
+        assert!(readings('行').contains(&"xing"));
+        assert!(readings('行').contains(&"hang"));
+        assert!(readings('a').is_empty());
+        assert!(readings('é').is_empty());
+        assert!(readings('あ').is_empty());
+        assert!(is_han('用'));
+        assert!(!is_han('あ'));
+        assert!(!is_han('é'));
+    }
+
+    #[test]
+    fn test_expansions() {
+        assert!(expansions("readme.md").is_empty());
+
+        let expanded = expansions("a用户.rs");
+        assert_eq!(expanded.len(), 1);
+        assert_eq!(expanded[0].text, "a yong hu.rs");
+
+        let mut mapped = Vec::new();
+        expanded[0].map_positions(&['/'], &[1, 3, 4, 7, 8, 11, 12], &mut mapped);
+        assert_eq!(mapped, vec![1, 2, 5, 9, 10]);
+
+        let texts = expansions("银行")
+            .into_iter()
+            .map(|expansion| expansion.text)
+            .collect::<Vec<_>>();
+        assert_eq!(texts, vec!["yin hang", "yin xing"]);
+    }
+}
@@ -67,45 +67,66 @@ impl CommitView {
                 let (commit_diff, commit_details) = futures::join!(commit_diff?, commit_details?);
                 let commit_diff = commit_diff.log_err()?.log_err()?;
                 let commit_details = commit_details.log_err()?.log_err()?;
-                let repo = repo.upgrade()?;
+                let work_directory_abs_path = repo
+                    .read_with(cx, |repo, _| repo.work_directory_abs_path.clone())
+                    .ok()?;
 
                 workspace
                     .update_in(cx, |workspace, window, cx| {
-                        let project = workspace.project();
-                        let commit_view = cx.new(|cx| {
-                            CommitView::new(
-                                commit_details,
-                                commit_diff,
-                                repo,
-                                project.clone(),
-                                window,
-                                cx,
-                            )
-                        });
-
-                        let pane = workspace.active_pane();
-                        pane.update(cx, |pane, cx| {
-                            let ix = pane.items().position(|item| {
-                                let commit_view = item.downcast::<CommitView>();
-                                commit_view
-                                    .is_some_and(|view| view.read(cx).commit.sha == commit.sha)
-                            });
-                            if let Some(ix) = ix {
-                                pane.activate_item(ix, true, true, window, cx);
-                            } else {
-                                pane.add_item(Box::new(commit_view), true, true, None, window, cx);
-                            }
-                        })
+                        Self::show(
+                            commit_details,
+                            commit_diff,
+                            work_directory_abs_path,
+                            workspace,
+                            window,
+                            cx,
+                        )
                     })
                     .log_err()
             })
             .detach();
     }
 
+    /// Shows a commit that has already been loaded, activating the existing view
+    /// of it if there is one. The paths in `commit_diff` are relative to
+    /// `work_directory_abs_path`.
+    pub fn show(
+        commit: CommitDetails,
+        commit_diff: CommitDiff,
+        work_directory_abs_path: Arc<Path>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let project = workspace.project().clone();
+        let pane = workspace.active_pane().clone();
+        pane.update(cx, |pane, cx| {
+            let ix = pane.items().position(|item| {
+                let commit_view = item.downcast::<CommitView>();
+                commit_view.is_some_and(|view| view.read(cx).commit.sha == commit.sha)
+            });
+            if let Some(ix) = ix {
+                pane.activate_item(ix, true, true, window, cx);
+            } else {
+                let commit_view = cx.new(|cx| {
+                    CommitView::new(
+                        commit,
+                        commit_diff,
+                        work_directory_abs_path,
+                        project,
+                        window,
+                        cx,
+                    )
+                });
+                pane.add_item(Box::new(commit_view), true, true, None, window, cx);
+            }
+        })
+    }
+
     fn new(
         commit: CommitDetails,
         commit_diff: CommitDiff,
-        repository: Entity<Repository>,
+        work_directory_abs_path: Arc<Path>,
         project: Entity<Project>,
         window: &mut Window,
         cx: &mut Context<Self>,
@@ -164,11 +185,12 @@ impl CommitView {
                 let is_deleted = file.new_text.is_none();
                 let new_text = file.new_text.unwrap_or_default();
                 let old_text = file.old_text;
-                let worktree_id = repository
-                    .update(cx, |repository, cx| {
-                        repository
-                            .repo_path_to_project_path(&file.path, cx)
-                            .map(|path| path.worktree_id)
+                let abs_path = work_directory_abs_path.join(&file.path.0);
+                let worktree_id = project
+                    .read_with(cx, |project, cx| {
+                        project
+                            .find_worktree(&abs_path, cx)
+                            .map(|(worktree, _)| worktree.read(cx).id())
                             .or(first_worktree_id)
                     })?
                     .context("project has no worktrees")?;
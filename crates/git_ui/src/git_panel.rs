@@ -1762,6 +1762,7 @@ impl GitPanel {
         });
 
         let temperature = AgentSettings::temperature_for_model(&model, cx);
+        let response_language = AgentSettings::get_global(cx).response_language(None);
 
         self.generate_commit_message_task = Some(cx.spawn(async move |this, cx| {
              async move {
@@ -1804,10 +1805,13 @@ impl GitPanel {
 
                 let text_empty = subject.trim().is_empty();
 
+                let language_instruction = response_language
+                    .map(|language| format!("\nWrite the commit message in {language}.\n"))
+                    .unwrap_or_default();
                 let content = if text_empty {
-                    format!("{PROMPT}\nHere are the changes in this commit:\n{diff_text}")
+                    format!("{PROMPT}{language_instruction}\nHere are the changes in this commit:\n{diff_text}")
                 } else {
-                    format!("{PROMPT}\nHere is the user's subject line:\n{subject}\nHere are the changes in this commit:\n{diff_text}\n")
+                    format!("{PROMPT}{language_instruction}\nHere is the user's subject line:\n{subject}\nHere are the changes in this commit:\n{diff_text}\n")
                 };
 
                 const PROMPT: &str = include_str!("commit_message_prompt.txt");
@@ -3220,11 +3224,11 @@ impl GitPanel {
             tooltip = "git add --all ."
         }
 
-        let change_string = match self.entry_count {
-            0 => "No Changes".to_string(),
-            1 => "1 Change".to_string(),
-            _ => format!("{} Changes", self.entry_count),
-        };
+        let change_string = localization::t!(
+            "git_panel.changes",
+            "{count, plural, =0 {No Changes} one {# Change} other {# Changes}}",
+            count = self.entry_count,
+        );
 
         Some(
             self.panel_header_container(window, cx)
@@ -4369,8 +4373,8 @@ impl Panel for GitPanel {
         Some(ui::IconName::GitBranchAlt).filter(|_| GitPanelSettings::get_global(cx).button)
     }
 
-    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
-        Some("Git Panel")
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some(localization::t!("git_panel.tooltip", "Git Panel"))
     }
 
     fn toggle_action(&self) -> Box<dyn Action> {
@@ -528,7 +528,11 @@ where
                 "local task polled by a thread that didn't spawn it. Task spawned at {}",
                 self.location
             );
-            unsafe { self.map_unchecked_mut(|c| &mut *c.inner).poll(cx) }
+            let location = self.location;
+            let started_at = Instant::now();
+            let poll = unsafe { self.map_unchecked_mut(|c| &mut *c.inner).poll(cx) };
+            crate::responsiveness::record_task_poll(location, started_at, started_at.elapsed());
+            poll
         }
     }
 
@@ -89,6 +89,7 @@ mod keymap;
 mod path_builder;
 mod platform;
 pub mod prelude;
+mod responsiveness;
 mod scene;
 mod shared_string;
 mod shared_uri;
@@ -144,6 +145,7 @@ pub use keymap::*;
 pub use path_builder::*;
 pub use platform::*;
 pub use refineable::*;
+pub use responsiveness::*;
 pub use scene::*;
 pub use shared_string::*;
 pub use shared_uri::*;
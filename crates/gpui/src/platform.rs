@@ -36,7 +36,7 @@ mod windows;
 pub(crate) mod scap_screen_capture;
 
 use crate::{
-    Action, AnyWindowHandle, App, AsyncWindowContext, BackgroundExecutor, Bounds,
+    Action, AnyWindowHandle, App, AsyncWindowContext, BackgroundExecutor, BidiLine, Bounds,
     DEFAULT_WINDOW_SIZE, DevicePixels, DispatchEventResult, Font, FontId, FontMetrics, FontRun,
     ForegroundExecutor, GlyphId, GpuSpecs, ImageSource, Keymap, LineLayout, Pixels, PlatformInput,
     Point, RenderGlyphParams, RenderImage, RenderImageParams, RenderSvgParams, Scene, ShapedGlyph,
@@ -674,8 +674,26 @@ impl PlatformTextSystem for NoopTextSystem {
                 .unwrap()
                 .width
             / metrics.units_per_em as f32;
+        // Like platform text systems, position the glyphs of right-to-left runs in visual order.
+        let bidi = BidiLine::new(text);
+        let mut visual_chars = Vec::with_capacity(text.len());
+        if bidi.has_rtl() {
+            for run in bidi.runs() {
+                let chars = text[run.range.clone()]
+                    .char_indices()
+                    .map(|(ix, c)| (run.range.start + ix, c));
+                if run.is_rtl {
+                    visual_chars.extend(chars.rev());
+                } else {
+                    visual_chars.extend(chars);
+                }
+            }
+        } else {
+            visual_chars.extend(text.char_indices());
+        }
+
         let mut glyphs = Vec::new();
-        for (ix, c) in text.char_indices() {
+        for (ix, c) in visual_chars {
             if let Some(glyph) = self.glyph_for_char(FontId(0), c) {
                 glyphs.push(ShapedGlyph {
                     id: glyph,
@@ -707,6 +725,7 @@ impl PlatformTextSystem for NoopTextSystem {
             width: position,
             ascent: font_size * (metrics.ascent / metrics.units_per_em as f32),
             descent: font_size * (metrics.descent / metrics.units_per_em as f32),
+            bidi: bidi.with_glyph_clusters(&runs, position),
             runs,
             len: text.len(),
         }
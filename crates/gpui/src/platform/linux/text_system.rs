@@ -1,7 +1,7 @@
 use crate::{
-    Bounds, DevicePixels, Font, FontFeatures, FontId, FontMetrics, FontRun, FontStyle, FontWeight,
-    GlyphId, LineLayout, Pixels, PlatformTextSystem, Point, RenderGlyphParams, SUBPIXEL_VARIANTS_X,
-    ShapedGlyph, ShapedRun, SharedString, Size, point, size,
+    BidiLine, Bounds, DevicePixels, Font, FontFeatures, FontId, FontMetrics, FontRun, FontStyle,
+    FontWeight, GlyphId, LineLayout, Pixels, PlatformTextSystem, Point, RenderGlyphParams,
+    SUBPIXEL_VARIANTS_X, ShapedGlyph, ShapedRun, SharedString, Size, point, size,
 };
 use anyhow::{Context as _, Ok, Result};
 use collections::HashMap;
@@ -444,11 +444,13 @@ impl CosmicTextSystemState {
             }
         }
 
+        let width = layout.w.into();
         LineLayout {
             font_size,
-            width: layout.w.into(),
+            width,
             ascent: layout.max_ascent.into(),
             descent: layout.max_descent.into(),
+            bidi: BidiLine::new(text).with_glyph_clusters(&runs, width),
             runs,
             len: text.len(),
         }
@@ -1,6 +1,6 @@
 use crate::{
-    Bounds, DevicePixels, Font, FontFallbacks, FontFeatures, FontId, FontMetrics, FontRun,
-    FontStyle, FontWeight, GlyphId, LineLayout, Pixels, PlatformTextSystem, Point,
+    BidiLine, Bounds, DevicePixels, Font, FontFallbacks, FontFeatures, FontId, FontMetrics,
+    FontRun, FontStyle, FontWeight, GlyphId, LineLayout, Pixels, PlatformTextSystem, Point,
     RenderGlyphParams, Result, SUBPIXEL_VARIANTS_X, ShapedGlyph, ShapedRun, SharedString, Size,
     point, px, size, swap_rgba_pa_to_bgra,
 };
@@ -505,10 +505,12 @@ impl MacTextSystemState {
             runs.push(ShapedRun { font_id, glyphs });
         }
         let typographic_bounds = line.get_typographic_bounds();
+        let width = typographic_bounds.width.into();
         LineLayout {
+            bidi: BidiLine::new(text).with_glyph_clusters(&runs, width),
             runs,
             font_size,
-            width: typographic_bounds.width.into(),
+            width,
             ascent: typographic_bounds.ascent.into(),
             descent: typographic_bounds.descent.into(),
             len: text.len(),
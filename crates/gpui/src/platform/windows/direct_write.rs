@@ -662,6 +662,7 @@ impl DirectWriteState {
                 width,
                 ascent,
                 descent,
+                bidi: BidiLine::new(text).with_glyph_clusters(&runs, width),
                 runs,
                 len: text.len(),
             })
@@ -1491,8 +1492,20 @@ impl IDWriteTextRenderer_Impl for TextRenderer_Impl {
         let cluster_map =
             unsafe { std::slice::from_raw_parts(desc.clusterMap, desc.stringLength as usize) };
 
-        let mut cluster_analyzer = ClusterAnalyzer::new(cluster_map, glyph_count);
+        // Runs are drawn in visual order, so a right-to-left run can start before the
+        // previous run in the text.
         let mut utf16_idx = desc.textPosition as usize;
+        if context.index_converter.utf16_ix > utf16_idx {
+            context.index_converter = StringIndexConverter::new(context.index_converter.text);
+        }
+
+        // The glyphs of right-to-left runs are in logical order and advance to the left.
+        let is_rtl = glyphrun.bidiLevel % 2 == 1;
+        let run_origin = context.width;
+        let run_width = glyph_advances.iter().sum::<f32>();
+        let mut run_advance = 0.0;
+
+        let mut cluster_analyzer = ClusterAnalyzer::new(cluster_map, glyph_count);
         let mut glyph_idx = 0;
         let mut glyphs = Vec::with_capacity(glyph_count);
         for (cluster_utf16_len, cluster_glyph_count) in cluster_analyzer {
@@ -1507,19 +1520,28 @@ impl IDWriteTextRenderer_Impl for TextRenderer_Impl {
                 let is_emoji = color_font
                     && is_color_glyph(font_face, id, &context.text_system.components.factory);
                 let this_glyph_idx = glyph_idx + cluster_glyph_idx;
+                let advance = glyph_advances[this_glyph_idx];
+                let advance_offset = glyph_offsets[this_glyph_idx].advanceOffset;
+                let x = if is_rtl {
+                    run_origin + run_width - run_advance - advance - advance_offset
+                } else {
+                    run_origin + run_advance + advance_offset
+                };
                 glyphs.push(ShapedGlyph {
                     id,
-                    position: point(
-                        px(context.width + glyph_offsets[this_glyph_idx].advanceOffset),
-                        px(0.0),
-                    ),
+                    position: point(px(x), px(0.0)),
                     index: context.index_converter.utf8_ix,
                     is_emoji,
                 });
-                context.width += glyph_advances[this_glyph_idx];
+                run_advance += advance;
             }
             glyph_idx += cluster_glyph_count;
         }
+        if is_rtl {
+            // Keep glyphs in visual order, as on other platforms.
+            glyphs.reverse();
+        }
+        context.width = run_origin + run_width;
         context.runs.push(ShapedRun { font_id, glyphs });
         Ok(())
     }
@@ -0,0 +1,192 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    panic::Location,
+    time::{Duration, Instant},
+};
+
+use crate::WindowId;
+
+/// Frames taking longer than this to draw and present are recorded as slow,
+/// since they miss at least one refresh at 60Hz.
+pub const SLOW_FRAME_THRESHOLD: Duration = Duration::from_millis(33);
+
+/// Foreground task polls taking longer than this are recorded as main thread stalls.
+pub const MAIN_THREAD_STALL_THRESHOLD: Duration = Duration::from_millis(50);
+
+const MAX_RECORDED_EVENTS: usize = 1024;
+
+/// The time spent producing a single frame for a window.
+#[derive(Clone, Copy, Debug)]
+pub struct FrameTiming {
+    /// The window the frame was drawn for.
+    pub window_id: WindowId,
+    /// When the frame started drawing.
+    pub started_at: Instant,
+    /// Time spent laying out and painting the frame.
+    pub draw: Duration,
+    /// Time spent handing the frame's scene to the platform renderer.
+    pub present: Duration,
+}
+
+impl FrameTiming {
+    /// The total time spent on the frame.
+    pub fn duration(&self) -> Duration {
+        self.draw + self.present
+    }
+}
+
+/// A single poll of a foreground task that blocked the main thread.
+#[derive(Clone, Copy, Debug)]
+pub struct MainThreadStall {
+    /// Where the task was spawned.
+    pub spawned_at: &'static Location<'static>,
+    /// When the poll started.
+    pub started_at: Instant,
+    /// How long the poll blocked the main thread.
+    pub duration: Duration,
+}
+
+/// Running totals of the frames and stalls observed on the main thread.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResponsivenessCounts {
+    /// Frames drawn across all windows.
+    pub frames: u64,
+    /// Frames that took longer than [`SLOW_FRAME_THRESHOLD`].
+    pub slow_frames: u64,
+    /// Task polls that took longer than [`MAIN_THREAD_STALL_THRESHOLD`].
+    pub main_thread_stalls: u64,
+}
+
+#[derive(Default)]
+struct ResponsivenessLog {
+    counts: ResponsivenessCounts,
+    slow_frames: VecDeque<FrameTiming>,
+    stalls: VecDeque<MainThreadStall>,
+}
+
+thread_local! {
+    // Frames are drawn and foreground tasks are polled on the main thread only,
+    // so a thread local avoids synchronizing on every poll.
+    static LOG: RefCell<ResponsivenessLog> = RefCell::default();
+}
+
+fn push_bounded<T>(events: &mut VecDeque<T>, event: T) {
+    if events.len() == MAX_RECORDED_EVENTS {
+        events.pop_front();
+    }
+    events.push_back(event);
+}
+
+pub(crate) fn record_frame(timing: FrameTiming) {
+    LOG.with_borrow_mut(|log| {
+        log.counts.frames += 1;
+        if timing.duration() >= SLOW_FRAME_THRESHOLD {
+            log.counts.slow_frames += 1;
+            push_bounded(&mut log.slow_frames, timing);
+        }
+    });
+}
+
+pub(crate) fn record_task_poll(
+    spawned_at: &'static Location<'static>,
+    started_at: Instant,
+    duration: Duration,
+) {
+    if duration < MAIN_THREAD_STALL_THRESHOLD {
+        return;
+    }
+    LOG.try_with(|log| {
+        let mut log = log.borrow_mut();
+        log.counts.main_thread_stalls += 1;
+        push_bounded(
+            &mut log.stalls,
+            MainThreadStall {
+                spawned_at,
+                started_at,
+                duration,
+            },
+        );
+    })
+    // Tasks can still be polled while the thread exits, after the log is destroyed, in
+    // which case there's nowhere left to record the stall.
+    .ok();
+}
+
+/// Returns the number of frames and stalls observed on the main thread so far.
+/// Must be called from the main thread.
+pub fn responsiveness_counts() -> ResponsivenessCounts {
+    LOG.with_borrow(|log| log.counts)
+}
+
+/// Returns the slow frames that started within the last `within`, oldest first.
+/// Must be called from the main thread.
+pub fn recent_slow_frames(within: Duration) -> Vec<FrameTiming> {
+    let now = Instant::now();
+    LOG.with_borrow(|log| {
+        log.slow_frames
+            .iter()
+            .filter(|frame| now.saturating_duration_since(frame.started_at) <= within)
+            .copied()
+            .collect()
+    })
+}
+
+/// Returns the main thread stalls that started within the last `within`, oldest first.
+/// Must be called from the main thread.
+pub fn recent_main_thread_stalls(within: Duration) -> Vec<MainThreadStall> {
+    let now = Instant::now();
+    LOG.with_borrow(|log| {
+        log.stalls
+            .iter()
+            .filter(|stall| now.saturating_duration_since(stall.started_at) <= within)
+            .copied()
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_only_slow_frames_and_long_polls() {
+        let start = Instant::now();
+        let window_id = WindowId::from(1);
+        let counts_before = responsiveness_counts();
+
+        record_frame(FrameTiming {
+            window_id,
+            started_at: start,
+            draw: Duration::from_millis(4),
+            present: Duration::from_millis(1),
+        });
+        record_frame(FrameTiming {
+            window_id,
+            started_at: start,
+            draw: Duration::from_millis(40),
+            present: Duration::from_millis(2),
+        });
+        record_task_poll(Location::caller(), start, Duration::from_millis(1));
+        record_task_poll(Location::caller(), start, Duration::from_millis(80));
+
+        let counts = responsiveness_counts();
+        assert_eq!(counts.frames - counts_before.frames, 2);
+        assert_eq!(counts.slow_frames - counts_before.slow_frames, 1);
+        assert_eq!(
+            counts.main_thread_stalls - counts_before.main_thread_stalls,
+            1
+        );
+
+        let slow_frames = recent_slow_frames(Duration::from_secs(60));
+        assert_eq!(slow_frames.len(), 1);
+        assert_eq!(slow_frames[0].duration(), Duration::from_millis(42));
+
+        let stalls = recent_main_thread_stalls(Duration::from_secs(60));
+        assert_eq!(stalls.len(), 1);
+        assert_eq!(stalls[0].spawned_at.file(), file!());
+
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(recent_slow_frames(Duration::from_millis(1)).is_empty());
+    }
+}
@@ -0,0 +1,290 @@
+use crate::{Pixels, ShapedRun};
+use std::ops::Range;
+use unicode_bidi::{BidiInfo, Level};
+
+/// The first code point of the Hebrew block. No character before it has a right-to-left
+/// bidi class, so lines made only of such characters skip the bidi algorithm entirely.
+const FIRST_RTL_CODE_POINT: char = '\u{0590}';
+
+/// The directional runs of a line of text, as resolved by the Unicode Bidirectional
+/// Algorithm (UAX #9) with a left-to-right base direction.
+///
+/// Platform text systems shape each run in its own direction and position the glyphs in
+/// visual order, so the glyph indices of a [`LineLayout`](crate::LineLayout) decrease
+/// across right-to-left runs. A `BidiLine` records where those runs are, so that
+/// positions in the text can be mapped to and from that visual order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BidiLine {
+    /// The runs of the line in visual order, from left to right. Empty when the line is
+    /// entirely left-to-right.
+    runs: Vec<BidiRun>,
+    /// The glyph clusters of the laid out line, ordered by their index in the text. Only
+    /// computed for lines with right-to-left text, whose glyphs aren't in text order.
+    glyph_clusters: Vec<GlyphCluster>,
+}
+
+/// A run of text with a single direction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BidiRun {
+    /// The range of the run in the line, in utf-8 bytes.
+    pub range: Range<usize>,
+    /// Whether the run is laid out from right to left.
+    pub is_rtl: bool,
+}
+
+/// The glyphs laid out for a single index in the text, and the horizontal extent they cover.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct GlyphCluster {
+    pub(crate) index: usize,
+    pub(crate) x: Range<Pixels>,
+}
+
+impl BidiLine {
+    /// Resolves the directional runs of a single line of `text`.
+    pub fn new(text: &str) -> Self {
+        if text.chars().all(|c| c < FIRST_RTL_CODE_POINT) {
+            return Self::default();
+        }
+
+        let info = BidiInfo::new(text, Some(Level::ltr()));
+        if !info.has_rtl() {
+            return Self::default();
+        }
+
+        let mut runs = Vec::new();
+        for paragraph in &info.paragraphs {
+            let (levels, level_runs) = info.visual_runs(paragraph, paragraph.range.clone());
+            runs.extend(level_runs.into_iter().map(|range| BidiRun {
+                is_rtl: levels[range.start].is_rtl(),
+                range,
+            }));
+        }
+        Self {
+            runs,
+            glyph_clusters: Vec::new(),
+        }
+    }
+
+    /// Records the glyph clusters of the line laid out from `runs`, so that positions
+    /// in right-to-left text can be looked up without sorting the glyphs each time.
+    pub(crate) fn with_glyph_clusters(mut self, runs: &[ShapedRun], width: Pixels) -> Self {
+        if !self.has_rtl() {
+            return self;
+        }
+
+        let mut glyphs = runs
+            .iter()
+            .flat_map(|run| run.glyphs.iter())
+            .map(|glyph| (glyph.position.x, glyph.index))
+            .collect::<Vec<_>>();
+        glyphs.sort_by_key(|(x, _)| *x);
+
+        let mut clusters = glyphs
+            .iter()
+            .enumerate()
+            .map(|(ix, (x, index))| GlyphCluster {
+                index: *index,
+                x: *x..glyphs.get(ix + 1).map_or(width, |(next_x, _)| *next_x),
+            })
+            .collect::<Vec<_>>();
+        clusters.sort_by_key(|cluster| cluster.index);
+        clusters.dedup_by(|next, cluster| {
+            if next.index == cluster.index {
+                cluster.x.start = cluster.x.start.min(next.x.start);
+                cluster.x.end = cluster.x.end.max(next.x.end);
+                true
+            } else {
+                false
+            }
+        });
+        self.glyph_clusters = clusters;
+        self
+    }
+
+    /// The glyph clusters of the laid out line, ordered by their index in the text.
+    /// Empty when the line is entirely left-to-right.
+    pub(crate) fn glyph_clusters(&self) -> &[GlyphCluster] {
+        &self.glyph_clusters
+    }
+
+    /// Whether the line contains any right-to-left text.
+    pub fn has_rtl(&self) -> bool {
+        !self.runs.is_empty()
+    }
+
+    /// The runs of the line in visual order, from left to right. Empty when the line is
+    /// entirely left-to-right.
+    pub fn runs(&self) -> &[BidiRun] {
+        &self.runs
+    }
+
+    /// Whether the character at `index` is laid out from right to left.
+    pub fn is_rtl(&self, index: usize) -> bool {
+        self.runs
+            .iter()
+            .any(|run| run.is_rtl && run.range.contains(&index))
+    }
+
+    /// Returns the caret positions of the line in visual order, from left to right.
+    ///
+    /// A caret is drawn on the side of the character following it where that character
+    /// starts: its left edge in left-to-right text and its right edge in right-to-left
+    /// text. The end of the line is drawn after its last character instead.
+    pub fn visual_carets(&self, text: &str) -> Vec<usize> {
+        if !self.has_rtl() {
+            return text
+                .char_indices()
+                .map(|(ix, _)| ix)
+                .chain([text.len()])
+                .collect();
+        }
+
+        let mut carets = Vec::with_capacity(text.len() + 1);
+        for run in &self.runs {
+            let boundaries = text[run.range.clone()]
+                .char_indices()
+                .map(|(ix, _)| run.range.start + ix);
+            let includes_end = run.range.end == text.len();
+            if run.is_rtl {
+                if includes_end {
+                    carets.push(run.range.end);
+                }
+                carets.extend(boundaries.rev());
+            } else {
+                carets.extend(boundaries);
+                if includes_end {
+                    carets.push(run.range.end);
+                }
+            }
+        }
+        carets
+    }
+
+    /// Returns the caret position visually to the left of the one at `index`, or `None`
+    /// at the left edge of the line.
+    pub fn caret_left(&self, text: &str, index: usize) -> Option<usize> {
+        let carets = self.visual_carets(text);
+        let position = carets.iter().position(|caret| *caret == index)?;
+        carets.get(position.checked_sub(1)?).copied()
+    }
+
+    /// Returns the caret position visually to the right of the one at `index`, or `None`
+    /// at the right edge of the line.
+    pub fn caret_right(&self, text: &str, index: usize) -> Option<usize> {
+        let carets = self.visual_carets(text);
+        let position = carets.iter().position(|caret| *caret == index)?;
+        carets.get(position + 1).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FontId, GlyphId, LineLayout, ShapedGlyph, ShapedRun, point, px};
+
+    #[test]
+    fn test_left_to_right_lines() {
+        let line = BidiLine::new("hello, 世界");
+        assert!(!line.has_rtl());
+        assert_eq!(line.visual_carets("ab"), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_mixed_direction_lines() {
+        // "abc אבג": the Hebrew letters are two bytes each.
+        let text = "abc \u{5d0}\u{5d1}\u{5d2}";
+        let line = BidiLine::new(text);
+        assert!(line.has_rtl());
+        assert_eq!(
+            line.runs(),
+            [
+                BidiRun {
+                    range: 0..4,
+                    is_rtl: false
+                },
+                BidiRun {
+                    range: 4..10,
+                    is_rtl: true
+                },
+            ]
+        );
+        assert!(!line.is_rtl(3));
+        assert!(line.is_rtl(4));
+        assert!(line.is_rtl(8));
+
+        // Visually "abc גבא": after the space comes the end of the line, which is drawn
+        // before the last Hebrew letter.
+        assert_eq!(line.visual_carets(text), [0, 1, 2, 3, 10, 8, 6, 4]);
+        assert_eq!(line.caret_right(text, 3), Some(10));
+        assert_eq!(line.caret_right(text, 10), Some(8));
+        assert_eq!(line.caret_right(text, 4), None);
+        assert_eq!(line.caret_left(text, 4), Some(6));
+        assert_eq!(line.caret_left(text, 0), None);
+
+        // Digits keep their left-to-right order within right-to-left text.
+        let text = "\u{5d0} 12 \u{5d1}";
+        let line = BidiLine::new(text);
+        assert_eq!(
+            line.runs()
+                .iter()
+                .map(|run| (&text[run.range.clone()], run.is_rtl))
+                .collect::<Vec<_>>(),
+            [(" \u{5d1}", true), ("12", false), ("\u{5d0} ", true)]
+        );
+    }
+
+    #[test]
+    fn test_layout_positions_in_mixed_direction_lines() {
+        // Lay out "ab אב" the way platform text systems do, with the Hebrew letters in
+        // visual order: "ab בא".
+        let text = "ab \u{5d0}\u{5d1}";
+        let glyph = |index: usize, x: f32| ShapedGlyph {
+            id: GlyphId(0),
+            position: point(px(x), px(0.)),
+            index,
+            is_emoji: false,
+        };
+        let runs = vec![ShapedRun {
+            font_id: FontId(0),
+            glyphs: vec![
+                glyph(0, 0.),
+                glyph(1, 10.),
+                glyph(2, 20.),
+                glyph(5, 30.),
+                glyph(3, 40.),
+            ],
+        }];
+        let layout = LineLayout {
+            font_size: px(10.),
+            width: px(50.),
+            ascent: px(8.),
+            descent: px(2.),
+            bidi: BidiLine::new(text).with_glyph_clusters(&runs, px(50.)),
+            runs,
+            len: text.len(),
+        };
+
+        assert_eq!(layout.x_for_index(0), px(0.));
+        assert_eq!(layout.x_for_index(2), px(20.));
+        assert_eq!(layout.x_for_index(3), px(50.));
+        assert_eq!(layout.x_for_index(5), px(40.));
+        assert_eq!(layout.x_for_index(7), px(30.));
+
+        assert_eq!(layout.index_for_x(px(35.)), Some(5));
+        assert_eq!(layout.index_for_x(px(45.)), Some(3));
+        assert_eq!(layout.index_for_x(px(55.)), None);
+
+        assert_eq!(layout.closest_index_for_x(px(29.)), 7);
+        assert_eq!(layout.closest_index_for_x(px(41.)), 5);
+        assert_eq!(layout.closest_index_for_x(px(60.)), 3);
+
+        assert_eq!(
+            layout.x_ranges_for_range(1..4).into_vec(),
+            [px(10.)..px(30.), px(40.)..px(50.)]
+        );
+        assert_eq!(
+            layout.x_ranges_for_range(3..7).into_vec(),
+            [px(30.)..px(50.)]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{FontId, GlyphId, Pixels, PlatformTextSystem, Point, SharedString, Size, point, px};
 use collections::FxHashMap;
 use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
-use smallvec::SmallVec;
+use smallvec::{SmallVec, smallvec};
 use std::{
     borrow::Borrow,
     hash::{Hash, Hasher},
@@ -9,7 +9,7 @@ use std::{
     sync::Arc,
 };
 
-use super::LineWrapper;
+use super::{BidiLine, GlyphCluster, LineWrapper};
 
 /// A laid out and styled line of text
 #[derive(Default, Debug)]
@@ -26,6 +26,8 @@ pub struct LineLayout {
     pub runs: Vec<ShapedRun>,
     /// The length of the line in utf-8 bytes
     pub len: usize,
+    /// The directional runs of the line, if it contains right-to-left text
+    pub bidi: BidiLine,
 }
 
 /// A run of text that has been shaped .
@@ -58,6 +60,14 @@ impl LineLayout {
     pub fn index_for_x(&self, x: Pixels) -> Option<usize> {
         if x >= self.width {
             None
+        } else if self.bidi.has_rtl() {
+            Some(
+                self.bidi
+                    .glyph_clusters()
+                    .iter()
+                    .find(|cluster| cluster.x.contains(&x))
+                    .map_or(0, |cluster| cluster.index),
+            )
         } else {
             for run in self.runs.iter().rev() {
                 for glyph in run.glyphs.iter().rev() {
@@ -73,6 +83,16 @@ impl LineLayout {
     /// closest_index_for_x returns the character boundary closest to the given x coordinate
     /// (e.g. to handle aligning up/down arrow keys)
     pub fn closest_index_for_x(&self, x: Pixels) -> usize {
+        if self.bidi.has_rtl() {
+            let clusters = self.bidi.glyph_clusters();
+            return clusters
+                .iter()
+                .map(|cluster| cluster.index)
+                .chain([self.len])
+                .min_by_key(|index| (self.bidi_x_for_index(clusters, *index) - x).abs())
+                .unwrap_or(0);
+        }
+
         let mut prev_index = 0;
         let mut prev_x = px(0.);
 
@@ -103,6 +123,10 @@ impl LineLayout {
 
     /// The x position of the character at the given index
     pub fn x_for_index(&self, index: usize) -> Pixels {
+        if self.bidi.has_rtl() {
+            return self.bidi_x_for_index(self.bidi.glyph_clusters(), index);
+        }
+
         for run in &self.runs {
             for glyph in &run.glyphs {
                 if glyph.index >= index {
@@ -113,6 +137,56 @@ impl LineLayout {
         self.width
     }
 
+    /// The horizontal extents covered by the text in the given range, from left to right.
+    ///
+    /// Text mixing left-to-right and right-to-left runs can cover several extents.
+    pub fn x_ranges_for_range(&self, range: Range<usize>) -> SmallVec<[Range<Pixels>; 1]> {
+        if !self.bidi.has_rtl() {
+            return smallvec![self.x_for_index(range.start)..self.x_for_index(range.end)];
+        }
+
+        let mut extents = self
+            .bidi
+            .glyph_clusters()
+            .iter()
+            .filter(|cluster| range.contains(&cluster.index))
+            .map(|cluster| cluster.x.clone())
+            .collect::<Vec<_>>();
+        extents.sort_by_key(|extent| extent.start);
+
+        let mut ranges = SmallVec::<[Range<Pixels>; 1]>::new();
+        for extent in extents {
+            match ranges.last_mut() {
+                Some(last) if extent.start <= last.end => last.end = last.end.max(extent.end),
+                _ => ranges.push(extent),
+            }
+        }
+        ranges
+    }
+
+    /// The x position of the caret before the character at `index`, which is on the right
+    /// edge of characters laid out from right to left.
+    fn bidi_x_for_index(&self, clusters: &[GlyphCluster], index: usize) -> Pixels {
+        if index >= self.len {
+            return match clusters.last() {
+                Some(last) if self.bidi.is_rtl(last.index) => last.x.start,
+                _ => self.width,
+            };
+        }
+
+        let preceding_clusters = clusters.partition_point(|cluster| cluster.index <= index);
+        let Some(cluster) = clusters[..preceding_clusters].last() else {
+            return clusters.first().map_or(px(0.), |cluster| cluster.x.start);
+        };
+        // Within a cluster, such as a ligature, the caret goes after the whole cluster.
+        let after_cluster = cluster.index < index;
+        if self.bidi.is_rtl(cluster.index) != after_cluster {
+            cluster.x.end
+        } else {
+            cluster.x.start
+        }
+    }
+
     /// The corresponding Font at the given index
     pub fn font_id_for_index(&self, index: usize) -> Option<FontId> {
         for run in &self.runs {
@@ -164,16 +238,9 @@ impl LineLayout {
 
             // Here is very similar to `LineWrapper::wrap_line` to determine text wrapping,
             // but there are some differences, so we have to duplicate the code here.
-            if LineWrapper::is_word_char(ch) {
-                if prev_ch == ' ' && ch != ' ' && first_non_whitespace_ix.is_some() {
-                    last_candidate_ix = Some(boundary);
-                    last_candidate_x = x;
-                }
-            } else {
-                if ch != ' ' && first_non_whitespace_ix.is_some() {
-                    last_candidate_ix = Some(boundary);
-                    last_candidate_x = x;
-                }
+            if first_non_whitespace_ix.is_some() && LineWrapper::allows_break_before(prev_ch, ch) {
+                last_candidate_ix = Some(boundary);
+                last_candidate_x = x;
             }
 
             if ch != ' ' && first_non_whitespace_ix.is_none() {
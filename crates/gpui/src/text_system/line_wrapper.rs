@@ -58,17 +58,10 @@ impl LineWrapper {
                             continue;
                         }
 
-                        if Self::is_word_char(c) {
-                            if prev_c == ' ' && c != ' ' && first_non_whitespace_ix.is_some() {
-                                last_candidate_ix = ix;
-                                last_candidate_width = width;
-                            }
-                        } else {
-                            // CJK may not be space separated, e.g.: `Hello world你好世界`
-                            if c != ' ' && first_non_whitespace_ix.is_some() {
-                                last_candidate_ix = ix;
-                                last_candidate_width = width;
-                            }
+                        if first_non_whitespace_ix.is_some() && Self::allows_break_before(prev_c, c)
+                        {
+                            last_candidate_ix = ix;
+                            last_candidate_width = width;
                         }
 
                         if c != ' ' && first_non_whitespace_ix.is_none() {
@@ -188,6 +181,24 @@ impl LineWrapper {
         matches!(c, '⋯')
     }
 
+    /// Returns whether a line may be wrapped before `c`, which follows `prev_c`.
+    ///
+    /// Lines wrap before any character that isn't part of a word, such as `(` or `[` in
+    /// code, and at the start of a word following a space. Around East Asian text, which
+    /// isn't separated by spaces, lines may also wrap between any two characters, except
+    /// where the kinsoku rules of East Asian typography forbid it: closing brackets and
+    /// punctuation such as `，` or `。` never start a line, and opening brackets such as
+    /// `「` never end one.
+    pub(crate) fn allows_break_before(prev_c: char, c: char) -> bool {
+        if c == ' ' {
+            return false;
+        }
+        if is_east_asian(prev_c) || is_east_asian(c) {
+            return !forbids_break_before(c) && !forbids_break_after(prev_c);
+        }
+        !Self::is_word_char(c) || prev_c == ' '
+    }
+
     #[inline(always)]
     fn width_for_char(&mut self, c: char) -> Pixels {
         if (c as u32) < 128 {
@@ -223,6 +234,53 @@ impl LineWrapper {
     }
 }
 
+/// Whether `c` is a CJK ideograph, kana, hangul, or CJK or fullwidth punctuation.
+fn is_east_asian(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1100}'..='\u{11FF}'
+            | '\u{2E80}'..='\u{9FFF}'
+            | '\u{A960}'..='\u{A97F}'
+            | '\u{AC00}'..='\u{D7FF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FE30}'..='\u{FE4F}'
+            | '\u{FF00}'..='\u{FFEF}'
+            | '\u{20000}'..='\u{3FFFF}'
+    )
+}
+
+/// Whether `c` can't start a line, following UAX #14's line break classes.
+fn forbids_break_before(c: char) -> bool {
+    use unicode_linebreak::BreakClass::*;
+
+    matches!(c, '”' | '’')
+        || matches!(
+            unicode_linebreak::break_property(c as u32),
+            ClosePunctuation
+                | CloseParenthesis
+                | Exclamation
+                | InfixSeparator
+                | NonStarter
+                | ConditionalJapaneseStarter
+                | Symbol
+                | CombiningMark
+                | ZeroWidthJoiner
+                | WordJoiner
+                | NonBreakingGlue
+        )
+}
+
+/// Whether `c` can't end a line, following UAX #14's line break classes.
+fn forbids_break_after(c: char) -> bool {
+    use unicode_linebreak::BreakClass::*;
+
+    matches!(c, '“' | '‘')
+        || matches!(
+            unicode_linebreak::break_property(c as u32),
+            OpenPunctuation | WordJoiner | NonBreakingGlue
+        )
+}
+
 fn update_runs_after_truncation(result: &str, ellipsis: &str, runs: &mut Vec<TextRun>) {
     let mut truncate_at = result.len() - ellipsis.len();
     let mut run_end = None;
@@ -489,6 +547,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_wrap_line_code() {
+        let mut wrapper = build_wrapper();
+
+        // Brackets are wrap points, so identifiers don't have to be split.
+        assert_eq!(
+            wrapper
+                .wrap_line(&[LineFragment::text("aaaa(bbbb[cccc])")], px(72.))
+                .collect::<Vec<_>>(),
+            &[Boundary::new(4, 0), Boundary::new(9, 0)],
+        );
+        assert!(LineWrapper::allows_break_before('o', '('));
+        assert!(LineWrapper::allows_break_before('r', '['));
+        assert!(LineWrapper::allows_break_before('(', '{'));
+        assert!(!LineWrapper::allows_break_before('o', 'o'));
+        assert!(!LineWrapper::allows_break_before('a', ' '));
+    }
+
+    #[test]
+    fn test_wrap_line_cjk() {
+        let mut wrapper = build_wrapper();
+
+        // Punctuation doesn't start a line.
+        assert_eq!(
+            wrapper
+                .wrap_line(
+                    &[LineFragment::text("我们在使用中文，调用get函数。")],
+                    px(72.)
+                )
+                .collect::<Vec<_>>(),
+            &[Boundary::new(18, 0), Boundary::new(33, 0)],
+        );
+        // Opening brackets don't end a line.
+        assert_eq!(
+            wrapper
+                .wrap_line(&[LineFragment::text("这是一个很长「句子」。")], px(72.))
+                .collect::<Vec<_>>(),
+            &[Boundary::new(18, 0)],
+        );
+        // Latin words between CJK characters stay together.
+        assert_eq!(
+            wrapper
+                .wrap_line(&[LineFragment::text("有些人喜欢Rust语言")], px(72.))
+                .collect::<Vec<_>>(),
+            &[Boundary::new(15, 0)],
+        );
+    }
+
     #[test]
     fn test_truncate_line() {
         let mut wrapper = build_wrapper();
@@ -1045,8 +1045,16 @@ impl Window {
                     measure("frame duration", || {
                         handle
                             .update(&mut cx, |_, window, cx| {
+                                let started_at = Instant::now();
                                 let arena_clear_needed = window.draw(cx);
+                                let drawn_at = Instant::now();
                                 window.present();
+                                crate::responsiveness::record_frame(crate::FrameTiming {
+                                    window_id: handle.window_id(),
+                                    started_at,
+                                    draw: drawn_at - started_at,
+                                    present: drawn_at.elapsed(),
+                                });
                                 // drop the arena elements after present to reduce latency
                                 arena_clear_needed.clear();
                             })
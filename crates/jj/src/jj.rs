@@ -1,5 +1,3 @@
 mod jj_repository;
-mod jj_store;
 
 pub use jj_repository::*;
-pub use jj_store::*;
@@ -1,24 +1,301 @@
-use std::path::Path;
-use std::sync::Arc;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::slice;
+use std::sync::{Arc, Mutex};
 
-use anyhow::Result;
+use anyhow::{Context as _, Result, bail};
+use collections::HashMap;
+use futures::StreamExt as _;
+use futures::executor::block_on;
 use gpui::SharedString;
-use jj_lib::config::StackedConfig;
-use jj_lib::repo::StoreFactories;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::config::{ConfigGetResultExt as _, ConfigSource, StackedConfig};
+use jj_lib::conflicts::{ConflictMarkerStyle, MaterializedTreeValue, materialize_tree_value};
+use jj_lib::fileset::{self, FilesetDiagnostics};
+use jj_lib::fsmonitor::FsmonitorSettings;
+use jj_lib::git;
+use jj_lib::gitignore::GitIgnoreFile;
+use jj_lib::graph::GraphEdgeType;
+use jj_lib::matchers::{EverythingMatcher, FilesMatcher, Matcher};
+use jj_lib::merged_tree::MergedTreeValue;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::RefTarget;
+use jj_lib::op_walk;
+use jj_lib::operation::Operation;
+use jj_lib::ref_name::{RefName, RemoteName, WorkspaceNameBuf};
+use jj_lib::repo::{MutableRepo, ReadonlyRepo, Repo as _, RepoLoader, StoreFactories};
+use jj_lib::repo_path::{RepoPath, RepoPathBuf, RepoPathUiConverter};
+use jj_lib::revset::{
+    self, ResolvedRevsetExpression, Revset, RevsetAliasesMap, RevsetDiagnostics, RevsetExtensions,
+    RevsetParseContext, RevsetWorkspaceContext, SymbolResolver, UserRevsetExpression,
+};
+use jj_lib::rewrite::{merge_commit_trees, restore_tree};
 use jj_lib::settings::UserSettings;
-use jj_lib::workspace::{self, DefaultWorkspaceLoaderFactory, WorkspaceLoaderFactory};
+use jj_lib::store::Store;
+use jj_lib::working_copy::{CheckoutOptions, SnapshotOptions};
+use jj_lib::workspace::{self, DefaultWorkspaceLoaderFactory, Workspace, WorkspaceLoaderFactory};
 
-#[derive(Debug, Clone)]
+/// The revset `jj log` shows when `revsets.log` isn't configured.
+pub const DEFAULT_LOG_REVSET: &str =
+    "present(@) | ancestors(immutable_heads().., 2) | present(trunk())";
+
+/// Aliases that jj's CLI defines in its built-in config rather than in
+/// `jj_lib`, and that [`DEFAULT_LOG_REVSET`] relies on. The user's
+/// `revset-aliases` take precedence over them.
+const BUILTIN_REVSET_ALIASES: &[(&str, &str)] = &[
+    (
+        "trunk()",
+        r#"latest(
+            remote_bookmarks(exact:"main", exact:"origin") |
+            remote_bookmarks(exact:"master", exact:"origin") |
+            remote_bookmarks(exact:"trunk", exact:"origin") |
+            remote_bookmarks(exact:"main", exact:"upstream") |
+            remote_bookmarks(exact:"master", exact:"upstream") |
+            remote_bookmarks(exact:"trunk", exact:"upstream") |
+            root()
+        )"#,
+    ),
+    (
+        "builtin_immutable_heads()",
+        "present(trunk()) | tags() | untracked_remote_bookmarks()",
+    ),
+    ("immutable_heads()", "builtin_immutable_heads()"),
+    ("immutable()", "::(immutable_heads() | root())"),
+    ("mutable()", "~immutable()"),
+];
+
+/// The remote jj uses for the refs of a colocated git repository, whose
+/// bookmarks mirror the local ones.
+const LOCAL_GIT_REMOTE: &str = "git";
+
+/// The files jj starts tracking when `snapshot.auto-track` isn't configured.
+const DEFAULT_AUTO_TRACK: &str = "all()";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Bookmark {
     pub ref_name: SharedString,
+    /// The remote the bookmark is on, or `None` for local bookmarks.
+    pub remote: Option<SharedString>,
+    /// Whether a remote bookmark is tracked by the local bookmark of the same name.
+    pub is_tracked: bool,
+}
+
+/// A change to the repository, made in a single jj transaction so that it
+/// shows up in the operation log and can be undone. Revisions are revsets
+/// that must resolve to exactly one commit, such as `@` or a change ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mutation {
+    /// Creates an empty commit on top of `parents` and edits it, like `jj new`.
+    New {
+        parents: Vec<String>,
+    },
+    /// Like `jj describe`.
+    Describe {
+        revision: String,
+        description: String,
+    },
+    /// Moves all changes in `revision` into its parent, like `jj squash`.
+    Squash {
+        revision: String,
+    },
+    /// Splits the changes to `paths` out of `revision` into a commit of
+    /// their own, before the rest of the changes, like `jj split`.
+    Split {
+        revision: String,
+        paths: Vec<Arc<Path>>,
+    },
+    /// Makes `revision` the working-copy commit, like `jj edit`.
+    Edit {
+        revision: String,
+    },
+    /// Like `jj abandon`.
+    Abandon {
+        revision: String,
+    },
+    CreateBookmark {
+        name: String,
+        revision: String,
+    },
+    MoveBookmark {
+        name: String,
+        revision: String,
+    },
+    DeleteBookmark {
+        name: String,
+    },
+    TrackBookmark {
+        name: String,
+        remote: String,
+    },
+    /// Reverts the changes made by an operation, like `jj op undo`.
+    UndoOperation {
+        operation_id: String,
+    },
+    /// Restores the repository to its state as of an operation, like
+    /// `jj op restore`.
+    RestoreOperation {
+        operation_id: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Removed,
+    Conflicted,
+}
+
+/// A file that differs between the working-copy commit (`@`) and its parents (`@-`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkingCopyChange {
+    /// The path of the file, relative to the workspace root.
+    pub path: Arc<Path>,
+    pub kind: ChangeKind,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkingCopyStatus {
+    /// The hex ID of the operation the status was read at.
+    pub operation_id: String,
+    pub changes: Vec<WorkingCopyChange>,
+}
+
+/// A commit matched by a revset, as shown by `jj log`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// The full commit ID, in hex.
+    pub commit_id: SharedString,
+    /// The full change ID, in jj's reverse-hex alphabet.
+    pub change_id: SharedString,
+    pub description: SharedString,
+    pub author_name: SharedString,
+    pub author_email: SharedString,
+    /// The author timestamp, in seconds since the Unix epoch.
+    pub timestamp: i64,
+    /// The local bookmarks pointing at the commit.
+    pub bookmarks: Vec<SharedString>,
+    pub is_working_copy: bool,
+    pub is_empty: bool,
+    pub has_conflict: bool,
+    /// The edges to the commit's nearest ancestors within the revset.
+    pub parents: Vec<LogEdge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEdge {
+    /// The commit ID of the ancestor.
+    pub target: SharedString,
+    pub kind: LogEdgeKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogEdgeKind {
+    /// The ancestor is a parent of the commit.
+    Direct,
+    /// Commits between the two aren't in the revset.
+    Indirect,
+    /// The parent isn't in the revset, and neither are any of its ancestors.
+    Missing,
+}
+
+/// The files changed by a commit, compared to its parents.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitDiff {
+    pub files: Vec<CommitFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitFile {
+    /// The path of the file, relative to the workspace root.
+    pub path: Arc<Path>,
+    /// The file's contents in the commit's parents, if it existed there as text.
+    pub old_text: Option<String>,
+    /// The file's contents in the commit, if it exists there as text.
+    pub new_text: Option<String>,
+}
+
+/// An operation in the operation log, as shown by `jj op log`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationLogEntry {
+    /// The full operation ID, in hex.
+    pub id: SharedString,
+    pub description: SharedString,
+    /// When the operation finished, in seconds since the Unix epoch.
+    pub timestamp: i64,
+    /// The user and host that made the operation, as `user@host`.
+    pub user: SharedString,
+    /// Whether this is the root operation, which can't be undone.
+    pub is_root: bool,
+}
+
+/// How an operation changed the repository's view, compared to its parents.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperationDiff {
+    /// Commits that the operation made visible, children first.
+    pub added_commits: Vec<LogEntry>,
+    /// Commits that the operation hid, such as rewritten or abandoned ones.
+    pub removed_commits: Vec<LogEntry>,
+    pub bookmark_changes: Vec<BookmarkChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookmarkChange {
+    pub name: SharedString,
+    /// The commit ID the bookmark pointed to before the operation, if it
+    /// existed and wasn't conflicted.
+    pub old_target: Option<SharedString>,
+    /// The commit ID the bookmark points to after the operation.
+    pub new_target: Option<SharedString>,
 }
 
 pub trait JujutsuRepository: Send + Sync {
-    fn list_bookmarks(&self) -> Vec<Bookmark>;
+    fn workspace_root(&self) -> &Path;
+
+    /// Returns the revset `jj log` shows: the `revsets.log` config, or
+    /// [`DEFAULT_LOG_REVSET`] when it isn't set.
+    fn log_revset(&self) -> Result<String>;
+
+    fn list_bookmarks(&self) -> Result<Vec<Bookmark>>;
+
+    /// Returns the files changed in the working-copy commit, as of jj's last
+    /// snapshot of the working copy.
+    fn working_copy_status(&self) -> Result<WorkingCopyStatus>;
+
+    /// Returns the contents of the file at `path` (relative to the workspace
+    /// root) in the parents of the working-copy commit. Returns `None` when the
+    /// file doesn't exist there or isn't valid UTF-8.
+    fn load_parent_text(&self, path: &Path) -> Result<Option<String>>;
+
+    /// Evaluates `revset` at the latest operation, returning at most `limit`
+    /// commits, children before their parents.
+    fn log(&self, revset: &str, limit: usize) -> Result<Vec<LogEntry>>;
+
+    /// Returns the changes `commit_id` (in hex) makes to its parents.
+    fn commit_diff(&self, commit_id: &str) -> Result<CommitDiff>;
+
+    /// Snapshots the working copy, applies `mutation` in a transaction, and
+    /// then updates the working copy if the working-copy commit changed.
+    fn mutate(&self, mutation: Mutation) -> Result<()>;
+
+    /// Returns at most `limit` operations leading up to the latest one,
+    /// newest first.
+    fn operation_log(&self, limit: usize) -> Result<Vec<OperationLogEntry>>;
+
+    /// Loads how the operation with the given hex ID changed the repository,
+    /// listing at most `limit` added and removed commits.
+    fn operation_diff(&self, operation_id: &str, limit: usize) -> Result<OperationDiff>;
 }
 
 pub struct RealJujutsuRepository {
-    repository: Arc<jj_lib::repo::ReadonlyRepo>,
+    settings: UserSettings,
+    repo_loader: RepoLoader,
+    workspace_name: WorkspaceNameBuf,
+    workspace_root: PathBuf,
+    /// Whether the workspace is also a git working tree, whose refs and
+    /// `HEAD` jj keeps in sync with its own.
+    colocated: bool,
 }
 
 impl RealJujutsuRepository {
@@ -26,7 +303,7 @@ impl RealJujutsuRepository {
         let workspace_loader_factory = DefaultWorkspaceLoaderFactory;
         let workspace_loader = workspace_loader_factory.create(Self::find_workspace_dir(cwd))?;
 
-        let config = StackedConfig::with_defaults();
+        let config = load_config(workspace_loader.repo_path())?;
         let settings = UserSettings::from_config(config)?;
 
         let workspace = workspace_loader.load(
@@ -35,10 +312,20 @@ impl RealJujutsuRepository {
             &workspace::default_working_copy_factories(),
         )?;
 
-        let repo_loader = workspace.repo_loader();
-        let repository = repo_loader.load_at_head()?;
+        let colocated = git::get_git_backend(workspace.repo_loader().store())
+            .ok()
+            .and_then(|git_backend| git_backend.git_workdir()?.canonicalize().ok())
+            .is_some_and(|git_workdir| {
+                workspace.workspace_root().canonicalize().ok() == Some(git_workdir)
+            });
 
-        Ok(Self { repository })
+        Ok(Self {
+            repo_loader: workspace.repo_loader().clone(),
+            settings,
+            workspace_name: workspace.workspace_name().to_owned(),
+            workspace_root: workspace.workspace_root().to_path_buf(),
+            colocated,
+        })
     }
 
     fn find_workspace_dir(cwd: &Path) -> &Path {
@@ -46,24 +333,1158 @@ impl RealJujutsuRepository {
             .find(|path| path.join(".jj").is_dir())
             .unwrap_or(cwd)
     }
+
+    /// Loads the latest operation, so that changes made by other jj processes are observed.
+    fn working_copy_commit(&self) -> Result<(Arc<ReadonlyRepo>, Commit)> {
+        let repository = self.repo_loader.load_at_head()?;
+        let commit_id = repository
+            .view()
+            .get_wc_commit_id(&self.workspace_name)
+            .with_context(|| {
+                format!(
+                    "workspace {:?} has no working-copy commit",
+                    self.workspace_name.as_str()
+                )
+            })?;
+        let commit = repository.store().get_commit(commit_id)?;
+        Ok((repository, commit))
+    }
+
+    fn parse_revset(&self, revset: &str) -> Result<Arc<UserRevsetExpression>> {
+        let mut aliases_map = RevsetAliasesMap::new();
+        for (declaration, definition) in BUILTIN_REVSET_ALIASES {
+            aliases_map.insert(*declaration, *definition)?;
+        }
+        for declaration in self.settings.table_keys("revset-aliases") {
+            let definition = self.settings.get_string(["revset-aliases", declaration])?;
+            aliases_map
+                .insert(declaration, definition)
+                .with_context(|| format!("invalid revset alias {declaration:?}"))?;
+        }
+        let path_converter = self.path_converter();
+        let context = RevsetParseContext {
+            aliases_map: &aliases_map,
+            local_variables: Default::default(),
+            user_email: self.settings.user_email(),
+            date_pattern_context: chrono::Local::now().into(),
+            extensions: &RevsetExtensions::default(),
+            workspace: Some(RevsetWorkspaceContext {
+                path_converter: &path_converter,
+                workspace_name: &self.workspace_name,
+            }),
+        };
+        let mut diagnostics = RevsetDiagnostics::new();
+        revset::parse(&mut diagnostics, revset, &context)
+            .with_context(|| format!("invalid revset {revset:?}"))
+    }
+
+    fn path_converter(&self) -> RepoPathUiConverter {
+        RepoPathUiConverter::Fs {
+            cwd: self.workspace_root.clone(),
+            base: self.workspace_root.clone(),
+        }
+    }
+
+    fn evaluate_revset<'a>(
+        &self,
+        repository: &'a ReadonlyRepo,
+        revset: &str,
+    ) -> Result<Box<dyn Revset + 'a>> {
+        let expression = self.parse_revset(revset)?;
+        let symbol_resolver = SymbolResolver::new(repository, &[]);
+        Ok(expression
+            .resolve_user_expression(repository, &symbol_resolver)?
+            .evaluate(repository)?)
+    }
+
+    fn resolve_single(&self, repository: &ReadonlyRepo, revision: &str) -> Result<Commit> {
+        let mut commit_ids = self.evaluate_revset(repository, revision)?.iter();
+        let commit_id = commit_ids
+            .next()
+            .transpose()?
+            .with_context(|| format!("revset {revision:?} didn't resolve to any commits"))?;
+        if commit_ids.next().is_some() {
+            bail!("revset {revision:?} resolved to more than one commit");
+        }
+        Ok(repository.store().get_commit(&commit_id)?)
+    }
+
+    /// Fails if any of `commits` are immutable, as jj refuses to rewrite them.
+    fn ensure_mutable(&self, repository: &ReadonlyRepo, commits: &[&Commit]) -> Result<()> {
+        let commit_ids = commits
+            .iter()
+            .map(|commit| commit.id().hex())
+            .collect::<Vec<_>>()
+            .join(" | ");
+        let immutable = self
+            .evaluate_revset(repository, &format!("({commit_ids}) & immutable()"))?
+            .iter()
+            .next()
+            .transpose()?;
+        if let Some(commit_id) = immutable {
+            bail!("commit {} is immutable", commit_id.hex());
+        }
+        Ok(())
+    }
+
+    fn load_workspace(&self) -> Result<Workspace> {
+        let workspace_loader = DefaultWorkspaceLoaderFactory.create(&self.workspace_root)?;
+        Ok(workspace_loader.load(
+            &self.settings,
+            &StoreFactories::default(),
+            &workspace::default_working_copy_factories(),
+        )?)
+    }
+
+    /// The files that snapshots start tracking, from `snapshot.auto-track`.
+    fn auto_track_matcher(&self) -> Result<Box<dyn Matcher>> {
+        let pattern = self
+            .settings
+            .get_string("snapshot.auto-track")
+            .optional()?
+            .unwrap_or_else(|| DEFAULT_AUTO_TRACK.to_string());
+        let expression = fileset::parse(
+            &mut FilesetDiagnostics::new(),
+            &pattern,
+            &self.path_converter(),
+        )
+        .with_context(|| format!("invalid snapshot.auto-track {pattern:?}"))?;
+        Ok(expression.to_matcher())
+    }
+
+    /// The ignores that apply beneath the workspace's `.gitignore` files: git's
+    /// global excludes file and the repository's `info/exclude`.
+    fn base_ignores(&self) -> Result<Arc<GitIgnoreFile>> {
+        let mut ignores = GitIgnoreFile::empty();
+        let Ok(git_backend) = git::get_git_backend(self.repo_loader.store()) else {
+            return Ok(ignores);
+        };
+        let excludes_file = git_backend
+            .git_repo()
+            .config_snapshot()
+            .string("core.excludesFile")
+            .and_then(|path| Some(expand_home_path(std::str::from_utf8(&path).ok()?)))
+            .or_else(|| Some(git_config_dir()?.join("ignore")));
+        if let Some(excludes_file) = excludes_file {
+            ignores = ignores.chain_with_file("", self.workspace_root.join(excludes_file))?;
+        }
+        let info_exclude = git_backend.git_repo_path().join("info").join("exclude");
+        Ok(ignores.chain_with_file("", info_exclude)?)
+    }
+
+    /// Imports `HEAD` and the refs of a colocated git repository, as jj does
+    /// before every command, so that commits made with git are seen.
+    fn import_git(&self) -> Result<()> {
+        if !self.colocated {
+            return Ok(());
+        }
+        let repository = self.repo_loader.load_at_head()?;
+        let mut transaction = repository.start_transaction();
+        git::import_head(transaction.repo_mut())?;
+        git::import_refs(transaction.repo_mut(), &self.settings.git_settings()?)?;
+        if transaction.repo().has_changes() {
+            transaction.repo_mut().rebase_descendants()?;
+            transaction.commit("import git refs")?;
+        }
+        Ok(())
+    }
+
+    /// Exports the bookmarks in `repo` to a colocated git repository and moves
+    /// its `HEAD` to the parent of the working-copy commit, as jj does after
+    /// every command.
+    fn export_git(&self, repo: &mut MutableRepo) -> Result<()> {
+        if !self.colocated {
+            return Ok(());
+        }
+        // Like jj, leave refs that can't be exported, e.g. conflicted
+        // bookmarks, as they are rather than failing the mutation.
+        git::export_refs(repo)?;
+        if let Some(commit_id) = repo.view().get_wc_commit_id(&self.workspace_name).cloned() {
+            let commit = repo.store().get_commit(&commit_id)?;
+            git::reset_head(repo, &commit)?;
+        }
+        Ok(())
+    }
+
+    /// Records changes made on disk in the working-copy commit, as jj does
+    /// before every command, so that they aren't lost when it's rewritten.
+    fn snapshot_working_copy(&self, workspace: &mut Workspace) -> Result<Arc<ReadonlyRepo>> {
+        let (repository, commit) = self.working_copy_commit()?;
+        let mut locked_workspace = workspace.start_working_copy_mutation()?;
+        if locked_workspace.locked_wc().old_tree_id() != commit.tree_id() {
+            bail!("the working copy is stale, run `jj workspace update-stale` to update it");
+        }
+
+        let start_tracking_matcher = self.auto_track_matcher()?;
+        let options = SnapshotOptions {
+            base_ignores: self.base_ignores()?,
+            fsmonitor_settings: FsmonitorSettings::None,
+            progress: None,
+            start_tracking_matcher: start_tracking_matcher.as_ref(),
+            max_new_file_size: self.settings.max_new_file_size()?,
+            conflict_marker_style: ConflictMarkerStyle::default(),
+        };
+        let (tree_id, _stats) = locked_workspace.locked_wc().snapshot(&options)?;
+
+        let repository = if tree_id == *commit.tree_id() {
+            repository
+        } else {
+            let mut transaction = repository.start_transaction();
+            let repo = transaction.repo_mut();
+            let commit = repo.rewrite_commit(&commit).set_tree_id(tree_id).write()?;
+            repo.set_wc_commit(self.workspace_name.clone(), commit.id().clone())?;
+            repo.rebase_descendants()?;
+            transaction.commit("snapshot working copy")?
+        };
+        locked_workspace.finish(repository.op_id().clone())?;
+        Ok(repository)
+    }
+
+    /// Applies `mutation` to `repo`, returning the description of the operation.
+    fn apply_mutation(
+        &self,
+        repository: &ReadonlyRepo,
+        repo: &mut MutableRepo,
+        mutation: Mutation,
+    ) -> Result<String> {
+        match mutation {
+            Mutation::New { parents } => {
+                let parents = parents
+                    .iter()
+                    .map(|revision| self.resolve_single(repository, revision))
+                    .collect::<Result<Vec<_>>>()?;
+                let tree = block_on(merge_commit_trees(&*repo, &parents))?;
+                let commit = repo
+                    .new_commit(
+                        parents.iter().map(|parent| parent.id().clone()).collect(),
+                        tree.id(),
+                    )
+                    .write()?;
+                repo.edit(self.workspace_name.clone(), &commit)?;
+                Ok("new empty commit".into())
+            }
+            Mutation::Describe {
+                revision,
+                description,
+            } => {
+                let commit = self.resolve_single(repository, &revision)?;
+                self.ensure_mutable(repository, &[&commit])?;
+                repo.rewrite_commit(&commit)
+                    .set_description(complete_newline(description))
+                    .write()?;
+                Ok(format!("describe commit {}", commit.id().hex()))
+            }
+            Mutation::Squash { revision } => {
+                let commit = self.resolve_single(repository, &revision)?;
+                let parents = commit.parents().collect::<Result<Vec<_>, _>>()?;
+                let [parent] = parents.as_slice() else {
+                    bail!("can't squash a commit with more than one parent");
+                };
+                self.ensure_mutable(repository, &[&commit, parent])?;
+                let description = match (parent.description(), commit.description()) {
+                    (parent, "") => parent.to_string(),
+                    ("", description) => description.to_string(),
+                    (parent, description) => format!("{parent}\n{description}"),
+                };
+                repo.rewrite_commit(parent)
+                    .set_tree_id(commit.tree_id().clone())
+                    .set_description(description)
+                    .write()?;
+                repo.record_abandoned_commit(&commit);
+                Ok(format!(
+                    "squash commit {} into its parent",
+                    commit.id().hex()
+                ))
+            }
+            Mutation::Split { revision, paths } => {
+                let commit = self.resolve_single(repository, &revision)?;
+                self.ensure_mutable(repository, &[&commit])?;
+                let paths = paths
+                    .iter()
+                    .map(|path| RepoPathBuf::from_relative_path(path))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let parent_tree = commit.parent_tree(repository)?;
+                let tree = commit.tree()?;
+                let first_tree_id = block_on(restore_tree(
+                    &tree,
+                    &parent_tree,
+                    &FilesMatcher::new(&paths),
+                ))?;
+                if first_tree_id == *parent_tree.id() || first_tree_id == *commit.tree_id() {
+                    bail!("splitting out the selected paths would leave one of the commits empty");
+                }
+
+                let first = repo
+                    .rewrite_commit(&commit)
+                    .set_tree_id(first_tree_id)
+                    .write()?;
+                let second = repo
+                    .rewrite_commit(&commit)
+                    .set_parents(vec![first.id().clone()])
+                    .set_tree_id(commit.tree_id().clone())
+                    .generate_new_change_id()
+                    .set_description(String::new())
+                    .write()?;
+                // Descendants, and the working copy, follow the second commit.
+                repo.set_rewritten_commit(commit.id().clone(), second.id().clone());
+                Ok(format!("split commit {}", commit.id().hex()))
+            }
+            Mutation::Edit { revision } => {
+                let commit = self.resolve_single(repository, &revision)?;
+                self.ensure_mutable(repository, &[&commit])?;
+                repo.edit(self.workspace_name.clone(), &commit)?;
+                Ok(format!("edit commit {}", commit.id().hex()))
+            }
+            Mutation::Abandon { revision } => {
+                let commit = self.resolve_single(repository, &revision)?;
+                self.ensure_mutable(repository, &[&commit])?;
+                repo.record_abandoned_commit(&commit);
+                Ok(format!("abandon commit {}", commit.id().hex()))
+            }
+            Mutation::CreateBookmark { name, revision } => {
+                let ref_name = bookmark_name(&name)?;
+                if repo.view().get_local_bookmark(ref_name).is_present() {
+                    bail!("bookmark {name:?} already exists");
+                }
+                let commit = self.resolve_single(repository, &revision)?;
+                repo.set_local_bookmark_target(ref_name, RefTarget::normal(commit.id().clone()));
+                Ok(format!(
+                    "create bookmark {name} pointing to commit {}",
+                    commit.id().hex()
+                ))
+            }
+            Mutation::MoveBookmark { name, revision } => {
+                let ref_name = bookmark_name(&name)?;
+                if repo.view().get_local_bookmark(ref_name).is_absent() {
+                    bail!("no such bookmark: {name:?}");
+                }
+                let commit = self.resolve_single(repository, &revision)?;
+                repo.set_local_bookmark_target(ref_name, RefTarget::normal(commit.id().clone()));
+                Ok(format!(
+                    "point bookmark {name} to commit {}",
+                    commit.id().hex()
+                ))
+            }
+            Mutation::DeleteBookmark { name } => {
+                let ref_name = bookmark_name(&name)?;
+                if repo.view().get_local_bookmark(ref_name).is_absent() {
+                    bail!("no such bookmark: {name:?}");
+                }
+                repo.set_local_bookmark_target(ref_name, RefTarget::absent());
+                Ok(format!("delete bookmark {name}"))
+            }
+            Mutation::TrackBookmark { name, remote } => {
+                let symbol = bookmark_name(&name)?.to_remote_symbol(RemoteName::new(&remote));
+                if repo.view().get_remote_bookmark(symbol).is_absent() {
+                    bail!("no such remote bookmark: {name}@{remote}");
+                }
+                repo.track_remote_bookmark(symbol);
+                Ok(format!("track remote bookmark {name}@{remote}"))
+            }
+            Mutation::UndoOperation { operation_id } => {
+                let operation = self.resolve_operation(&operation_id)?;
+                let mut parents = operation.parents();
+                let Some(parent) = parents.next().transpose()? else {
+                    bail!("can't undo the root operation");
+                };
+                if parents.next().is_some() {
+                    bail!("can't undo a merge operation");
+                }
+                let bad_repository = self.load_at_operation(&operation)?;
+                let parent_repository = self.load_at_operation(&parent)?;
+                repo.merge(&bad_repository, &parent_repository)?;
+                Ok(format!("undo operation {}", operation.id().hex()))
+            }
+            Mutation::RestoreOperation { operation_id } => {
+                let operation = self.resolve_operation(&operation_id)?;
+                let target_repository = self.load_at_operation(&operation)?;
+                repo.set_view(target_repository.view().store_view().clone());
+                Ok(format!("restore to operation {}", operation.id().hex()))
+            }
+        }
+    }
+
+    fn resolve_operation(&self, operation_id: &str) -> Result<Operation> {
+        op_walk::resolve_op_for_load(&self.repo_loader, operation_id)
+            .with_context(|| format!("invalid operation {operation_id:?}"))
+    }
+
+    /// Loads the repository as it was after an operation, rather than at the
+    /// latest one.
+    fn load_at_operation(&self, operation: &Operation) -> Result<Arc<ReadonlyRepo>> {
+        Ok(self.repo_loader.load_at(operation)?)
+    }
+
+    /// Loads the repository as it was before an operation, merging the
+    /// parents of operations that reconciled concurrent ones.
+    fn load_before_operation(&self, operation: &Operation) -> Result<Option<Arc<ReadonlyRepo>>> {
+        let parents = operation.parents().collect::<Result<Vec<_>, _>>()?;
+        let parent = match parents.len() {
+            0 => return Ok(None),
+            1 => parents.into_iter().next().unwrap(),
+            _ => self.repo_loader.merge_operations(parents, None)?,
+        };
+        self.load_at_operation(&parent).map(Some)
+    }
+
+    /// Lists at most `limit` commits reachable from `heads` but not from
+    /// `excluded_heads`.
+    fn commits_between(
+        &self,
+        repository: &ReadonlyRepo,
+        excluded_heads: &ReadonlyRepo,
+        heads: &ReadonlyRepo,
+        limit: usize,
+    ) -> Result<Vec<CommitId>> {
+        let excluded_heads = excluded_heads.view().heads().iter().cloned().collect();
+        let heads = heads.view().heads().iter().cloned().collect();
+        let revset = ResolvedRevsetExpression::commits(excluded_heads)
+            .range(&ResolvedRevsetExpression::commits(heads))
+            .evaluate(repository)?;
+        Ok(revset.iter().take(limit).collect::<Result<_, _>>()?)
+    }
+
+    fn log_entry(
+        &self,
+        repository: &ReadonlyRepo,
+        commit_id: &CommitId,
+        parents: Vec<LogEdge>,
+    ) -> Result<LogEntry> {
+        let commit = repository.store().get_commit(commit_id)?;
+        let author = commit.author();
+        let view = repository.view();
+        Ok(LogEntry {
+            commit_id: commit_id.hex().into(),
+            change_id: commit.change_id().reverse_hex().into(),
+            description: commit.description().trim_end().to_string().into(),
+            author_name: author.name.clone().into(),
+            author_email: author.email.clone().into(),
+            timestamp: author.timestamp.timestamp.0 / 1000,
+            bookmarks: view
+                .local_bookmarks_for_commit(commit_id)
+                .map(|(name, _)| name.as_str().to_string().into())
+                .collect(),
+            is_working_copy: view.get_wc_commit_id(&self.workspace_name) == Some(commit_id),
+            is_empty: commit.is_empty(repository)?,
+            has_conflict: commit.has_conflict()?,
+            parents,
+        })
+    }
+}
+
+/// Reads a file's contents from a tree, if it's a file containing valid UTF-8.
+async fn read_text(
+    store: &Arc<Store>,
+    path: &RepoPath,
+    value: MergedTreeValue,
+) -> Result<Option<String>> {
+    match materialize_tree_value(store, path, value).await? {
+        MaterializedTreeValue::File(mut file) => {
+            let content = file.read_all(path).await?;
+            Ok(String::from_utf8(content).ok())
+        }
+        _ => Ok(None),
+    }
 }
 
 impl JujutsuRepository for RealJujutsuRepository {
-    fn list_bookmarks(&self) -> Vec<Bookmark> {
-        self.repository
+    fn workspace_root(&self) -> &Path {
+        &self.workspace_root
+    }
+
+    fn log_revset(&self) -> Result<String> {
+        Ok(self
+            .settings
+            .get_string("revsets.log")
+            .optional()?
+            .unwrap_or_else(|| DEFAULT_LOG_REVSET.to_string()))
+    }
+
+    fn list_bookmarks(&self) -> Result<Vec<Bookmark>> {
+        let repository = self.repo_loader.load_at_head()?;
+        let view = repository.view();
+        let local_bookmarks = view.local_bookmarks().map(|(ref_name, _)| Bookmark {
+            ref_name: ref_name.as_str().to_string().into(),
+            remote: None,
+            is_tracked: false,
+        });
+        let remote_bookmarks = view
+            .all_remote_bookmarks()
+            .filter(|(symbol, _)| symbol.remote.as_str() != LOCAL_GIT_REMOTE)
+            .map(|(symbol, remote_ref)| Bookmark {
+                ref_name: symbol.name.as_str().to_string().into(),
+                remote: Some(symbol.remote.as_str().to_string().into()),
+                is_tracked: remote_ref.is_tracked(),
+            });
+        Ok(local_bookmarks.chain(remote_bookmarks).collect())
+    }
+
+    fn working_copy_status(&self) -> Result<WorkingCopyStatus> {
+        let (repository, commit) = self.working_copy_commit()?;
+        let parent_tree = commit.parent_tree(repository.as_ref())?;
+        let tree = commit.tree()?;
+
+        block_on(async {
+            let mut changes = Vec::new();
+            let mut diff = parent_tree.diff_stream(&tree, &EverythingMatcher);
+            while let Some(entry) = diff.next().await {
+                let (before, after) = entry.values?;
+                let kind = if before.is_absent() {
+                    ChangeKind::Added
+                } else if after.is_absent() {
+                    ChangeKind::Removed
+                } else if !after.is_resolved() {
+                    ChangeKind::Conflicted
+                } else {
+                    ChangeKind::Modified
+                };
+                changes.push(WorkingCopyChange {
+                    path: Path::new(entry.path.as_internal_file_string()).into(),
+                    kind,
+                });
+            }
+            anyhow::Ok(WorkingCopyStatus {
+                operation_id: repository.op_id().hex(),
+                changes,
+            })
+        })
+    }
+
+    fn load_parent_text(&self, path: &Path) -> Result<Option<String>> {
+        let (repository, commit) = self.working_copy_commit()?;
+        let parent_tree = commit.parent_tree(repository.as_ref())?;
+        let repo_path = RepoPathBuf::from_relative_path(path)?;
+        let value = parent_tree.path_value(&repo_path)?;
+        block_on(read_text(repository.store(), &repo_path, value))
+    }
+
+    fn log(&self, revset: &str, limit: usize) -> Result<Vec<LogEntry>> {
+        let repository = self.repo_loader.load_at_head()?;
+        let revset = self.evaluate_revset(&repository, revset)?;
+
+        let mut entries = Vec::new();
+        for node in revset.iter_graph().take(limit) {
+            let (commit_id, edges) = node?;
+            let parents = edges
+                .into_iter()
+                .map(|edge| LogEdge {
+                    target: edge.target.hex().into(),
+                    kind: match edge.edge_type {
+                        GraphEdgeType::Direct => LogEdgeKind::Direct,
+                        GraphEdgeType::Indirect => LogEdgeKind::Indirect,
+                        GraphEdgeType::Missing => LogEdgeKind::Missing,
+                    },
+                })
+                .collect();
+            entries.push(self.log_entry(&repository, &commit_id, parents)?);
+        }
+        Ok(entries)
+    }
+
+    fn commit_diff(&self, commit_id: &str) -> Result<CommitDiff> {
+        let repository = self.repo_loader.load_at_head()?;
+        let id = CommitId::try_from_hex(commit_id)
+            .with_context(|| format!("invalid commit id {commit_id:?}"))?;
+        let commit = repository.store().get_commit(&id)?;
+        let parent_tree = commit.parent_tree(repository.as_ref())?;
+        let tree = commit.tree()?;
+
+        block_on(async {
+            let store = repository.store();
+            let mut files = Vec::new();
+            let mut diff = parent_tree.diff_stream(&tree, &EverythingMatcher);
+            while let Some(entry) = diff.next().await {
+                let (before, after) = entry.values?;
+                files.push(CommitFile {
+                    path: Path::new(entry.path.as_internal_file_string()).into(),
+                    old_text: read_text(store, &entry.path, before).await?,
+                    new_text: read_text(store, &entry.path, after).await?,
+                });
+            }
+            anyhow::Ok(CommitDiff { files })
+        })
+    }
+
+    fn mutate(&self, mutation: Mutation) -> Result<()> {
+        self.import_git()?;
+        let mut workspace = self.load_workspace()?;
+        let repository = self.snapshot_working_copy(&mut workspace)?;
+        let old_wc_commit_id = repository
+            .view()
+            .get_wc_commit_id(&self.workspace_name)
+            .cloned();
+
+        let mut transaction = repository.start_transaction();
+        let description = self.apply_mutation(&repository, transaction.repo_mut(), mutation)?;
+        transaction.repo_mut().rebase_descendants()?;
+        self.export_git(transaction.repo_mut())?;
+        let repository = transaction.commit(description)?;
+
+        let new_wc_commit_id = repository
             .view()
-            .bookmarks()
-            .map(|(ref_name, _target)| Bookmark {
-                ref_name: ref_name.as_str().to_string().into(),
+            .get_wc_commit_id(&self.workspace_name)
+            .cloned();
+        match new_wc_commit_id {
+            Some(commit_id) if Some(&commit_id) != old_wc_commit_id.as_ref() => {
+                let commit = repository.store().get_commit(&commit_id)?;
+                let old_tree_id = old_wc_commit_id
+                    .map(|commit_id| repository.store().get_commit(&commit_id))
+                    .transpose()?
+                    .map(|commit| commit.tree_id().clone());
+                workspace.check_out(
+                    repository.op_id().clone(),
+                    old_tree_id.as_ref(),
+                    &commit,
+                    &CheckoutOptions {
+                        conflict_marker_style: ConflictMarkerStyle::default(),
+                    },
+                )?;
+            }
+            _ => {
+                let locked_workspace = workspace.start_working_copy_mutation()?;
+                locked_workspace.finish(repository.op_id().clone())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn operation_log(&self, limit: usize) -> Result<Vec<OperationLogEntry>> {
+        let repository = self.repo_loader.load_at_head()?;
+        op_walk::walk_ancestors(slice::from_ref(repository.operation()))
+            .take(limit)
+            .map(|operation| {
+                let operation = operation?;
+                let metadata = operation.metadata();
+                Ok(OperationLogEntry {
+                    id: operation.id().hex().into(),
+                    description: metadata.description.clone().into(),
+                    timestamp: metadata.time.end.timestamp.0 / 1000,
+                    user: format!("{}@{}", metadata.username, metadata.hostname).into(),
+                    is_root: operation.parent_ids().is_empty(),
+                })
             })
             .collect()
     }
+
+    fn operation_diff(&self, operation_id: &str, limit: usize) -> Result<OperationDiff> {
+        let operation = self.resolve_operation(operation_id)?;
+        let repository = self.load_at_operation(&operation)?;
+        let Some(parent_repository) = self.load_before_operation(&operation)? else {
+            return Ok(OperationDiff::default());
+        };
+
+        // The index at an operation includes the commits of all its
+        // ancestors, so both ranges can be evaluated against it.
+        let added_commits = self
+            .commits_between(&repository, &parent_repository, &repository, limit)?
+            .iter()
+            .map(|commit_id| self.log_entry(&repository, commit_id, Vec::new()))
+            .collect::<Result<_>>()?;
+        let removed_commits = self
+            .commits_between(&repository, &repository, &parent_repository, limit)?
+            .iter()
+            .map(|commit_id| self.log_entry(&parent_repository, commit_id, Vec::new()))
+            .collect::<Result<_>>()?;
+
+        let old_bookmarks = parent_repository
+            .view()
+            .local_bookmarks()
+            .collect::<BTreeMap<_, _>>();
+        let new_bookmarks = repository
+            .view()
+            .local_bookmarks()
+            .collect::<BTreeMap<_, _>>();
+        let names = old_bookmarks
+            .keys()
+            .chain(new_bookmarks.keys())
+            .collect::<BTreeSet<_>>();
+        let bookmark_changes = names
+            .into_iter()
+            .filter_map(|name| {
+                let old_target = old_bookmarks.get(name).copied();
+                let new_target = new_bookmarks.get(name).copied();
+                if old_target == new_target {
+                    return None;
+                }
+                let normal_target = |target: Option<&RefTarget>| {
+                    Some(SharedString::from(target?.as_normal()?.hex()))
+                };
+                Some(BookmarkChange {
+                    name: name.as_str().to_string().into(),
+                    old_target: normal_target(old_target),
+                    new_target: normal_target(new_target),
+                })
+            })
+            .collect();
+
+        Ok(OperationDiff {
+            added_commits,
+            removed_commits,
+            bookmark_changes,
+        })
+    }
+}
+
+/// Layers the user's config and the repository's over jj's defaults, so that
+/// new commits are authored by the user.
+fn load_config(repo_dir: &Path) -> Result<StackedConfig> {
+    let mut config = StackedConfig::with_defaults();
+    for path in user_config_paths() {
+        if path.is_dir() {
+            config.load_dir(ConfigSource::User, &path)?;
+        } else if path.is_file() {
+            config.load_file(ConfigSource::User, path)?;
+        }
+    }
+    let repo_config = repo_dir.join("config.toml");
+    if repo_config.is_file() {
+        config.load_file(ConfigSource::Repo, repo_config)?;
+    }
+    Ok(config)
 }
 
-pub struct FakeJujutsuRepository {}
+/// The locations jj reads user config from, in order of increasing precedence.
+fn user_config_paths() -> Vec<PathBuf> {
+    if let Some(paths) = std::env::var_os("JJ_CONFIG") {
+        return std::env::split_paths(&paths)
+            .filter(|path| !path.as_os_str().is_empty())
+            .collect();
+    }
+    let mut paths = Vec::new();
+    if let Some(home_dir) = dirs::home_dir() {
+        paths.push(home_dir.join(".jjconfig.toml"));
+    }
+    if let Some(config_dir) = dirs::config_dir() {
+        paths.push(config_dir.join("jj").join("config.toml"));
+        paths.push(config_dir.join("jj").join("conf.d"));
+    }
+    paths
+}
+
+/// Expands a leading `~/` in a path from git's config, as git does.
+fn expand_home_path(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(path), Some(home_dir)) => home_dir.join(path),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// The directory git reads its global `ignore` file from.
+fn git_config_dir() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| Some(dirs::home_dir()?.join(".config")))?;
+    Some(config_dir.join("git"))
+}
+
+fn bookmark_name(name: &str) -> Result<&RefName> {
+    if name.is_empty() || name.chars().any(char::is_whitespace) {
+        bail!("invalid bookmark name {name:?}");
+    }
+    Ok(RefName::new(name))
+}
+
+fn complete_newline(mut description: String) -> String {
+    if !description.is_empty() && !description.ends_with('\n') {
+        description.push('\n');
+    }
+    description
+}
+
+#[derive(Default)]
+pub struct FakeJujutsuRepository {
+    pub workspace_root: PathBuf,
+    /// The `revsets.log` config.
+    pub log_revset: Option<String>,
+    pub bookmarks: Vec<Bookmark>,
+    /// The status, which tests can change to simulate new operations.
+    pub status: Mutex<WorkingCopyStatus>,
+    pub parent_texts: Mutex<HashMap<Arc<Path>, String>>,
+    pub log: Vec<LogEntry>,
+    /// Commit diffs by hex commit ID.
+    pub commit_diffs: HashMap<String, CommitDiff>,
+    pub operations: Vec<OperationLogEntry>,
+    /// Operation diffs by hex operation ID.
+    pub operation_diffs: HashMap<String, OperationDiff>,
+    /// The mutations applied so far, in order.
+    pub mutations: Mutex<Vec<Mutation>>,
+}
 
 impl JujutsuRepository for FakeJujutsuRepository {
-    fn list_bookmarks(&self) -> Vec<Bookmark> {
-        Vec::new()
+    fn workspace_root(&self) -> &Path {
+        &self.workspace_root
+    }
+
+    fn log_revset(&self) -> Result<String> {
+        Ok(self
+            .log_revset
+            .clone()
+            .unwrap_or_else(|| DEFAULT_LOG_REVSET.to_string()))
+    }
+
+    fn list_bookmarks(&self) -> Result<Vec<Bookmark>> {
+        Ok(self.bookmarks.clone())
+    }
+
+    fn working_copy_status(&self) -> Result<WorkingCopyStatus> {
+        Ok(self.status.lock().unwrap().clone())
+    }
+
+    fn load_parent_text(&self, path: &Path) -> Result<Option<String>> {
+        Ok(self.parent_texts.lock().unwrap().get(path).cloned())
+    }
+
+    fn log(&self, _revset: &str, limit: usize) -> Result<Vec<LogEntry>> {
+        Ok(self.log.iter().take(limit).cloned().collect())
+    }
+
+    fn commit_diff(&self, commit_id: &str) -> Result<CommitDiff> {
+        self.commit_diffs
+            .get(commit_id)
+            .cloned()
+            .with_context(|| format!("no commit {commit_id:?}"))
+    }
+
+    fn mutate(&self, mutation: Mutation) -> Result<()> {
+        self.mutations.lock().unwrap().push(mutation);
+        Ok(())
+    }
+
+    fn operation_log(&self, limit: usize) -> Result<Vec<OperationLogEntry>> {
+        Ok(self.operations.iter().take(limit).cloned().collect())
+    }
+
+    fn operation_diff(&self, operation_id: &str, _limit: usize) -> Result<OperationDiff> {
+        self.operation_diffs
+            .get(operation_id)
+            .cloned()
+            .with_context(|| format!("no operation {operation_id:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jj_lib::op_store::{RemoteRef, RemoteRefState};
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Initializes a workspace backed by jj's native store in a temporary
+    /// directory.
+    fn init_repository() -> (TempDir, RealJujutsuRepository) {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = UserSettings::from_config(StackedConfig::with_defaults()).unwrap();
+        Workspace::init_simple(&settings, dir.path()).unwrap();
+        let repository = RealJujutsuRepository::new(dir.path()).unwrap();
+        (dir, repository)
+    }
+
+    fn commit(repository: &RealJujutsuRepository, revision: &str) -> LogEntry {
+        let mut entries = repository.log(revision, 2).unwrap();
+        assert_eq!(entries.len(), 1, "{revision} should resolve to one commit");
+        entries.remove(0)
+    }
+
+    fn changed_paths(repository: &RealJujutsuRepository, revision: &str) -> Vec<String> {
+        let commit_id = commit(repository, revision).commit_id;
+        repository
+            .commit_diff(&commit_id)
+            .unwrap()
+            .files
+            .iter()
+            .map(|file| file.path.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let (dir, repository) = init_repository();
+        let old_working_copy = commit(&repository, "@");
+        fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+
+        repository
+            .mutate(Mutation::New {
+                parents: vec!["@".into()],
+            })
+            .unwrap();
+
+        let working_copy = commit(&repository, "@");
+        assert!(working_copy.is_empty);
+        assert_ne!(working_copy.change_id, old_working_copy.change_id);
+        // The file written before the mutation was snapshotted into the
+        // previous working-copy commit.
+        assert_eq!(
+            commit(&repository, "@-").change_id,
+            old_working_copy.change_id
+        );
+        assert_eq!(changed_paths(&repository, "@-"), ["a.txt"]);
+    }
+
+    #[test]
+    fn test_log_revset() {
+        let (dir, _) = init_repository();
+        fs::write(
+            dir.path().join(".jj/repo/config.toml"),
+            "[revsets]\nlog = \"all()\"\n",
+        )
+        .unwrap();
+
+        let repository = RealJujutsuRepository::new(dir.path()).unwrap();
+        assert_eq!(repository.log_revset().unwrap(), "all()");
+    }
+
+    #[test]
+    fn test_squash() {
+        let (dir, repository) = init_repository();
+        fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+        repository
+            .mutate(Mutation::New {
+                parents: vec!["@".into()],
+            })
+            .unwrap();
+        let parent = commit(&repository, "@-");
+        fs::write(dir.path().join("b.txt"), "b\n").unwrap();
+        repository
+            .mutate(Mutation::Describe {
+                revision: "@".into(),
+                description: "add b".into(),
+            })
+            .unwrap();
+
+        repository
+            .mutate(Mutation::Squash {
+                revision: "@".into(),
+            })
+            .unwrap();
+
+        let squashed = commit(&repository, "@-");
+        assert_eq!(squashed.change_id, parent.change_id);
+        assert_eq!(squashed.description, "add b");
+        assert_eq!(changed_paths(&repository, "@-"), ["a.txt", "b.txt"]);
+        // The squashed working-copy commit was replaced by a new, empty one.
+        assert!(commit(&repository, "@").is_empty);
+        assert_eq!(fs::read_to_string(dir.path().join("b.txt")).unwrap(), "b\n");
+    }
+
+    #[test]
+    fn test_split() {
+        let (dir, repository) = init_repository();
+        fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+        fs::write(dir.path().join("b.txt"), "b\n").unwrap();
+        let old_working_copy = commit(&repository, "@");
+
+        repository
+            .mutate(Mutation::Split {
+                revision: "@".into(),
+                paths: vec![Path::new("a.txt").into()],
+            })
+            .unwrap();
+
+        let first = commit(&repository, "@-");
+        assert_eq!(first.change_id, old_working_copy.change_id);
+        assert_eq!(changed_paths(&repository, "@-"), ["a.txt"]);
+        let second = commit(&repository, "@");
+        assert_ne!(second.change_id, old_working_copy.change_id);
+        assert_eq!(changed_paths(&repository, "@"), ["b.txt"]);
+
+        assert!(
+            repository
+                .mutate(Mutation::Split {
+                    revision: "@".into(),
+                    paths: vec![Path::new("b.txt").into()],
+                })
+                .is_err(),
+            "splitting out every change should fail"
+        );
+    }
+
+    #[test]
+    fn test_abandon() {
+        let (dir, repository) = init_repository();
+        fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+        repository
+            .mutate(Mutation::New {
+                parents: vec!["@".into()],
+            })
+            .unwrap();
+
+        repository
+            .mutate(Mutation::Abandon {
+                revision: "@-".into(),
+            })
+            .unwrap();
+
+        // The working-copy commit was rebased onto the root commit and
+        // checked out, removing the abandoned commit's file.
+        assert_eq!(
+            commit(&repository, "@-").commit_id,
+            commit(&repository, "root()").commit_id
+        );
+        assert!(!dir.path().join("a.txt").exists());
+        assert!(
+            repository
+                .mutate(Mutation::Abandon {
+                    revision: "root()".into(),
+                })
+                .is_err(),
+            "the root commit is immutable"
+        );
+    }
+
+    #[test]
+    fn test_bookmarks() {
+        let (_dir, repository) = init_repository();
+        repository
+            .mutate(Mutation::CreateBookmark {
+                name: "main".into(),
+                revision: "@".into(),
+            })
+            .unwrap();
+        assert_eq!(commit(&repository, "@").bookmarks, ["main"]);
+        assert!(
+            repository
+                .mutate(Mutation::CreateBookmark {
+                    name: "main".into(),
+                    revision: "@".into(),
+                })
+                .is_err(),
+            "bookmarks can't be created twice"
+        );
+
+        repository
+            .mutate(Mutation::New {
+                parents: vec!["@".into()],
+            })
+            .unwrap();
+        repository
+            .mutate(Mutation::MoveBookmark {
+                name: "main".into(),
+                revision: "@".into(),
+            })
+            .unwrap();
+        assert_eq!(commit(&repository, "@").bookmarks, ["main"]);
+        assert!(commit(&repository, "@-").bookmarks.is_empty());
+
+        repository
+            .mutate(Mutation::DeleteBookmark {
+                name: "main".into(),
+            })
+            .unwrap();
+        assert!(repository.list_bookmarks().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_track_bookmark() {
+        let (_dir, repository) = init_repository();
+        let repo = repository.repo_loader.load_at_head().unwrap();
+        let mut transaction = repo.start_transaction();
+        transaction.repo_mut().set_remote_bookmark(
+            RefName::new("feature").to_remote_symbol(RemoteName::new("origin")),
+            RemoteRef {
+                target: RefTarget::normal(repo.store().root_commit_id().clone()),
+                state: RemoteRefState::New,
+            },
+        );
+        transaction.commit("fetch from origin").unwrap();
+
+        repository
+            .mutate(Mutation::TrackBookmark {
+                name: "feature".into(),
+                remote: "origin".into(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            repository.list_bookmarks().unwrap(),
+            [
+                Bookmark {
+                    ref_name: "feature".into(),
+                    remote: None,
+                    is_tracked: false,
+                },
+                Bookmark {
+                    ref_name: "feature".into(),
+                    remote: Some("origin".into()),
+                    is_tracked: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_undo_operation() {
+        let (dir, repository) = init_repository();
+        fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+        repository
+            .mutate(Mutation::New {
+                parents: vec!["@".into()],
+            })
+            .unwrap();
+        let change_id = commit(&repository, "@-").change_id;
+        repository
+            .mutate(Mutation::Abandon {
+                revision: "@-".into(),
+            })
+            .unwrap();
+        assert!(!dir.path().join("a.txt").exists());
+        let abandon_operation = repository.operation_log(1).unwrap().remove(0);
+        assert!(abandon_operation.description.starts_with("abandon commit"));
+
+        repository
+            .mutate(Mutation::UndoOperation {
+                operation_id: abandon_operation.id.to_string(),
+            })
+            .unwrap();
+
+        assert_eq!(commit(&repository, "@-").change_id, change_id);
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "a\n");
+
+        let root_operation = repository.operation_log(usize::MAX).unwrap().pop().unwrap();
+        assert!(root_operation.is_root);
+        assert!(
+            repository
+                .mutate(Mutation::UndoOperation {
+                    operation_id: root_operation.id.to_string(),
+                })
+                .is_err(),
+            "the root operation can't be undone"
+        );
+    }
+
+    #[test]
+    fn test_restore_operation() {
+        let (dir, repository) = init_repository();
+        fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+        repository
+            .mutate(Mutation::New {
+                parents: vec!["@".into()],
+            })
+            .unwrap();
+        let operation = repository.operation_log(1).unwrap().remove(0);
+        let change_id = commit(&repository, "@-").change_id;
+        repository
+            .mutate(Mutation::Abandon {
+                revision: "@-".into(),
+            })
+            .unwrap();
+        fs::write(dir.path().join("b.txt"), "b\n").unwrap();
+        repository
+            .mutate(Mutation::New {
+                parents: vec!["@".into()],
+            })
+            .unwrap();
+
+        repository
+            .mutate(Mutation::RestoreOperation {
+                operation_id: operation.id.to_string(),
+            })
+            .unwrap();
+
+        assert_eq!(commit(&repository, "@-").change_id, change_id);
+        assert!(commit(&repository, "@").is_empty);
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "a\n");
+        assert!(!dir.path().join("b.txt").exists());
     }
 }
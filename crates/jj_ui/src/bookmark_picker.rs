@@ -2,15 +2,20 @@ use std::sync::Arc;
 
 use fuzzy::{StringMatchCandidate, match_strings};
 use gpui::{
-    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity, Window,
-    prelude::*,
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString, Task,
+    WeakEntity, Window, prelude::*,
 };
-use jj::{Bookmark, JujutsuStore};
+use jj::{Bookmark, Mutation};
+use localization::t;
 use picker::{Picker, PickerDelegate};
+use project::jj_store::Repository;
 use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
 use util::ResultExt as _;
+use workspace::notifications::DetachAndPromptErr;
 use workspace::{ModalView, Workspace};
 
+use crate::mutations;
+
 pub fn register(workspace: &mut Workspace) {
     workspace.register_action(open);
 }
@@ -21,14 +26,80 @@ fn open(
     window: &mut Window,
     cx: &mut Context<Workspace>,
 ) {
-    let Some(jj_store) = JujutsuStore::try_global(cx) else {
-        return;
-    };
-
-    workspace.toggle_modal(window, cx, |window, cx| {
-        let delegate = BookmarkPickerDelegate::new(cx.entity().downgrade(), jj_store, cx);
-        BookmarkPicker::new(delegate, window, cx)
-    });
+    if let Some((repository, _)) = mutations::target(workspace, window, cx) {
+        toggle(
+            workspace,
+            repository,
+            BookmarkPickerMode::Switch,
+            window,
+            cx,
+        );
+    }
+}
+
+/// What confirming a bookmark in the picker does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookmarkPickerMode {
+    /// Starts a new commit on top of the bookmark, like `jj new <bookmark>`.
+    Switch,
+    /// Creates a bookmark named after the query, pointing to `revision`.
+    Create {
+        revision: String,
+    },
+    Move {
+        revision: String,
+    },
+    Delete,
+    /// Lists the untracked remote bookmarks, to start tracking one.
+    Track,
+}
+
+impl BookmarkPickerMode {
+    fn lists(&self, bookmark: &Bookmark) -> bool {
+        match self {
+            Self::Switch | Self::Move { .. } | Self::Delete => bookmark.remote.is_none(),
+            Self::Create { .. } => false,
+            Self::Track => bookmark.remote.is_some() && !bookmark.is_tracked,
+        }
+    }
+}
+
+pub fn toggle(
+    workspace: &mut Workspace,
+    repository: Entity<Repository>,
+    mode: BookmarkPickerMode,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let bookmarks = repository.read(cx).bookmarks(cx);
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let bookmarks = bookmarks
+            .await?
+            .into_iter()
+            .filter(|bookmark| mode.lists(bookmark))
+            .collect();
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.toggle_modal(window, cx, |window, cx| {
+                let delegate = BookmarkPickerDelegate::new(
+                    cx.entity().downgrade(),
+                    repository,
+                    mode,
+                    bookmarks,
+                );
+                BookmarkPicker::new(delegate, window, cx)
+            });
+        })
+    })
+    .detach_and_prompt_err(
+        &t!(
+            "jj_ui.bookmark_picker.list_failed",
+            "Failed to list bookmarks"
+        ),
+        window,
+        cx,
+        |_, _, _| None,
+    );
 }
 
 pub struct BookmarkPicker {
@@ -70,37 +141,81 @@ struct BookmarkEntry {
 
 pub struct BookmarkPickerDelegate {
     picker: WeakEntity<BookmarkPicker>,
+    repository: Entity<Repository>,
+    mode: BookmarkPickerMode,
     matches: Vec<BookmarkEntry>,
     all_bookmarks: Vec<Bookmark>,
+    /// The name of the bookmark to create, when creating one.
+    new_bookmark: Option<SharedString>,
     selected_index: usize,
 }
 
 impl BookmarkPickerDelegate {
     fn new(
         picker: WeakEntity<BookmarkPicker>,
-        jj_store: Entity<JujutsuStore>,
-        cx: &mut Context<BookmarkPicker>,
+        repository: Entity<Repository>,
+        mode: BookmarkPickerMode,
+        bookmarks: Vec<Bookmark>,
     ) -> Self {
-        let bookmarks = jj_store.read(cx).repository().list_bookmarks();
-
         Self {
             picker,
+            repository,
+            mode,
             matches: Vec::new(),
             all_bookmarks: bookmarks,
+            new_bookmark: None,
             selected_index: 0,
         }
     }
+
+    fn new_bookmark_count(&self) -> usize {
+        usize::from(self.new_bookmark.is_some())
+    }
+
+    fn mutation(&self, bookmark: &Bookmark) -> Option<Mutation> {
+        let name = bookmark.ref_name.to_string();
+        Some(match &self.mode {
+            BookmarkPickerMode::Switch => Mutation::New {
+                parents: vec![format!("bookmarks(exact:{name:?})")],
+            },
+            BookmarkPickerMode::Create { .. } => return None,
+            BookmarkPickerMode::Move { revision } => Mutation::MoveBookmark {
+                name,
+                revision: revision.clone(),
+            },
+            BookmarkPickerMode::Delete => Mutation::DeleteBookmark { name },
+            BookmarkPickerMode::Track => Mutation::TrackBookmark {
+                name,
+                remote: bookmark.remote.as_ref()?.to_string(),
+            },
+        })
+    }
+}
+
+fn display_name(bookmark: &Bookmark) -> SharedString {
+    match &bookmark.remote {
+        Some(remote) => format!("{}@{remote}", bookmark.ref_name).into(),
+        None => bookmark.ref_name.clone(),
+    }
 }
 
 impl PickerDelegate for BookmarkPickerDelegate {
     type ListItem = ListItem;
 
     fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
-        "Select Bookmark…".into()
+        match self.mode {
+            BookmarkPickerMode::Create { .. } => t!(
+                "jj_ui.bookmark_picker.create_placeholder",
+                "Enter a bookmark name…"
+            ),
+            _ => t!("jj_ui.bookmark_picker.placeholder", "Select Bookmark…"),
+        }
+        .to_string()
+        .into()
     }
 
     fn match_count(&self) -> usize {
-        self.matches.len()
+        self.new_bookmark_count() + self.matches.len()
     }
 
     fn selected_index(&self) -> usize {
@@ -124,6 +239,10 @@ impl PickerDelegate for BookmarkPickerDelegate {
     ) -> Task<()> {
         let background = cx.background_executor().clone();
         let all_bookmarks = self.all_bookmarks.clone();
+        let new_bookmark = matches!(self.mode, BookmarkPickerMode::Create { .. })
+            .then(|| query.trim())
+            .filter(|name| !name.is_empty())
+            .map(|name| SharedString::from(name.to_string()));
 
         cx.spawn_in(window, async move |this, cx| {
             let matches = if query.is_empty() {
@@ -138,7 +257,7 @@ impl PickerDelegate for BookmarkPickerDelegate {
                 let candidates = all_bookmarks
                     .iter()
                     .enumerate()
-                    .map(|(ix, bookmark)| StringMatchCandidate::new(ix, &bookmark.ref_name))
+                    .map(|(ix, bookmark)| StringMatchCandidate::new(ix, &display_name(bookmark)))
                     .collect::<Vec<_>>();
                 match_strings(
                     &candidates,
@@ -160,13 +279,31 @@ impl PickerDelegate for BookmarkPickerDelegate {
 
             this.update(cx, |this, _cx| {
                 this.delegate.matches = matches;
+                this.delegate.new_bookmark = new_bookmark;
+                this.delegate.selected_index = 0;
             })
             .log_err();
         })
     }
 
-    fn confirm(&mut self, _secondary: bool, _window: &mut Window, _cx: &mut Context<Picker<Self>>) {
-        //
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let mutation = match (&self.mode, &self.new_bookmark) {
+            (BookmarkPickerMode::Create { revision }, Some(name)) if self.selected_index == 0 => {
+                Some(Mutation::CreateBookmark {
+                    name: name.to_string(),
+                    revision: revision.clone(),
+                })
+            }
+            _ => self
+                .matches
+                .get(self.selected_index - self.new_bookmark_count())
+                .and_then(|entry| self.mutation(&entry.bookmark)),
+        };
+        let Some(mutation) = mutation else {
+            return;
+        };
+        mutations::run(&self.repository, mutation, window, cx);
+        self.dismissed(window, cx);
     }
 
     fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
@@ -182,17 +319,24 @@ impl PickerDelegate for BookmarkPickerDelegate {
         _window: &mut Window,
         _cx: &mut Context<Picker<Self>>,
     ) -> Option<Self::ListItem> {
-        let entry = &self.matches.get(ix)?;
-
-        Some(
-            ListItem::new(ix)
-                .inset(true)
-                .spacing(ListItemSpacing::Sparse)
-                .toggle_state(selected)
-                .child(HighlightedLabel::new(
-                    entry.bookmark.ref_name.clone(),
-                    entry.positions.clone(),
-                )),
-        )
+        let item = ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+
+        if let Some(new_bookmark) = self.new_bookmark.as_ref()
+            && ix == 0
+        {
+            return Some(item.child(Label::new(t!(
+                "jj_ui.bookmark_picker.create",
+                "Create bookmark “{name}”",
+                name = new_bookmark
+            ))));
+        }
+        let entry = self.matches.get(ix - self.new_bookmark_count())?;
+        Some(item.child(HighlightedLabel::new(
+            display_name(&entry.bookmark),
+            entry.positions.clone(),
+        )))
     }
 }
@@ -0,0 +1,144 @@
+use anyhow::Context as _;
+use editor::Editor;
+use gpui::{App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Window};
+use jj::Mutation;
+use localization::t;
+use menu::Cancel;
+use project::jj_store::Repository;
+use ui::{KeyBinding, prelude::*};
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
+use zed_actions::jj::ConfirmDescription;
+
+use crate::mutations;
+
+/// Edits a commit's description, like `jj describe` does in `$EDITOR`.
+pub struct DescribeModal {
+    repository: Entity<Repository>,
+    /// The change ID of the commit, which keeps pointing at it after jj
+    /// snapshots the working copy and rewrites `@`.
+    change_id: String,
+    editor: Entity<Editor>,
+}
+
+impl DescribeModal {
+    /// Loads the description of `revision` and opens the modal to edit it.
+    pub fn toggle(
+        workspace: &mut Workspace,
+        repository: Entity<Repository>,
+        revision: String,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let entries = repository.read(cx).log(revision, 1, cx);
+        cx.spawn_in(window, async move |workspace, cx| {
+            let entry = entries
+                .await?
+                .into_iter()
+                .next()
+                .context("commit not found")?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    Self::new(
+                        repository,
+                        entry.change_id.to_string(),
+                        entry.description.to_string(),
+                        window,
+                        cx,
+                    )
+                });
+            })
+        })
+        .detach_and_prompt_err(
+            &t!("jj_ui.load_commit_failed", "Failed to load commit"),
+            window,
+            cx,
+            |_, _, _| None,
+        );
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        change_id: String,
+        description: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(4, 16, window, cx);
+            editor.set_placeholder_text(
+                &t!("jj_ui.describe.placeholder", "Describe the change…"),
+                window,
+                cx,
+            );
+            editor.set_text(description, window, cx);
+            editor
+        });
+        Self {
+            repository,
+            change_id,
+            editor,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &ConfirmDescription, window: &mut Window, cx: &mut Context<Self>) {
+        let mutation = Mutation::Describe {
+            revision: self.change_id.clone(),
+            description: self.editor.read(cx).text(cx),
+        };
+        mutations::run(&self.repository, mutation, window, cx);
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for DescribeModal {}
+impl ModalView for DescribeModal {}
+impl Focusable for DescribeModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Render for DescribeModal {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.editor.focus_handle(cx);
+        v_flex()
+            .key_context("JjDescribe")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::Pencil).size(IconSize::XSmall))
+                    .child(
+                        Headline::new(t!("jj_ui.describe.title", "Describe Commit"))
+                            .size(HeadlineSize::XSmall),
+                    ),
+            )
+            .child(div().px_3().w_full().child(self.editor.clone()))
+            .child(
+                h_flex().px_3().py_2().w_full().justify_end().child(
+                    Button::new(
+                        "confirm-description",
+                        t!("jj_ui.describe.confirm", "Describe"),
+                    )
+                    .key_binding(
+                        KeyBinding::for_action_in(&ConfirmDescription, &focus_handle, window, cx)
+                            .map(|kb| kb.size(rems_from_px(12.))),
+                    )
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.confirm(&ConfirmDescription, window, cx)
+                    })),
+                ),
+            )
+    }
+}
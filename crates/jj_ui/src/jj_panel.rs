@@ -0,0 +1,647 @@
+use std::{ops::Range, sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use db::kvp::KEY_VALUE_STORE;
+use feature_flags::{FeatureFlagAppExt as _, JjUiFeatureFlag};
+use fs::Fs;
+use git::repository::{CommitDetails, CommitDiff, CommitFile, RepoPath};
+use git_ui::commit_view::CommitView;
+use gpui::{
+    Action, App, AsyncWindowContext, Entity, EventEmitter, FocusHandle, Focusable, ScrollStrategy,
+    SharedString, Subscription, Task, UniformListScrollHandle, WeakEntity, Window, actions,
+    uniform_list,
+};
+use jj::{DEFAULT_LOG_REVSET, LogEntry};
+use localization::t;
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use panel::{PanelHeader, panel_button};
+use project::{
+    Project,
+    jj_store::{JujutsuStoreEvent, Repository},
+};
+use serde::{Deserialize, Serialize};
+use settings::{Settings as _, SettingsStore};
+use ui::{ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::DetachAndPromptErr,
+};
+
+use crate::{
+    active_repository,
+    jj_panel_settings::JjPanelSettings,
+    log_graph::{self, GraphRow},
+    revset_picker::RevsetPicker,
+};
+
+actions!(
+    jj_panel,
+    [
+        /// Toggles focus on the Jujutsu panel.
+        ToggleFocus,
+        /// Picks the revset whose commits the Jujutsu panel lists.
+        SelectRevset
+    ]
+);
+
+const JJ_PANEL_KEY: &str = "JjPanel";
+
+const SERIALIZATION_THROTTLE_TIME: Duration = Duration::from_millis(50);
+
+/// The most commits the panel loads, since revsets like `all()` can match the
+/// whole history.
+const LOG_LIMIT: usize = 1000;
+
+const SHORT_CHANGE_ID_LENGTH: usize = 8;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+        workspace.toggle_panel_focus::<JjPanel>(window, cx);
+    });
+    workspace.register_action(|workspace, _: &SelectRevset, window, cx| {
+        let Some(panel) = workspace.panel::<JjPanel>(cx) else {
+            return;
+        };
+        let configured_revset = panel.read(cx).configured_revset(cx);
+        let current_revset = panel.read(cx).revset(cx);
+        let panel = panel.downgrade();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            RevsetPicker::new(panel, configured_revset, current_revset, window, cx)
+        });
+    });
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedJjPanel {
+    width: Option<Pixels>,
+    #[serde(default)]
+    revset: Option<String>,
+}
+
+/// Lists the commits matched by a revset in the active Jujutsu workspace,
+/// drawn as a graph like `jj log`.
+pub struct JjPanel {
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    repository: Option<Entity<Repository>>,
+    /// The revset picked in the panel, overriding the `log_revset` setting.
+    revset_override: Option<SharedString>,
+    /// The `log_revset` setting, which overrides the repository's default.
+    log_revset_setting: Option<SharedString>,
+    entries: Vec<LogEntry>,
+    graph: Vec<GraphRow>,
+    selected_entry: Option<usize>,
+    error: Option<SharedString>,
+    loading: bool,
+    width: Option<Pixels>,
+    pending_serialization: Task<()>,
+    reload_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl JjPanel {
+    pub fn new(workspace: &Workspace, cx: &mut Context<Self>) -> Self {
+        let project = workspace.project().clone();
+        let jj_store = project.read(cx).jj_store().clone();
+
+        let subscriptions = vec![
+            cx.subscribe(&jj_store, |this, _, event, cx| match *event {
+                JujutsuStoreEvent::RepositoryAdded(_) | JujutsuStoreEvent::RepositoryRemoved(_) => {
+                    this.update_repository(cx);
+                }
+                JujutsuStoreEvent::RepositoryUpdated(id) => {
+                    if this
+                        .repository
+                        .as_ref()
+                        .is_some_and(|repository| repository.read(cx).id == id)
+                    {
+                        this.reload(cx);
+                    }
+                }
+            }),
+            cx.subscribe(&project, |this, _, event, cx| {
+                if let project::Event::ActiveEntryChanged(Some(_)) = event {
+                    this.update_repository(cx);
+                }
+            }),
+            cx.observe_global::<SettingsStore>(|this, cx| {
+                let log_revset_setting = JjPanelSettings::get_global(cx)
+                    .log_revset
+                    .clone()
+                    .map(SharedString::from);
+                if log_revset_setting != this.log_revset_setting {
+                    this.log_revset_setting = log_revset_setting;
+                    if this.revset_override.is_none() {
+                        this.reload(cx);
+                    }
+                }
+            }),
+        ];
+
+        let mut this = Self {
+            workspace: workspace.weak_handle(),
+            project,
+            fs: workspace.app_state().fs.clone(),
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            repository: None,
+            revset_override: None,
+            log_revset_setting: JjPanelSettings::get_global(cx)
+                .log_revset
+                .clone()
+                .map(SharedString::from),
+            entries: Vec::new(),
+            graph: Vec::new(),
+            selected_entry: None,
+            error: None,
+            loading: false,
+            width: None,
+            pending_serialization: Task::ready(()),
+            reload_task: Task::ready(()),
+            _subscriptions: subscriptions,
+        };
+        this.update_repository(cx);
+        this
+    }
+
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        let serialized_panel = match workspace
+            .read_with(&cx, |workspace, _| Self::serialization_key(workspace))
+            .ok()
+            .flatten()
+        {
+            Some(serialization_key) => cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(&serialization_key) })
+                .await
+                .context("loading jj panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedJjPanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten(),
+            None => None,
+        };
+
+        workspace.update_in(&mut cx, |workspace, _, cx| {
+            let panel = cx.new(|cx| JjPanel::new(workspace, cx));
+
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width;
+                    if let Some(revset) = serialized_panel.revset {
+                        panel.revset_override = Some(revset.into());
+                        panel.reload(cx);
+                    }
+                    cx.notify();
+                })
+            }
+
+            panel
+        })
+    }
+
+    fn serialization_key(workspace: &Workspace) -> Option<String> {
+        workspace
+            .database_id()
+            .map(|id| i64::from(id).to_string())
+            .or(workspace.session_id())
+            .map(|id| format!("{}-{:?}", JJ_PANEL_KEY, id))
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        let revset = self.revset_override.as_ref().map(ToString::to_string);
+
+        self.pending_serialization = cx.spawn(async move |jj_panel, cx| {
+            cx.background_executor()
+                .timer(SERIALIZATION_THROTTLE_TIME)
+                .await;
+            let Some(serialization_key) = jj_panel
+                .update(cx, |jj_panel, cx| {
+                    jj_panel
+                        .workspace
+                        .read_with(cx, |workspace, _| Self::serialization_key(workspace))
+                        .ok()
+                        .flatten()
+                })
+                .ok()
+                .flatten()
+            else {
+                return;
+            };
+            cx.background_spawn(
+                async move {
+                    KEY_VALUE_STORE
+                        .write_kvp(
+                            serialization_key,
+                            serde_json::to_string(&SerializedJjPanel { width, revset })?,
+                        )
+                        .await?;
+                    anyhow::Ok(())
+                }
+                .log_err(),
+            )
+            .await;
+        });
+    }
+
+    /// The revset the panel lists the commits of.
+    pub fn revset(&self, cx: &App) -> SharedString {
+        self.revset_override
+            .clone()
+            .unwrap_or_else(|| self.configured_revset(cx))
+    }
+
+    /// The revset listed when none was picked in the panel: the `log_revset`
+    /// setting if set, or else the one `jj log` shows in the repository.
+    pub fn configured_revset(&self, cx: &App) -> SharedString {
+        if let Some(revset) = &self.log_revset_setting {
+            return revset.clone();
+        }
+        match &self.repository {
+            Some(repository) => repository.read(cx).log_revset.clone().into(),
+            None => DEFAULT_LOG_REVSET.into(),
+        }
+    }
+
+    /// Lists the commits matching `revset`. Picking the configured revset
+    /// clears the override, so the panel follows later changes to the setting.
+    pub fn set_revset(&mut self, revset: SharedString, cx: &mut Context<Self>) {
+        self.revset_override = (revset != self.configured_revset(cx)).then_some(revset);
+        self.selected_entry = None;
+        self.serialize(cx);
+        self.reload(cx);
+    }
+
+    /// The repository and commit that jj actions apply to, if the panel is
+    /// focused and has a commit selected. The commit is named by its change ID,
+    /// since jj snapshots the working copy before each action, which rewrites
+    /// `@` and would leave a commit ID pointing at the old, hidden commit.
+    pub(crate) fn selected_revision(
+        &self,
+        window: &Window,
+        cx: &App,
+    ) -> Option<(Entity<Repository>, String)> {
+        if !self.focus_handle.contains_focused(window, cx) {
+            return None;
+        }
+        let entry = self.entries.get(self.selected_entry?)?;
+        Some((self.repository.clone()?, entry.change_id.to_string()))
+    }
+
+    fn update_repository(&mut self, cx: &mut Context<Self>) {
+        let repository = active_repository(&self.project, cx);
+        if repository != self.repository {
+            self.repository = repository;
+            self.selected_entry = None;
+            self.reload(cx);
+        }
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        let Some(repository) = self.repository.clone() else {
+            self.entries.clear();
+            self.graph.clear();
+            self.error = None;
+            self.loading = false;
+            self.reload_task = Task::ready(());
+            cx.notify();
+            return;
+        };
+        let log = repository
+            .read(cx)
+            .log(self.revset(cx).to_string(), LOG_LIMIT, cx);
+        self.loading = true;
+        cx.notify();
+
+        self.reload_task = cx.spawn(async move |this, cx| {
+            let log = log.await;
+            this.update(cx, |this, cx| {
+                this.loading = false;
+                match log {
+                    Ok(entries) => {
+                        let selected_commit = this
+                            .selected_entry
+                            .and_then(|ix| this.entries.get(ix))
+                            .map(|entry| entry.commit_id.clone());
+                        this.graph = log_graph::layout(&entries);
+                        this.entries = entries;
+                        this.selected_entry = selected_commit.and_then(|commit_id| {
+                            this.entries
+                                .iter()
+                                .position(|entry| entry.commit_id == commit_id)
+                        });
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        this.entries.clear();
+                        this.graph.clear();
+                        this.selected_entry = None;
+                        this.error = Some(format!("{error:#}").into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.entries.is_empty() {
+            self.select_entry(0, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.entries.is_empty() {
+            self.select_entry(self.entries.len() - 1, cx);
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = self
+            .selected_entry
+            .map_or(0, |ix| (ix + 1).min(self.entries.len() - 1));
+        self.select_entry(ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = self.selected_entry.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_entry(ix, cx);
+    }
+
+    fn select_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_entry = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_entry {
+            self.open_commit(ix, window, cx);
+        }
+    }
+
+    /// Opens the changes the commit at `ix` makes in a commit view.
+    fn open_commit(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(repository), Some(entry)) = (self.repository.clone(), self.entries.get(ix))
+        else {
+            return;
+        };
+        let commit = CommitDetails {
+            sha: entry.commit_id.clone(),
+            message: entry.description.clone(),
+            commit_timestamp: entry.timestamp,
+            author_email: entry.author_email.clone(),
+            author_name: entry.author_name.clone(),
+        };
+        let workspace_root = repository.read(cx).workspace_root.clone();
+        let commit_diff = repository
+            .read(cx)
+            .commit_diff(entry.commit_id.to_string(), cx);
+        let workspace = self.workspace.clone();
+
+        cx.spawn_in(window, async move |_, cx| {
+            let commit_diff = commit_diff.await?;
+            let commit_diff = CommitDiff {
+                files: commit_diff
+                    .files
+                    .into_iter()
+                    .map(|file| CommitFile {
+                        path: RepoPath(file.path),
+                        old_text: file.old_text,
+                        new_text: file.new_text,
+                    })
+                    .collect(),
+            };
+            workspace.update_in(cx, |workspace, window, cx| {
+                CommitView::show(commit, commit_diff, workspace_root, workspace, window, cx);
+            })
+        })
+        .detach_and_prompt_err(
+            &t!("jj_ui.panel.open_commit_failed", "Failed to open commit"),
+            window,
+            cx,
+            |_, _, _| None,
+        );
+    }
+
+    fn render_panel_header(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.panel_header_container(window, cx)
+            .px_2()
+            .justify_between()
+            .child(
+                panel_button(self.revset(cx))
+                    .icon(IconName::Filter)
+                    .icon_position(IconPosition::Start)
+                    .color(Color::Muted)
+                    .tooltip(Tooltip::for_action_title_in(
+                        t!("jj_ui.panel.select_revset", "Select Revset"),
+                        &SelectRevset,
+                        &self.focus_handle,
+                    ))
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(SelectRevset.boxed_clone(), cx)
+                    }),
+            )
+    }
+
+    fn render_empty_state(&self) -> impl IntoElement {
+        let message = if self.repository.is_none() {
+            t!("jj_ui.panel.no_repository", "No Jujutsu repositories")
+        } else if let Some(error) = self.error.clone() {
+            error
+        } else if self.loading {
+            t!("jj_ui.panel.loading", "Loading…")
+        } else {
+            t!("jj_ui.panel.no_commits", "No commits match the revset")
+        };
+        h_flex().size_full().justify_center().child(
+            Label::new(message)
+                .color(if self.error.is_some() {
+                    Color::Error
+                } else {
+                    Color::Muted
+                })
+                .size(LabelSize::Small),
+        )
+    }
+
+    fn render_entries(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        uniform_list(
+            "jj_log_entries",
+            self.entries.len(),
+            cx.processor(|this, range: Range<usize>, _window, cx| {
+                range.filter_map(|ix| this.render_entry(ix, cx)).collect()
+            }),
+        )
+        .size_full()
+        .track_scroll(self.scroll_handle.clone())
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> Option<AnyElement> {
+        let entry = self.entries.get(ix)?;
+        let graph = self.graph.get(ix)?.to_text();
+        let change_id = entry
+            .change_id
+            .get(..SHORT_CHANGE_ID_LENGTH)
+            .unwrap_or(entry.change_id.as_ref())
+            .to_string();
+        let description = entry.description.lines().next().unwrap_or_default();
+
+        Some(
+            ListItem::new(ix)
+                .spacing(ListItemSpacing::Dense)
+                .toggle_state(self.selected_entry == Some(ix))
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.selected_entry = Some(ix);
+                    this.open_commit(ix, window, cx);
+                }))
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .overflow_hidden()
+                        .child(Label::new(graph).buffer_font(cx).color(Color::Muted))
+                        .child(Label::new(change_id).buffer_font(cx).color(Color::Accent))
+                        .children(entry.bookmarks.iter().map(|bookmark| {
+                            Label::new(bookmark.clone())
+                                .color(Color::Info)
+                                .size(LabelSize::Small)
+                        }))
+                        .when(entry.has_conflict, |this| {
+                            this.child(
+                                Label::new(t!("jj_ui.panel.conflict", "conflict"))
+                                    .color(Color::Error)
+                                    .size(LabelSize::Small),
+                            )
+                        })
+                        .when(entry.is_empty, |this| {
+                            this.child(
+                                Label::new(t!("jj_ui.panel.empty", "(empty)"))
+                                    .color(Color::Success)
+                                    .size(LabelSize::Small),
+                            )
+                        })
+                        .map(|this| {
+                            if description.is_empty() {
+                                this.child(
+                                    Label::new(t!(
+                                        "jj_ui.panel.no_description",
+                                        "(no description set)"
+                                    ))
+                                    .color(Color::Warning)
+                                    .truncate(),
+                                )
+                            } else {
+                                this.child(Label::new(description.to_string()).truncate())
+                            }
+                        }),
+                )
+                .into_any_element(),
+        )
+    }
+}
+
+impl Render for JjPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("jj_panel")
+            .key_context("JjPanel")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .overflow_hidden()
+            .bg(cx.theme().colors().panel_background)
+            .when(self.repository.is_some(), |this| {
+                this.child(self.render_panel_header(window, cx))
+            })
+            .map(|this| {
+                if self.entries.is_empty() {
+                    this.child(self.render_empty_state().into_any_element())
+                } else {
+                    this.child(self.render_entries(cx).into_any_element())
+                }
+            })
+    }
+}
+
+impl Focusable for JjPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for JjPanel {}
+
+impl Panel for JjPanel {
+    fn persistent_name() -> &'static str {
+        "JjPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        JjPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.jj_panel.get_or_insert_default().dock = Some(position.into())
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| JjPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<ui::IconName> {
+        Some(ui::IconName::GitBranch).filter(|_| JjPanelSettings::get_global(cx).button)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some(t!("jj_ui.panel.tooltip", "Jujutsu Panel"))
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        4
+    }
+
+    fn enabled(&self, cx: &App) -> bool {
+        cx.has_flag::<JjUiFeatureFlag>()
+    }
+}
+
+impl PanelHeader for JjPanel {}
@@ -0,0 +1,24 @@
+use gpui::Pixels;
+use settings::Settings;
+use ui::px;
+use workspace::dock::DockPosition;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JjPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+    pub log_revset: Option<String>,
+}
+
+impl Settings for JjPanelSettings {
+    fn from_settings(content: &settings::SettingsContent, _cx: &mut ui::App) -> Self {
+        let jj_panel = content.jj_panel.clone().unwrap();
+        Self {
+            button: jj_panel.button.unwrap(),
+            dock: jj_panel.dock.unwrap().into(),
+            default_width: px(jj_panel.default_width.unwrap()),
+            log_revset: jj_panel.log_revset,
+        }
+    }
+}
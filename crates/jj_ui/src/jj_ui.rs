@@ -1,36 +1,70 @@
 mod bookmark_picker;
+mod describe_modal;
+pub mod jj_panel;
+mod jj_panel_settings;
+mod log_graph;
+mod mutations;
+mod operation_log;
+mod revset_picker;
+mod split_picker;
 
 use command_palette_hooks::CommandPaletteFilter;
 use feature_flags::FeatureFlagAppExt as _;
-use gpui::App;
-use jj::JujutsuStore;
+use gpui::{App, Entity};
+use project::{Project, jj_store::Repository};
+use settings::Settings as _;
 use workspace::Workspace;
 
+use crate::jj_panel_settings::JjPanelSettings;
+
 pub fn init(cx: &mut App) {
-    JujutsuStore::init_global(cx);
+    JjPanelSettings::register(cx);
 
     cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
         bookmark_picker::register(workspace);
+        jj_panel::register(workspace);
+        mutations::register(workspace);
+        operation_log::register(workspace);
     })
     .detach();
 
     feature_gate_jj_ui_actions(cx);
 }
 
+/// Returns the Jujutsu workspace of the active entry's worktree, falling back
+/// to the first visible worktree that has one.
+pub(crate) fn active_repository(project: &Entity<Project>, cx: &App) -> Option<Entity<Repository>> {
+    let project = project.read(cx);
+    let jj_store = project.jj_store().read(cx);
+    project
+        .active_entry()
+        .and_then(|entry_id| project.worktree_for_entry(entry_id, cx))
+        .and_then(|worktree| jj_store.repository_for_worktree(worktree.read(cx).id()))
+        .or_else(|| {
+            project
+                .visible_worktrees(cx)
+                .find_map(|worktree| jj_store.repository_for_worktree(worktree.read(cx).id()))
+        })
+}
+
 fn feature_gate_jj_ui_actions(cx: &mut App) {
-    const JJ_ACTION_NAMESPACE: &str = "jj";
+    const JJ_ACTION_NAMESPACES: &[&str] = &["jj", "jj_panel"];
 
     CommandPaletteFilter::update_global(cx, |filter, _cx| {
-        filter.hide_namespace(JJ_ACTION_NAMESPACE);
+        for &namespace in JJ_ACTION_NAMESPACES {
+            filter.hide_namespace(namespace);
+        }
     });
 
     cx.observe_flag::<feature_flags::JjUiFeatureFlag, _>({
         move |is_enabled, cx| {
             CommandPaletteFilter::update_global(cx, |filter, _cx| {
-                if is_enabled {
-                    filter.show_namespace(JJ_ACTION_NAMESPACE);
-                } else {
-                    filter.hide_namespace(JJ_ACTION_NAMESPACE);
+                for &namespace in JJ_ACTION_NAMESPACES {
+                    if is_enabled {
+                        filter.show_namespace(namespace);
+                    } else {
+                        filter.hide_namespace(namespace);
+                    }
                 }
             });
         }
@@ -0,0 +1,193 @@
+use gpui::SharedString;
+use jj::{LogEdgeKind, LogEntry};
+
+/// One column of a row in the log graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GraphCell {
+    Empty,
+    Node(NodeKind),
+    /// An edge passing through the row on its way to an ancestor further down.
+    Edge(LogEdgeKind),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NodeKind {
+    WorkingCopy,
+    Conflicted,
+    Normal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GraphRow {
+    pub cells: Vec<GraphCell>,
+    /// Whether some of the commit's ancestors aren't in the revset at all.
+    pub elided: bool,
+}
+
+impl GraphRow {
+    /// Renders the row with the glyphs `jj log` uses.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for cell in &self.cells {
+            text.push(match cell {
+                GraphCell::Empty => ' ',
+                GraphCell::Node(NodeKind::WorkingCopy) => '@',
+                GraphCell::Node(NodeKind::Conflicted) => '×',
+                GraphCell::Node(NodeKind::Normal) => '○',
+                GraphCell::Edge(LogEdgeKind::Indirect) => '╎',
+                GraphCell::Edge(_) => '│',
+            });
+            text.push(' ');
+        }
+        if self.elided {
+            text.push_str("~ ");
+        }
+        text
+    }
+}
+
+/// Assigns each entry a column, keeping a lane open from every commit down to
+/// each of its ancestors in the log. Entries must be ordered children first,
+/// as [`jj::JujutsuRepository::log`] returns them.
+pub(crate) fn layout(entries: &[LogEntry]) -> Vec<GraphRow> {
+    // Each lane holds the commit it's heading towards, and the kind of edge.
+    let mut lanes: Vec<Option<(SharedString, LogEdgeKind)>> = Vec::new();
+    let mut rows = Vec::with_capacity(entries.len());
+
+    for entry in entries {
+        let mut column = None;
+        for (ix, lane) in lanes.iter_mut().enumerate() {
+            if lane
+                .as_ref()
+                .is_some_and(|(target, _)| *target == entry.commit_id)
+            {
+                *lane = None;
+                column.get_or_insert(ix);
+            }
+        }
+        let column = column.unwrap_or_else(|| free_lane(&mut lanes));
+
+        let node = if entry.is_working_copy {
+            NodeKind::WorkingCopy
+        } else if entry.has_conflict {
+            NodeKind::Conflicted
+        } else {
+            NodeKind::Normal
+        };
+        let mut cells = lanes
+            .iter()
+            .map(|lane| match lane {
+                Some((_, kind)) => GraphCell::Edge(*kind),
+                None => GraphCell::Empty,
+            })
+            .collect::<Vec<_>>();
+        cells[column] = GraphCell::Node(node);
+        rows.push(GraphRow {
+            cells,
+            elided: entry
+                .parents
+                .iter()
+                .any(|edge| edge.kind == LogEdgeKind::Missing),
+        });
+
+        let mut first_parent = true;
+        for edge in &entry.parents {
+            if edge.kind == LogEdgeKind::Missing
+                || lanes
+                    .iter()
+                    .flatten()
+                    .any(|(target, _)| *target == edge.target)
+            {
+                continue;
+            }
+            let ix = if first_parent && lanes[column].is_none() {
+                column
+            } else {
+                free_lane(&mut lanes)
+            };
+            lanes[ix] = Some((edge.target.clone(), edge.kind));
+            first_parent = false;
+        }
+
+        while lanes.last().is_some_and(Option::is_none) {
+            lanes.pop();
+        }
+    }
+
+    rows
+}
+
+fn free_lane(lanes: &mut Vec<Option<(SharedString, LogEdgeKind)>>) -> usize {
+    if let Some(ix) = lanes.iter().position(Option::is_none) {
+        ix
+    } else {
+        lanes.push(None);
+        lanes.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jj::LogEdge;
+
+    fn entry(commit_id: &'static str, parents: &[(&'static str, LogEdgeKind)]) -> LogEntry {
+        LogEntry {
+            commit_id: commit_id.into(),
+            change_id: commit_id.into(),
+            description: SharedString::default(),
+            author_name: SharedString::default(),
+            author_email: SharedString::default(),
+            timestamp: 0,
+            bookmarks: Vec::new(),
+            is_working_copy: false,
+            is_empty: false,
+            has_conflict: false,
+            parents: parents
+                .iter()
+                .map(|(target, kind)| LogEdge {
+                    target: (*target).into(),
+                    kind: *kind,
+                })
+                .collect(),
+        }
+    }
+
+    fn render(entries: &[LogEntry]) -> Vec<String> {
+        layout(entries)
+            .iter()
+            .map(|row| row.to_text().trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn linear_history() {
+        let mut working_copy = entry("c", &[("b", LogEdgeKind::Direct)]);
+        working_copy.is_working_copy = true;
+        let entries = [
+            working_copy,
+            entry("b", &[("a", LogEdgeKind::Indirect)]),
+            entry("a", &[("root", LogEdgeKind::Missing)]),
+        ];
+        assert_eq!(render(&entries), ["@", "○", "○ ~"]);
+    }
+
+    #[test]
+    fn branches_and_merges() {
+        let mut conflicted = entry("c", &[("a", LogEdgeKind::Direct)]);
+        conflicted.has_conflict = true;
+        let entries = [
+            entry(
+                "merge",
+                &[("b", LogEdgeKind::Direct), ("c", LogEdgeKind::Direct)],
+            ),
+            entry("b", &[("a", LogEdgeKind::Direct)]),
+            conflicted,
+            entry("a", &[]),
+            entry("other", &[("x", LogEdgeKind::Indirect)]),
+            entry("side", &[("x", LogEdgeKind::Direct)]),
+            entry("x", &[]),
+        ];
+        assert_eq!(render(&entries), ["○", "○ │", "│ ×", "○", "○", "╎ ○", "○"]);
+    }
+}
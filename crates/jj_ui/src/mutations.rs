@@ -0,0 +1,109 @@
+use gpui::{App, Entity, Window};
+use jj::Mutation;
+use localization::t;
+use project::jj_store::Repository;
+use workspace::{Workspace, notifications::DetachAndPromptErr};
+use zed_actions::jj::{
+    Abandon, CreateBookmark, DeleteBookmark, Describe, Edit, MoveBookmark, New, Split, Squash,
+    TrackBookmark,
+};
+
+use crate::{
+    active_repository,
+    bookmark_picker::{self, BookmarkPickerMode},
+    describe_modal::DescribeModal,
+    jj_panel::JjPanel,
+    split_picker::SplitPicker,
+};
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &New, window, cx| {
+        if let Some((repository, revision)) = target(workspace, window, cx) {
+            let parents = vec![revision];
+            run(&repository, Mutation::New { parents }, window, cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &Describe, window, cx| {
+        if let Some((repository, revision)) = target(workspace, window, cx) {
+            DescribeModal::toggle(workspace, repository, revision, window, cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &Squash, window, cx| {
+        if let Some((repository, revision)) = target(workspace, window, cx) {
+            run(&repository, Mutation::Squash { revision }, window, cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &Split, window, cx| {
+        if let Some((repository, revision)) = target(workspace, window, cx) {
+            SplitPicker::toggle(workspace, repository, revision, window, cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &Edit, window, cx| {
+        if let Some((repository, revision)) = target(workspace, window, cx) {
+            run(&repository, Mutation::Edit { revision }, window, cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &Abandon, window, cx| {
+        if let Some((repository, revision)) = target(workspace, window, cx) {
+            run(&repository, Mutation::Abandon { revision }, window, cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &CreateBookmark, window, cx| {
+        if let Some((repository, revision)) = target(workspace, window, cx) {
+            let mode = BookmarkPickerMode::Create { revision };
+            bookmark_picker::toggle(workspace, repository, mode, window, cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &MoveBookmark, window, cx| {
+        if let Some((repository, revision)) = target(workspace, window, cx) {
+            let mode = BookmarkPickerMode::Move { revision };
+            bookmark_picker::toggle(workspace, repository, mode, window, cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &DeleteBookmark, window, cx| {
+        if let Some((repository, _)) = target(workspace, window, cx) {
+            let mode = BookmarkPickerMode::Delete;
+            bookmark_picker::toggle(workspace, repository, mode, window, cx);
+        }
+    });
+    workspace.register_action(|workspace, _: &TrackBookmark, window, cx| {
+        if let Some((repository, _)) = target(workspace, window, cx) {
+            let mode = BookmarkPickerMode::Track;
+            bookmark_picker::toggle(workspace, repository, mode, window, cx);
+        }
+    });
+}
+
+/// The repository and revision that jj actions apply to: the commit selected
+/// in the Jujutsu panel while it's focused, or else the working-copy commit
+/// of the active repository.
+pub(crate) fn target(
+    workspace: &Workspace,
+    window: &Window,
+    cx: &App,
+) -> Option<(Entity<Repository>, String)> {
+    workspace
+        .panel::<JjPanel>(cx)
+        .and_then(|panel| panel.read(cx).selected_revision(window, cx))
+        .or_else(|| Some((active_repository(workspace.project(), cx)?, "@".to_string())))
+}
+
+/// Applies `mutation`, prompting with the error if jj refuses it.
+pub(crate) fn run(
+    repository: &Entity<Repository>,
+    mutation: Mutation,
+    window: &Window,
+    cx: &mut App,
+) {
+    repository
+        .update(cx, |repository, cx| repository.mutate(mutation, cx))
+        .detach_and_prompt_err(
+            &t!(
+                "jj_ui.mutation_failed",
+                "Failed to update the jj repository"
+            ),
+            window,
+            cx,
+            |error, _, _| Some(format!("{error:#}")),
+        );
+}
@@ -0,0 +1,552 @@
+use std::ops::Range;
+
+use gpui::{
+    App, Entity, EventEmitter, FocusHandle, Focusable, ScrollStrategy, SharedString, Subscription,
+    Task, UniformListScrollHandle, Window, uniform_list,
+};
+use jj::{LogEntry, Mutation, OperationDiff, OperationLogEntry};
+use localization::t;
+use menu::{SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::jj_store::Repository;
+use time::OffsetDateTime;
+use time_format::format_local_timestamp;
+use ui::{ListItem, ListItemSpacing, Tooltip, prelude::*};
+use workspace::{Item, Workspace, item::ItemEvent};
+use zed_actions::jj::{OperationLog, RestoreOperation, UndoOperation};
+
+use crate::mutations;
+
+/// The most operations the view loads, since the log grows with every command.
+const OPERATION_LOG_LIMIT: usize = 1000;
+
+/// The most added and removed commits shown for an operation, since imports
+/// from git can touch the whole history.
+const OPERATION_DIFF_LIMIT: usize = 100;
+
+const SHORT_OPERATION_ID_LENGTH: usize = 12;
+
+const SHORT_ID_LENGTH: usize = 8;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(OperationLogView::deploy);
+}
+
+/// Lists the operations in a Jujutsu repository's operation log, like
+/// `jj op log`, along with what the selected one changed.
+pub struct OperationLogView {
+    repository: Entity<Repository>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    operations: Vec<OperationLogEntry>,
+    selected_operation: Option<usize>,
+    diff: Option<OperationDiff>,
+    error: Option<SharedString>,
+    loading: bool,
+    reload_task: Task<()>,
+    diff_task: Task<()>,
+    _subscription: Subscription,
+}
+
+impl OperationLogView {
+    fn deploy(
+        workspace: &mut Workspace,
+        _: &OperationLog,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some((repository, _)) = mutations::target(workspace, window, cx) else {
+            return;
+        };
+        let existing = workspace
+            .items_of_type::<Self>(cx)
+            .find(|view| view.read(cx).repository == repository);
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+        } else {
+            let view = cx.new(|cx| Self::new(repository, cx));
+            workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+        }
+    }
+
+    fn new(repository: Entity<Repository>, cx: &mut Context<Self>) -> Self {
+        // The repository is updated whenever a new operation is committed.
+        let subscription = cx.subscribe(&repository, |this, _, _, cx| this.reload(cx));
+        let mut this = Self {
+            repository,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            operations: Vec::new(),
+            selected_operation: None,
+            diff: None,
+            error: None,
+            loading: false,
+            reload_task: Task::ready(()),
+            diff_task: Task::ready(()),
+            _subscription: subscription,
+        };
+        this.reload(cx);
+        this
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        let operations = self
+            .repository
+            .read(cx)
+            .operation_log(OPERATION_LOG_LIMIT, cx);
+        self.loading = true;
+        cx.notify();
+
+        self.reload_task = cx.spawn(async move |this, cx| {
+            let operations = operations.await;
+            this.update(cx, |this, cx| {
+                this.loading = false;
+                match operations {
+                    Ok(operations) => {
+                        let selected_id = this.selected_operation().map(|op| op.id.clone());
+                        this.operations = operations;
+                        let selected_operation = selected_id
+                            .and_then(|id| this.operations.iter().position(|op| op.id == id))
+                            .or((!this.operations.is_empty()).then_some(0));
+                        this.error = None;
+                        this.select_operation(selected_operation, cx);
+                    }
+                    Err(error) => {
+                        this.operations.clear();
+                        this.error = Some(format!("{error:#}").into());
+                        this.select_operation(None, cx);
+                    }
+                }
+            })
+            .ok();
+        });
+    }
+
+    fn selected_operation(&self) -> Option<&OperationLogEntry> {
+        self.operations.get(self.selected_operation?)
+    }
+
+    fn select_operation(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
+        let previous_id = self.selected_operation().map(|op| op.id.clone());
+        self.selected_operation = ix;
+        if let Some(ix) = ix {
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+        }
+        cx.notify();
+
+        let Some(operation) = self.selected_operation() else {
+            self.diff = None;
+            self.diff_task = Task::ready(());
+            return;
+        };
+        if previous_id.as_ref() == Some(&operation.id) && self.diff.is_some() {
+            return;
+        }
+        let diff = self.repository.read(cx).operation_diff(
+            operation.id.to_string(),
+            OPERATION_DIFF_LIMIT,
+            cx,
+        );
+        self.diff = None;
+        self.diff_task = cx.spawn(async move |this, cx| {
+            let diff = diff.await;
+            this.update(cx, |this, cx| {
+                match diff {
+                    Ok(diff) => this.diff = Some(diff),
+                    Err(error) => {
+                        log::error!("failed to load jj operation diff: {error:#}");
+                        this.diff = Some(OperationDiff::default());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.operations.is_empty() {
+            self.select_operation(Some(0), cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.operations.is_empty() {
+            self.select_operation(Some(self.operations.len() - 1), cx);
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.operations.is_empty() {
+            return;
+        }
+        let ix = self
+            .selected_operation
+            .map_or(0, |ix| (ix + 1).min(self.operations.len() - 1));
+        self.select_operation(Some(ix), cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if self.operations.is_empty() {
+            return;
+        }
+        let ix = self.selected_operation.map_or(0, |ix| ix.saturating_sub(1));
+        self.select_operation(Some(ix), cx);
+    }
+
+    fn undo_operation(&mut self, _: &UndoOperation, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(operation) = self.selected_operation() else {
+            return;
+        };
+        let operation_id = operation.id.to_string();
+        mutations::run(
+            &self.repository,
+            Mutation::UndoOperation { operation_id },
+            window,
+            cx,
+        );
+    }
+
+    fn restore_operation(
+        &mut self,
+        _: &RestoreOperation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(operation) = self.selected_operation() else {
+            return;
+        };
+        let operation_id = operation.id.to_string();
+        mutations::run(
+            &self.repository,
+            Mutation::RestoreOperation { operation_id },
+            window,
+            cx,
+        );
+    }
+
+    fn render_empty_state(&self) -> impl IntoElement {
+        let message = if let Some(error) = self.error.clone() {
+            error
+        } else if self.loading {
+            t!("jj_ui.panel.loading", "Loading…")
+        } else {
+            t!("jj_ui.operation_log.no_operations", "No operations")
+        };
+        h_flex().size_full().justify_center().child(
+            Label::new(message)
+                .color(if self.error.is_some() {
+                    Color::Error
+                } else {
+                    Color::Muted
+                })
+                .size(LabelSize::Small),
+        )
+    }
+
+    fn render_operations(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        uniform_list(
+            "jj_operations",
+            self.operations.len(),
+            cx.processor(|this, range: Range<usize>, _window, cx| {
+                range
+                    .filter_map(|ix| this.render_operation(ix, cx))
+                    .collect()
+            }),
+        )
+        .size_full()
+        .track_scroll(self.scroll_handle.clone())
+    }
+
+    fn render_operation(&self, ix: usize, cx: &mut Context<Self>) -> Option<AnyElement> {
+        let operation = self.operations.get(ix)?;
+        // Like `jj op log`, mark the operation the repository is at.
+        let node = if ix == 0 { "@" } else { "○" };
+
+        Some(
+            ListItem::new(ix)
+                .spacing(ListItemSpacing::Dense)
+                .toggle_state(self.selected_operation == Some(ix))
+                .on_click(cx.listener(move |this, _, _window, cx| {
+                    this.select_operation(Some(ix), cx);
+                }))
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .overflow_hidden()
+                        .child(Label::new(node).buffer_font(cx).color(Color::Muted))
+                        .child(
+                            Label::new(short_operation_id(operation))
+                                .buffer_font(cx)
+                                .color(Color::Accent),
+                        )
+                        .child(
+                            Label::new(format_timestamp(operation.timestamp))
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        )
+                        .child(Label::new(operation.description.clone()).truncate()),
+                )
+                .into_any_element(),
+        )
+    }
+
+    fn render_details(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(operation) = self.selected_operation() else {
+            return div().into_any_element();
+        };
+        let focus_handle = self.focus_handle.clone();
+
+        v_flex()
+            .id("jj_operation_details")
+            .size_full()
+            .overflow_y_scroll()
+            .p_3()
+            .gap_3()
+            .child(
+                v_flex()
+                    .gap_1()
+                    .child(Headline::new(operation.description.clone()).size(HeadlineSize::Small))
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                Label::new(short_operation_id(operation))
+                                    .buffer_font(cx)
+                                    .color(Color::Accent),
+                            )
+                            .child(Label::new(operation.user.clone()).color(Color::Muted))
+                            .child(
+                                Label::new(format_timestamp(operation.timestamp))
+                                    .color(Color::Muted),
+                            ),
+                    ),
+            )
+            .when(!operation.is_root, |this| {
+                this.child(
+                    h_flex()
+                        .gap_2()
+                        .child(
+                            Button::new(
+                                "undo_operation",
+                                t!("jj_ui.operation_log.undo", "Undo Operation"),
+                            )
+                            .icon(IconName::Undo)
+                            .icon_position(IconPosition::Start)
+                            .tooltip(Tooltip::for_action_title_in(
+                                t!(
+                                    "jj_ui.operation_log.undo_tooltip",
+                                    "Revert the changes made by this operation"
+                                ),
+                                &UndoOperation,
+                                &focus_handle,
+                            ))
+                            .on_click(cx.listener(
+                                |this, _, window, cx| {
+                                    this.undo_operation(&UndoOperation, window, cx)
+                                },
+                            )),
+                        )
+                        .child(
+                            Button::new(
+                                "restore_operation",
+                                t!("jj_ui.operation_log.restore", "Restore to Operation"),
+                            )
+                            .icon(IconName::HistoryRerun)
+                            .icon_position(IconPosition::Start)
+                            .tooltip(Tooltip::for_action_title_in(
+                                t!(
+                                    "jj_ui.operation_log.restore_tooltip",
+                                    "Return the repository to its state after this operation"
+                                ),
+                                &RestoreOperation,
+                                &focus_handle,
+                            ))
+                            .on_click(cx.listener(
+                                |this, _, window, cx| {
+                                    this.restore_operation(&RestoreOperation, window, cx)
+                                },
+                            )),
+                        ),
+                )
+            })
+            .map(|this| match &self.diff {
+                None => this.child(
+                    Label::new(t!("jj_ui.panel.loading", "Loading…"))
+                        .color(Color::Muted)
+                        .size(LabelSize::Small),
+                ),
+                Some(diff)
+                    if diff.added_commits.is_empty()
+                        && diff.removed_commits.is_empty()
+                        && diff.bookmark_changes.is_empty() =>
+                {
+                    this.child(
+                        Label::new(t!(
+                            "jj_ui.operation_log.no_changes",
+                            "This operation didn't change any commits or bookmarks"
+                        ))
+                        .color(Color::Muted)
+                        .size(LabelSize::Small),
+                    )
+                }
+                Some(diff) => this
+                    .when(!diff.added_commits.is_empty(), |this| {
+                        this.child(render_section(
+                            t!("jj_ui.operation_log.added_commits", "Added commits"),
+                            diff.added_commits
+                                .iter()
+                                .map(|entry| render_commit(entry, Color::Created, cx)),
+                        ))
+                    })
+                    .when(!diff.removed_commits.is_empty(), |this| {
+                        this.child(render_section(
+                            t!("jj_ui.operation_log.removed_commits", "Removed commits"),
+                            diff.removed_commits
+                                .iter()
+                                .map(|entry| render_commit(entry, Color::Deleted, cx)),
+                        ))
+                    })
+                    .when(!diff.bookmark_changes.is_empty(), |this| {
+                        this.child(render_section(
+                            t!("jj_ui.operation_log.changed_bookmarks", "Changed bookmarks"),
+                            diff.bookmark_changes.iter().map(|change| {
+                                let target = |target: &Option<SharedString>| {
+                                    target.as_ref().map_or_else(
+                                        || SharedString::from("-"),
+                                        |commit_id| short_id(commit_id, SHORT_ID_LENGTH),
+                                    )
+                                };
+                                h_flex()
+                                    .gap_1()
+                                    .child(Label::new(change.name.clone()).color(Color::Info))
+                                    .child(
+                                        Label::new(target(&change.old_target))
+                                            .buffer_font(cx)
+                                            .color(Color::Muted),
+                                    )
+                                    .child(Label::new("→").color(Color::Muted))
+                                    .child(
+                                        Label::new(target(&change.new_target))
+                                            .buffer_font(cx)
+                                            .color(Color::Muted),
+                                    )
+                                    .into_any_element()
+                            }),
+                        ))
+                    }),
+            })
+            .into_any_element()
+    }
+}
+
+fn render_section(
+    title: SharedString,
+    rows: impl IntoIterator<Item = AnyElement>,
+) -> impl IntoElement {
+    v_flex()
+        .gap_1()
+        .child(Label::new(title).color(Color::Muted).size(LabelSize::Small))
+        .children(rows)
+}
+
+fn render_commit(entry: &LogEntry, color: Color, cx: &App) -> AnyElement {
+    let description = entry.description.lines().next().unwrap_or_default();
+    h_flex()
+        .gap_1()
+        .overflow_hidden()
+        .child(
+            Label::new(short_id(&entry.change_id, SHORT_ID_LENGTH))
+                .buffer_font(cx)
+                .color(color),
+        )
+        .map(|this| {
+            if description.is_empty() {
+                this.child(
+                    Label::new(t!("jj_ui.panel.no_description", "(no description set)"))
+                        .color(Color::Warning)
+                        .truncate(),
+                )
+            } else {
+                this.child(Label::new(description.to_string()).truncate())
+            }
+        })
+        .into_any_element()
+}
+
+fn short_id(id: &SharedString, length: usize) -> SharedString {
+    id.get(..length).unwrap_or(id.as_ref()).to_string().into()
+}
+
+fn short_operation_id(operation: &OperationLogEntry) -> SharedString {
+    short_id(&operation.id, SHORT_OPERATION_ID_LENGTH)
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    let timestamp = OffsetDateTime::from_unix_timestamp(timestamp)
+        .unwrap_or_else(|_| OffsetDateTime::now_utc());
+    format_local_timestamp(
+        timestamp,
+        OffsetDateTime::now_utc(),
+        time_format::TimestampFormat::Relative,
+    )
+}
+
+impl Render for OperationLogView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .id("jj_operation_log")
+            .key_context("JjOperationLog")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::undo_operation))
+            .on_action(cx.listener(Self::restore_operation))
+            .size_full()
+            .overflow_hidden()
+            .bg(cx.theme().colors().editor_background)
+            .map(|this| {
+                if self.operations.is_empty() {
+                    this.child(self.render_empty_state().into_any_element())
+                } else {
+                    this.child(
+                        div()
+                            .h_full()
+                            .w_1_2()
+                            .border_r_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .child(self.render_operations(cx)),
+                    )
+                    .child(div().h_full().w_1_2().child(self.render_details(cx)))
+                }
+            })
+    }
+}
+
+impl Focusable for OperationLogView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<()> for OperationLogView {}
+
+impl Item for OperationLogView {
+    type Event = ();
+
+    fn to_item_events(_: &Self::Event, _: impl FnMut(ItemEvent)) {}
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        t!("jj_ui.operation_log.title", "Operation Log")
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+}
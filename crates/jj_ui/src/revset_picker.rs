@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use fuzzy::{StringMatchCandidate, match_strings};
+use gpui::{
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString, Task,
+    WeakEntity, Window, prelude::*,
+};
+use localization::t;
+use picker::{Picker, PickerDelegate};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::ModalView;
+
+use crate::jj_panel::JjPanel;
+
+/// Revsets offered alongside the configured one. Anything else can be typed in.
+const PRESET_REVSETS: &[&str] = &["all()", "::@", "mine()", "bookmarks()", "conflicts()"];
+
+pub struct RevsetPicker {
+    picker: Entity<Picker<RevsetPickerDelegate>>,
+}
+
+impl RevsetPicker {
+    pub fn new(
+        panel: WeakEntity<JjPanel>,
+        configured_revset: SharedString,
+        current_revset: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut revsets = vec![configured_revset];
+        revsets.extend(
+            PRESET_REVSETS
+                .iter()
+                .map(|revset| SharedString::from(*revset)),
+        );
+        if !revsets.contains(&current_revset) {
+            revsets.insert(0, current_revset.clone());
+        }
+
+        let delegate = RevsetPickerDelegate {
+            picker: cx.entity().downgrade(),
+            panel,
+            revsets,
+            current_revset,
+            matches: Vec::new(),
+            custom_revset: None,
+            selected_index: 0,
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl ModalView for RevsetPicker {}
+
+impl EventEmitter<DismissEvent> for RevsetPicker {}
+
+impl Focusable for RevsetPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for RevsetPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RevsetMatch {
+    revset: SharedString,
+    positions: Vec<usize>,
+}
+
+pub struct RevsetPickerDelegate {
+    picker: WeakEntity<RevsetPicker>,
+    panel: WeakEntity<JjPanel>,
+    revsets: Vec<SharedString>,
+    current_revset: SharedString,
+    matches: Vec<RevsetMatch>,
+    /// The query, offered as the first match when it isn't one of the listed revsets.
+    custom_revset: Option<SharedString>,
+    selected_index: usize,
+}
+
+impl RevsetPickerDelegate {
+    fn custom_match_count(&self) -> usize {
+        usize::from(self.custom_revset.is_some())
+    }
+}
+
+impl PickerDelegate for RevsetPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        t!("jj_ui.revset_picker.placeholder", "Enter a revset…")
+            .to_string()
+            .into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.custom_match_count() + self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let revsets = self.revsets.clone();
+        let current_revset = self.current_revset.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let query = query.trim().to_string();
+            let matches = if query.is_empty() {
+                revsets
+                    .iter()
+                    .map(|revset| RevsetMatch {
+                        revset: revset.clone(),
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = revsets
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, revset)| StringMatchCandidate::new(ix, revset))
+                    .collect::<Vec<_>>();
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+                .into_iter()
+                .map(|mat| RevsetMatch {
+                    revset: revsets[mat.candidate_id].clone(),
+                    positions: mat.positions,
+                })
+                .collect()
+            };
+            let selected_index = if query.is_empty() {
+                revsets
+                    .iter()
+                    .position(|revset| *revset == current_revset)
+                    .unwrap_or_default()
+            } else {
+                0
+            };
+            let custom_revset = (!query.is_empty()
+                && !revsets.iter().any(|revset| revset.as_ref() == query))
+            .then(|| SharedString::from(query));
+
+            this.update(cx, |this, _cx| {
+                this.delegate.matches = matches;
+                this.delegate.custom_revset = custom_revset;
+                this.delegate.selected_index = selected_index;
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let revset = match self.custom_revset.as_ref() {
+            Some(custom_revset) if self.selected_index == 0 => Some(custom_revset.clone()),
+            _ => self
+                .matches
+                .get(self.selected_index - self.custom_match_count())
+                .map(|mat| mat.revset.clone()),
+        };
+        let Some(revset) = revset else {
+            return;
+        };
+        self.panel
+            .update(cx, |panel, cx| panel.set_revset(revset, cx))
+            .log_err();
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let item = ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+
+        if let Some(custom_revset) = self.custom_revset.as_ref()
+            && ix == 0
+        {
+            return Some(
+                item.child(
+                    h_flex()
+                        .gap_2()
+                        .child(Label::new(custom_revset.clone()))
+                        .child(
+                            Label::new(t!("jj_ui.revset_picker.custom", "Custom revset"))
+                                .color(Color::Muted),
+                        ),
+                ),
+            );
+        }
+        let mat = self.matches.get(ix - self.custom_match_count())?;
+        Some(item.child(HighlightedLabel::new(
+            mat.revset.clone(),
+            mat.positions.clone(),
+        )))
+    }
+}
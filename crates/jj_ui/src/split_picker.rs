@@ -0,0 +1,230 @@
+use std::{path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use fuzzy::{StringMatchCandidate, match_strings};
+use gpui::{
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity, Window,
+    prelude::*,
+};
+use jj::Mutation;
+use localization::t;
+use picker::{Picker, PickerDelegate};
+use project::jj_store::Repository;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace, notifications::DetachAndPromptErr};
+
+use crate::mutations;
+
+/// Picks a file whose changes are split out of a commit, like `jj split`.
+pub struct SplitPicker {
+    picker: Entity<Picker<SplitPickerDelegate>>,
+}
+
+impl SplitPicker {
+    /// Loads the files `revision` changes and opens the picker over them.
+    pub fn toggle(
+        workspace: &mut Workspace,
+        repository: Entity<Repository>,
+        revision: String,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let entries = repository.read(cx).log(revision, 1, cx);
+        cx.spawn_in(window, async move |workspace, cx| {
+            let entry = entries
+                .await?
+                .into_iter()
+                .next()
+                .context("commit not found")?;
+            let diff = repository
+                .read_with(cx, |repository, cx| {
+                    repository.commit_diff(entry.commit_id.to_string(), cx)
+                })?
+                .await?;
+            let change_id = entry.change_id.to_string();
+            let paths = diff.files.into_iter().map(|file| file.path).collect();
+            workspace.update_in(cx, |workspace, window, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    Self::new(repository, change_id, paths, window, cx)
+                });
+            })
+        })
+        .detach_and_prompt_err(
+            &t!("jj_ui.load_commit_failed", "Failed to load commit"),
+            window,
+            cx,
+            |_, _, _| None,
+        );
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        change_id: String,
+        paths: Vec<Arc<Path>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = SplitPickerDelegate {
+            picker: cx.entity().downgrade(),
+            repository,
+            change_id,
+            paths,
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl ModalView for SplitPicker {}
+
+impl EventEmitter<DismissEvent> for SplitPicker {}
+
+impl Focusable for SplitPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for SplitPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PathMatch {
+    path: Arc<Path>,
+    positions: Vec<usize>,
+}
+
+pub struct SplitPickerDelegate {
+    picker: WeakEntity<SplitPicker>,
+    repository: Entity<Repository>,
+    /// The change ID of the commit, which keeps pointing at it after jj
+    /// snapshots the working copy and rewrites `@`.
+    change_id: String,
+    paths: Vec<Arc<Path>>,
+    matches: Vec<PathMatch>,
+    selected_index: usize,
+}
+
+impl PickerDelegate for SplitPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        t!(
+            "jj_ui.split_picker.placeholder",
+            "Select a file to split out…"
+        )
+        .to_string()
+        .into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let paths = self.paths.clone();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                paths
+                    .into_iter()
+                    .map(|path| PathMatch {
+                        path,
+                        positions: Vec::new(),
+                    })
+                    .collect()
+            } else {
+                let candidates = paths
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, path)| StringMatchCandidate::new(ix, &path.to_string_lossy()))
+                    .collect::<Vec<_>>();
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+                .into_iter()
+                .map(|mat| PathMatch {
+                    path: paths[mat.candidate_id].clone(),
+                    positions: mat.positions,
+                })
+                .collect()
+            };
+
+            this.update(cx, |this, _cx| {
+                this.delegate.matches = matches;
+                this.delegate.selected_index = 0;
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(mat) = self.matches.get(self.selected_index) else {
+            return;
+        };
+        let mutation = Mutation::Split {
+            revision: self.change_id.clone(),
+            paths: vec![mat.path.clone()],
+        };
+        mutations::run(&self.repository, mutation, window, cx);
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(HighlightedLabel::new(
+                    mat.path.to_string_lossy().to_string(),
+                    mat.positions.clone(),
+                )),
+        )
+    }
+}
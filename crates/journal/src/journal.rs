@@ -186,11 +186,10 @@ fn heading_entry(now: NaiveTime, hour_format: &HourFormat) -> String {
             let hour = now.hour();
             format!("# {}:{:02}", hour, now.minute())
         }
-        HourFormat::Hour12 => {
-            let (pm, hour) = now.hour12();
-            let am_or_pm = if pm { "PM" } else { "AM" };
-            format!("# {}:{:02} {}", hour, now.minute(), am_or_pm)
-        }
+        HourFormat::Hour12 => format!(
+            "# {}",
+            time_format::format_time_of_day(now.hour() as u8, now.minute() as u8, true)
+        ),
     }
 }
 
@@ -202,6 +201,7 @@ mod tests {
         #[test]
         fn test_heading_entry_defaults_to_hour_12() {
             let naive_time = NaiveTime::from_hms_milli_opt(15, 0, 0, 0).unwrap();
+            let _language = localization::set_language_for_test(localization::Language::ENGLISH);
             let actual_heading_entry = heading_entry(naive_time, &HourFormat::Hour12);
             let expected_heading_entry = "# 3:00 PM";
 
@@ -211,6 +211,7 @@ mod tests {
         #[test]
         fn test_heading_entry_is_hour_12() {
             let naive_time = NaiveTime::from_hms_milli_opt(15, 0, 0, 0).unwrap();
+            let _language = localization::set_language_for_test(localization::Language::ENGLISH);
             let actual_heading_entry = heading_entry(naive_time, &HourFormat::Hour12);
             let expected_heading_entry = "# 3:00 PM";
 
@@ -97,6 +97,11 @@ pub struct Buffer {
     /// The mtime of the file when this buffer was last loaded from
     /// or saved to disk.
     saved_mtime: Option<MTime>,
+    /// The encoding used to load the file and to save the buffer back to it.
+    encoding: Encoding,
+    /// Whether the encoding was guessed when loading the file, rather than detected
+    /// reliably or chosen by the user.
+    encoding_guessed: bool,
     /// The version vector when this buffer was last loaded from
     /// or saved to disk.
     saved_version: clock::Global,
@@ -300,6 +305,14 @@ pub enum Operation {
         /// The buffer's lamport timestamp.
         lamport_timestamp: clock::Lamport,
     },
+
+    /// An update to the encoding of this buffer's file.
+    UpdateEncoding {
+        /// The encoding.
+        encoding: Encoding,
+        /// The buffer's lamport timestamp.
+        lamport_timestamp: clock::Lamport,
+    },
 }
 
 /// An event that occurs in a buffer.
@@ -877,6 +890,8 @@ impl Buffer {
         this.text.set_line_ending(proto::deserialize_line_ending(
             rpc::proto::LineEnding::from_i32(message.line_ending).context("missing line_ending")?,
         ));
+        this.encoding = proto::deserialize_encoding(message.encoding);
+        this.encoding_guessed = message.encoding_guessed;
         this.saved_version = proto::deserialize_version(&message.saved_version);
         this.saved_mtime = message.saved_mtime.map(|time| time.into());
         Ok(this)
@@ -891,6 +906,8 @@ impl Buffer {
             line_ending: proto::serialize_line_ending(self.line_ending()) as i32,
             saved_version: proto::serialize_version(&self.saved_version),
             saved_mtime: self.saved_mtime.map(|time| time.into()),
+            encoding: Some(proto::serialize_encoding(self.encoding)),
+            encoding_guessed: self.encoding_guessed,
         }
     }
 
@@ -950,6 +967,14 @@ impl Buffer {
         self
     }
 
+    /// Assign the encoding detected when loading its file to the buffer, returning the
+    /// buffer. Encodings other than UTF-8 and UTF-16 are only guessed by detection.
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self.encoding_guessed = !encoding.is_unicode();
+        self
+    }
+
     /// Returns the [`Capability`] of this buffer.
     pub fn capability(&self) -> Capability {
         self.capability
@@ -967,6 +992,8 @@ impl Buffer {
         let syntax_map = Mutex::new(SyntaxMap::new(&snapshot));
         Self {
             saved_mtime,
+            encoding: Encoding::default(),
+            encoding_guessed: false,
             saved_version: buffer.version(),
             preview_version: buffer.version(),
             reload_task: None,
@@ -1097,6 +1124,8 @@ impl Buffer {
                     merged_operations: Default::default(),
                 }),
                 language: self.language.clone(),
+                encoding: self.encoding,
+                encoding_guessed: self.encoding_guessed,
                 has_conflict: self.has_conflict,
                 has_unsaved_edits: Cell::new(self.has_unsaved_edits.get_mut().clone()),
                 _subscriptions: vec![cx.subscribe(&this, Self::on_base_buffer_event)],
@@ -1295,6 +1324,34 @@ impl Buffer {
         );
     }
 
+    /// The encoding of the buffer's file.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Whether the encoding of the buffer's file was guessed from its contents, and so
+    /// may be wrong.
+    pub fn encoding_guessed(&self) -> bool {
+        self.encoding_guessed
+    }
+
+    /// Assign the encoding that the buffer is saved and reloaded with.
+    pub fn set_encoding(&mut self, encoding: Encoding, cx: &mut Context<Self>) {
+        self.encoding = encoding;
+        self.encoding_guessed = false;
+
+        let lamport_timestamp = self.text.lamport_clock.tick();
+        self.send_operation(
+            Operation::UpdateEncoding {
+                encoding,
+                lamport_timestamp,
+            },
+            true,
+            cx,
+        );
+        cx.notify();
+    }
+
     /// Assign the buffer a new [`Capability`].
     pub fn set_capability(&mut self, capability: Capability, cx: &mut Context<Self>) {
         if self.capability != capability {
@@ -1325,16 +1382,23 @@ impl Buffer {
         let (tx, rx) = futures::channel::oneshot::channel();
         let prev_version = self.text.version();
         self.reload_task = Some(cx.spawn(async move |this, cx| {
-            let Some((new_mtime, new_text)) = this.update(cx, |this, cx| {
+            let Some((new_mtime, new_bytes, encoding)) = this.update(cx, |this, cx| {
                 let file = this.file.as_ref()?.as_local()?;
 
-                Some((file.disk_state().mtime(), file.load(cx)))
+                Some((
+                    file.disk_state().mtime(),
+                    file.load_bytes(cx),
+                    this.encoding,
+                ))
             })?
             else {
                 return Ok(());
             };
 
-            let new_text = new_text.await?;
+            let new_bytes = new_bytes.await?;
+            let new_text = cx
+                .background_spawn(async move { encoding.decode(&new_bytes) })
+                .await;
             let diff = this
                 .update(cx, |this, cx| this.diff(new_text.clone(), cx))?
                 .await;
@@ -2612,7 +2676,9 @@ impl Buffer {
             Operation::UpdateSelections { selections, .. } => selections
                 .iter()
                 .all(|s| self.can_resolve(&s.start) && self.can_resolve(&s.end)),
-            Operation::UpdateCompletionTriggers { .. } | Operation::UpdateLineEnding { .. } => true,
+            Operation::UpdateCompletionTriggers { .. }
+            | Operation::UpdateLineEnding { .. }
+            | Operation::UpdateEncoding { .. } => true,
         }
     }
 
@@ -2685,6 +2751,15 @@ impl Buffer {
                 self.text.set_line_ending(line_ending);
                 self.text.lamport_clock.observe(lamport_timestamp);
             }
+            Operation::UpdateEncoding {
+                encoding,
+                lamport_timestamp,
+            } => {
+                self.encoding = encoding;
+                self.encoding_guessed = false;
+                self.text.lamport_clock.observe(lamport_timestamp);
+                cx.notify();
+            }
         }
     }
 
@@ -5031,6 +5106,9 @@ impl operation_queue::Operation for Operation {
             }
             | Operation::UpdateLineEnding {
                 lamport_timestamp, ..
+            }
+            | Operation::UpdateEncoding {
+                lamport_timestamp, ..
             } => *lamport_timestamp,
         }
     }
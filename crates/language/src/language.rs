@@ -18,6 +18,7 @@ mod syntax_map;
 mod task_context;
 mod text_diff;
 mod toolchain;
+mod word_segmentation;
 
 #[cfg(test)]
 pub mod buffer_tests;
@@ -84,8 +85,9 @@ pub use outline::*;
 pub use syntax_map::{
     OwnedSyntaxLayer, SyntaxLayer, SyntaxMapMatches, ToTreeSitterPoint, TreeSitterOptions,
 };
-pub use text::{AnchorRangeExt, LineEnding};
+pub use text::{AnchorRangeExt, Encoding, LineEnding};
 pub use tree_sitter::{Node, Parser, Tree, TreeCursor};
+pub use word_segmentation::{is_cjk, word_boundaries};
 
 /// Initializes the `language` crate.
 ///
@@ -29,6 +29,22 @@ pub fn serialize_line_ending(message: text::LineEnding) -> proto::LineEnding {
     }
 }
 
+/// Deserializes a [`text::Encoding`] from the RPC representation. Encodings that are
+/// missing or unknown to this version fall back to UTF-8.
+pub fn deserialize_encoding(message: Option<proto::Encoding>) -> text::Encoding {
+    message
+        .and_then(|message| text::Encoding::for_name(&message.name, message.bom))
+        .unwrap_or_default()
+}
+
+/// Serializes a [`text::Encoding`] to be sent over RPC.
+pub fn serialize_encoding(encoding: text::Encoding) -> proto::Encoding {
+    proto::Encoding {
+        name: encoding.name().to_string(),
+        bom: encoding.has_bom(),
+    }
+}
+
 /// Serializes a [`crate::Operation`] to be sent over RPC.
 pub fn serialize_operation(operation: &crate::Operation) -> proto::Operation {
     proto::Operation {
@@ -99,6 +115,15 @@ pub fn serialize_operation(operation: &crate::Operation) -> proto::Operation {
                 lamport_timestamp: lamport_timestamp.value,
                 line_ending: serialize_line_ending(*line_ending) as i32,
             }),
+
+            crate::Operation::UpdateEncoding {
+                encoding,
+                lamport_timestamp,
+            } => proto::operation::Variant::UpdateEncoding(proto::operation::UpdateEncoding {
+                replica_id: lamport_timestamp.replica_id as u32,
+                lamport_timestamp: lamport_timestamp.value,
+                encoding: Some(serialize_encoding(*encoding)),
+            }),
         }),
     }
 }
@@ -362,6 +387,15 @@ pub fn deserialize_operation(message: proto::Operation) -> Result<crate::Operati
                     ),
                 }
             }
+            proto::operation::Variant::UpdateEncoding(message) => {
+                crate::Operation::UpdateEncoding {
+                    lamport_timestamp: clock::Lamport {
+                        replica_id: message.replica_id as ReplicaId,
+                        value: message.lamport_timestamp,
+                    },
+                    encoding: deserialize_encoding(message.encoding),
+                }
+            }
         },
     )
 }
@@ -521,6 +555,10 @@ pub fn lamport_timestamp_for_operation(operation: &proto::Operation) -> Option<c
             replica_id = op.replica_id;
             value = op.lamport_timestamp;
         }
+        proto::operation::Variant::UpdateEncoding(op) => {
+            replica_id = op.replica_id;
+            value = op.lamport_timestamp;
+        }
     }
 
     Some(clock::Lamport {
@@ -0,0 +1,173 @@
+//! Splitting CJK text into words.
+//!
+//! Chinese and Japanese are written without spaces between words, so a sentence is a
+//! single run of word characters. Like Vim, we put a word boundary wherever the script
+//! changes between Han, Hiragana, Katakana, Hangul and everything else. Runs of Han
+//! characters are further split using the dictionary in `cjk_words.txt`, preferring
+//! the split with the fewest words and then the fewest characters left on their own.
+
+use std::{collections::HashSet, ops::Range, sync::LazyLock};
+
+static DICTIONARY: LazyLock<Dictionary> = LazyLock::new(|| {
+    let words = include_str!("cjk_words.txt")
+        .split_whitespace()
+        .collect::<HashSet<_>>();
+    let max_word_len = words
+        .iter()
+        .map(|word| word.chars().count())
+        .max()
+        .unwrap_or(1);
+    Dictionary {
+        words,
+        max_word_len,
+    }
+});
+
+struct Dictionary {
+    words: HashSet<&'static str>,
+    /// The length of the longest word, in characters.
+    max_word_len: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Script {
+    Han,
+    Hiragana,
+    Katakana,
+    Hangul,
+    Other,
+}
+
+impl Script {
+    fn of(ch: char) -> Self {
+        match ch {
+            '\u{3005}'
+            | '\u{3007}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{3134F}' => Self::Han,
+            '\u{3041}'..='\u{309F}' => Self::Hiragana,
+            '\u{30A0}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => {
+                Self::Katakana
+            }
+            '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' | '\u{AC00}'..='\u{D7AF}' => {
+                Self::Hangul
+            }
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Returns whether `ch` is a Han, Kana or Hangul character.
+pub fn is_cjk(ch: char) -> bool {
+    Script::of(ch) != Script::Other
+}
+
+/// Returns the byte offsets of the boundaries between the words of `text`, a run of
+/// word characters, excluding its start and end.
+///
+/// Text without CJK characters has no boundaries.
+pub fn word_boundaries(text: &str) -> Vec<usize> {
+    let mut boundaries = Vec::new();
+    let mut run_start = 0;
+    let mut run_script = None;
+    for (ix, ch) in text.char_indices() {
+        let mut script = Script::of(ch);
+        // The prolonged sound mark is also written after Hiragana.
+        if ch == 'ー' && run_script == Some(Script::Hiragana) {
+            script = Script::Hiragana;
+        }
+
+        if let Some(run_script) = run_script
+            && run_script != script
+        {
+            if run_script == Script::Han {
+                split_han(text, run_start..ix, &mut boundaries);
+            }
+            boundaries.push(ix);
+            run_start = ix;
+        }
+        run_script = Some(script);
+    }
+    if run_script == Some(Script::Han) {
+        split_han(text, run_start..text.len(), &mut boundaries);
+    }
+    boundaries
+}
+
+fn split_han(text: &str, range: Range<usize>, boundaries: &mut Vec<usize>) {
+    let offsets = text[range.clone()]
+        .char_indices()
+        .map(|(ix, _)| range.start + ix)
+        .chain([range.end])
+        .collect::<Vec<_>>();
+    let char_count = offsets.len() - 1;
+    let dictionary = &*DICTIONARY;
+
+    // For each prefix of the run, the cost of its best split as the number of words and
+    // of characters on their own, and where the last word of that split starts.
+    let mut best = vec![((usize::MAX, usize::MAX), 0); char_count + 1];
+    best[0].0 = (0, 0);
+    for end in 1..=char_count {
+        for start in end.saturating_sub(dictionary.max_word_len)..end {
+            let is_single = end - start == 1;
+            if !is_single
+                && !dictionary
+                    .words
+                    .contains(&text[offsets[start]..offsets[end]])
+            {
+                continue;
+            }
+            let (words, singles) = best[start].0;
+            let cost = (words + 1, singles + usize::from(is_single));
+            if cost < best[end].0 {
+                best[end] = (cost, start);
+            }
+        }
+    }
+
+    let first_boundary = boundaries.len();
+    let mut end = char_count;
+    while end > 0 {
+        let start = best[end].1;
+        if start > 0 {
+            boundaries.push(offsets[start]);
+        }
+        end = start;
+    }
+    boundaries[first_boundary..].reverse();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<&str> {
+        let mut start = 0;
+        let mut words = Vec::new();
+        for boundary in word_boundaries(text).into_iter().chain([text.len()]) {
+            words.push(&text[start..boundary]);
+            start = boundary;
+        }
+        words
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        assert_eq!(word_boundaries("hello_world42"), Vec::<usize>::new());
+        assert_eq!(words("我们使用中文"), ["我们", "使用", "中文"]);
+        assert_eq!(words("读取配置文件"), ["读取", "配置", "文件"]);
+        assert_eq!(words("获取用户信息"), ["获取", "用户", "信息"]);
+        assert_eq!(words("讀取設定檔案"), ["讀取", "設定", "檔案"]);
+        assert_eq!(words("调用get函数"), ["调用", "get", "函数"]);
+        assert_eq!(words("鑫淼"), ["鑫", "淼"]);
+        assert_eq!(
+            words("設定ファイルを読み込む"),
+            ["設定", "ファイル", "を", "読", "み", "込", "む"]
+        );
+        assert_eq!(words("すごーい"), ["すごーい"]);
+        assert_eq!(words("한국어입니다"), ["한국어입니다"]);
+        assert_eq!(words("UTF8编码"), ["UTF8", "编码"]);
+    }
+}
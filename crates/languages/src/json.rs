@@ -167,14 +167,21 @@ impl JsonLspAdapter {
                     .collect::<Vec<SharedString>>()
             })
             .unwrap_or_default();
-        let settings_schema = cx
-            .global::<SettingsStore>()
-            .json_schema(&SettingsJsonSchemaParams {
-                language_names: &language_names,
-                font_names,
-                theme_names,
-                icon_theme_names,
-            });
+        let ui_language_names = &localization::available_languages()
+            .into_iter()
+            .chain([localization::Language::PSEUDO])
+            .map(|language| language.code().to_string())
+            .collect::<Vec<_>>();
+        let mut settings_schema =
+            cx.global::<SettingsStore>()
+                .json_schema(&SettingsJsonSchemaParams {
+                    language_names: &language_names,
+                    font_names,
+                    theme_names,
+                    icon_theme_names,
+                    ui_language_names,
+                });
+        localization::localize_settings_schema(&mut settings_schema);
 
         let tasks_schema = task::TaskTemplates::generate_json_schema();
         let debug_schema = task::DebugTaskFile::generate_json_schema(&adapter_schemas);
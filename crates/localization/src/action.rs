@@ -0,0 +1,33 @@
+//! Translations of action names and documentation, shown in the command palette.
+//!
+//! Actions are translated under `action.<name>` keys, e.g. `action.editor::Backspace`,
+//! and their documentation under `action.<name>.documentation`. The English text is
+//! derived from the action itself rather than written at a call site, so actions
+//! without a translation are not reported as missing.
+
+use crate::{Language, current_language, lookup, pseudolocalize};
+use gpui::SharedString;
+
+/// Returns the translated display name of the action called `action_name`, or `None`
+/// when it should be shown under its English `humanized_name`.
+pub fn action_display_name(action_name: &str, humanized_name: &str) -> Option<SharedString> {
+    translate_action(&format!("action.{action_name}"), humanized_name)
+}
+
+/// Returns the translated documentation of the action called `action_name`, or `None`
+/// when only the English `documentation` is available.
+pub fn action_documentation(action_name: &str, documentation: &str) -> Option<SharedString> {
+    translate_action(
+        &format!("action.{action_name}.documentation"),
+        documentation,
+    )
+}
+
+fn translate_action(key: &str, english: &str) -> Option<SharedString> {
+    let language = current_language();
+    if language == Language::PSEUDO {
+        Some(pseudolocalize(english).into())
+    } else {
+        lookup(&language, key)
+    }
+}
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use collections::HashMap;
+use gpui::SharedString;
+
+/// A flat mapping from translation keys to translated text for a single language.
+///
+/// Catalogs are stored on disk as JSON objects (comments and trailing commas are
+/// allowed), e.g. `assets/locales/zh-CN/ui.json`:
+///
+/// ```json
+/// {
+///   "menu.file.save": "保存"
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Catalog {
+    entries: HashMap<SharedString, SharedString>,
+}
+
+impl Catalog {
+    pub fn parse(content: &str) -> Result<Self> {
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        let entries: HashMap<String, String> = settings::parse_json_with_comments(content)?;
+        Ok(Self {
+            entries: entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        })
+    }
+
+    /// Adds every entry of `other` to this catalog, replacing existing values
+    /// for keys present in both.
+    pub fn merge(&mut self, other: Catalog) {
+        self.entries.extend(other.entries);
+    }
+
+    pub fn get(&self, key: &str) -> Option<&SharedString> {
+        self.entries.get(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &SharedString> {
+        self.entries.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,49 @@
+use collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// The default English text of a translation key, registered by each use of [`t!`](crate::t).
+pub struct DefaultText {
+    pub key: &'static str,
+    pub default: &'static str,
+    pub file: &'static str,
+    pub line: u32,
+}
+
+inventory::collect!(DefaultText);
+
+/// Builds the English catalog from the default text of every [`t!`](crate::t) call
+/// linked into the binary, for translators to work from.
+///
+/// Fails if a key is used with different default text, like `cargo xtask translations`
+/// does when checking the source.
+pub fn default_catalog() -> anyhow::Result<BTreeMap<&'static str, &'static str>> {
+    let mut uses = BTreeMap::<&str, Vec<&DefaultText>>::default();
+    for text in inventory::iter::<DefaultText>() {
+        uses.entry(text.key).or_default().push(text);
+    }
+
+    let mut catalog = BTreeMap::default();
+    let mut conflicts = String::new();
+    for (key, mut uses) in uses {
+        uses.sort_by_key(|text| (text.file, text.line));
+        if uses.iter().any(|text| text.default != uses[0].default) {
+            writeln!(conflicts, "{key:?} has different default text:").ok();
+            for text in &uses {
+                writeln!(
+                    conflicts,
+                    "  {}:{}: {:?}",
+                    text.file, text.line, text.default
+                )
+                .ok();
+            }
+            continue;
+        }
+        catalog.insert(key, uses[0].default);
+    }
+
+    if conflicts.is_empty() {
+        Ok(catalog)
+    } else {
+        anyhow::bail!("conflicting default translations\n{conflicts}")
+    }
+}
@@ -0,0 +1,112 @@
+use gpui::SharedString;
+
+/// An interface language, identified by a BCP-47 tag such as `zh-CN` or `ja`.
+///
+/// Languages are data-driven: any tag with a catalog directory under `locales/` is
+/// available, in addition to English, which is built into the call sites.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Language(SharedString);
+
+impl Language {
+    pub const ENGLISH: Language = Language(SharedString::new_static("en"));
+    pub const SIMPLIFIED_CHINESE: Language = Language(SharedString::new_static("zh-CN"));
+    pub const TRADITIONAL_CHINESE: Language = Language(SharedString::new_static("zh-Hant"));
+    pub const JAPANESE: Language = Language(SharedString::new_static("ja"));
+    pub const KOREAN: Language = Language(SharedString::new_static("ko"));
+    /// English rendered with accented, bracketed and padded text, which makes strings that
+    /// bypass translation stand out. See [`pseudolocalize`](crate::pseudolocalize).
+    pub const PSEUDO: Language = Language(SharedString::new_static("en-XA"));
+
+    /// The canonical BCP-47 tag, which is also the name of the language's catalog directory.
+    pub fn code(&self) -> &str {
+        &self.0
+    }
+
+    /// The primary language subtag, e.g. `zh` for `zh-Hant`.
+    pub fn primary_subtag(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+
+    /// Parses a BCP-47 tag or POSIX locale name, normalizing its case and separators.
+    ///
+    /// Tags that refer to the same catalog are canonicalized, so `zh`, `zh-Hans` and
+    /// `zh_CN` all resolve to [`Language::SIMPLIFIED_CHINESE`]. Returns `None` if `code`
+    /// is not a well-formed tag.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.trim();
+        let code = &code[..code.find(['.', '@']).unwrap_or(code.len())];
+        if code.is_empty() {
+            return None;
+        }
+
+        let mut canonical = String::with_capacity(code.len());
+        for (index, subtag) in code.split(['-', '_']).enumerate() {
+            if subtag.is_empty()
+                || subtag.len() > 8
+                || !subtag.chars().all(|ch| ch.is_ascii_alphanumeric())
+            {
+                return None;
+            }
+            if index > 0 {
+                canonical.push('-');
+            }
+            match (index, subtag.len()) {
+                (0, _) => canonical.push_str(&subtag.to_ascii_lowercase()),
+                (_, 2) => canonical.push_str(&subtag.to_ascii_uppercase()),
+                (_, 4) => {
+                    canonical.push_str(&subtag[..1].to_ascii_uppercase());
+                    canonical.push_str(&subtag[1..].to_ascii_lowercase());
+                }
+                _ => canonical.push_str(&subtag.to_ascii_lowercase()),
+            }
+        }
+
+        Some(match canonical.as_str() {
+            "en" => Self::ENGLISH,
+            "zh" | "zh-CN" | "zh-Hans" | "zh-Hans-CN" => Self::SIMPLIFIED_CHINESE,
+            "zh-Hant" => Self::TRADITIONAL_CHINESE,
+            "ja" => Self::JAPANESE,
+            "ko" => Self::KOREAN,
+            "en-XA" | "pseudo" => Self::PSEUDO,
+            _ => Self(canonical.into()),
+        })
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonicalizes_tags() {
+        assert_eq!(
+            Language::from_code("zh_CN.UTF-8"),
+            Some(Language::SIMPLIFIED_CHINESE)
+        );
+        assert_eq!(
+            Language::from_code("ZH-hans"),
+            Some(Language::SIMPLIFIED_CHINESE)
+        );
+        assert_eq!(
+            Language::from_code("zh-hant"),
+            Some(Language::TRADITIONAL_CHINESE)
+        );
+        assert_eq!(Language::from_code("JA"), Some(Language::JAPANESE));
+        assert_eq!(Language::from_code("pseudo"), Some(Language::PSEUDO));
+        assert_eq!(Language::from_code("en_xa"), Some(Language::PSEUDO));
+        assert_eq!(Language::from_code("pt_br").unwrap().code(), "pt-BR");
+        assert_eq!(
+            Language::from_code("sr-latn-rs").unwrap().code(),
+            "sr-Latn-RS"
+        );
+        assert_eq!(Language::from_code(""), None);
+        assert_eq!(Language::from_code("en--US"), None);
+        assert_eq!(Language::from_code("../etc"), None);
+    }
+}
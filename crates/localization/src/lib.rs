@@ -1,219 +1,280 @@
-use gpui::SharedString;
+mod action;
+mod catalog;
+mod default_text;
+mod language;
+mod locale;
+mod message;
+mod missing;
+mod number;
+mod pseudo;
+mod settings_schema;
+
+pub use action::{action_display_name, action_documentation};
+pub use catalog::Catalog;
+pub use default_text::{DefaultText, default_catalog};
+pub use language::Language;
+pub use locale::{fallback_chain, negotiate, requested_locales_from_env, system_locales};
+pub use message::{Message, MessageArg, PluralCategory, plural_category};
+pub use missing::{
+    CallSite, MissingTranslation, missing_translations, missing_translations_report,
+};
+pub use number::{format_bytes, format_number};
+pub use pseudo::pseudolocalize;
+pub use settings_schema::{localize_settings_schema, setting_title};
+pub use ui_macros::t;
+
+use anyhow::Context as _;
+use collections::{BTreeMap, HashMap};
+use fs::Fs;
+use futures::StreamExt as _;
+use gpui::{App, Global, SharedString};
 use parking_lot::RwLock;
-use settings::{Settings, SettingsContent, SettingsStore};
+use rust_embed::RustEmbed;
+use settings::{Settings, SettingsContent, SettingsStore, UiLanguagePreference};
 use std::borrow::Cow;
-use std::collections::HashMap;
-use std::sync::OnceLock;
-
-type TranslationMap = HashMap<&'static str, &'static str>;
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum Language {
-    English,
-    SimplifiedChinese,
-}
-
-static LANGUAGE: RwLock<Language> = RwLock::new(Language::SimplifiedChinese);
-static ZH_CN_TRANSLATIONS: OnceLock<TranslationMap> = OnceLock::new();
-
-const ZH_CN_ENTRIES: &[(&str, &str)] = &[
-    ("settings.group.font", "字体"),
-    ("settings.group.editor", "编辑器"),
-    ("settings.group.gutter", "行号栏"),
-    ("setting.buffer_font_family", "编辑器字体"),
-    ("setting.buffer_font_size", "编辑器字号"),
-    ("setting.buffer_font_weight", "编辑器字体粗细"),
-    ("setting.buffer_font_ligatures", "字体连字"),
-    ("setting.inline_git_blame", "内联 Git Blame"),
-    ("setting.line_numbers", "行号"),
-    ("setting.relative_line_numbers", "相对行号"),
-    ("option.relative_line_numbers.ascending", "递增"),
-    ("option.relative_line_numbers.relative", "相对"),
-    ("command.jump_to_file", "跳转到文件"),
-    ("command.apply_all", "全部应用"),
-    ("command.hold", "按住"),
-    ("command.preview", "预览"),
-    ("command.jump_to_edit", "跳转到编辑"),
-    (
-        "warning.conflict_with_accept_keybinding",
-        "与接受快捷键冲突",
-    ),
-    (
-        "message.conflict_accept_keybinding",
-        "当前按键映射已覆盖默认的接受预测快捷键。要继续，请为 `editor::AcceptEditPrediction` 操作分配一个快捷键。",
-    ),
-    ("button.assign_keybinding", "分配快捷键"),
-    ("button.see_docs", "查看文档"),
-    ("menu.settings", "设置"),
-    ("menu.settings.open_settings", "打开设置"),
-    ("menu.settings.open_key_bindings", "打开键绑定"),
-    ("menu.settings.open_default_settings", "打开默认设置"),
-    ("menu.settings.open_default_key_bindings", "打开默认键绑定"),
-    ("menu.settings.open_project_settings", "打开项目设置"),
-    ("menu.settings.select_profile", "选择设置配置"),
-    ("menu.settings.select_theme", "选择主题"),
-    ("menu.interface_language", "界面语言"),
-    ("menu.interface_language.follow_system", "跟随系统"),
-    ("menu.interface_language.simplified_chinese", "简体中文"),
-    ("menu.interface_language.english", "英语"),
-    ("menu.zed", "Zed"),
-    ("menu.zed.about", "关于 Zed"),
-    ("menu.zed.check_updates", "检查更新"),
-    ("menu.zed.services", "服务"),
-    ("menu.zed.extensions", "扩展"),
-    ("menu.zed.install_cli", "安装 CLI"),
-    ("menu.zed.hide", "隐藏 Zed"),
-    ("menu.zed.hide_others", "隐藏其他"),
-    ("menu.zed.show_all", "显示全部"),
-    ("menu.zed.quit", "退出 Zed"),
-    ("menu.file", "文件"),
-    ("menu.file.new", "新建"),
-    ("menu.file.new_window", "新建窗口"),
-    ("menu.file.open_file", "打开文件..."),
-    ("menu.file.open_folder", "打开文件夹..."),
-    ("menu.file.open", "打开…"),
-    ("menu.file.open_recent", "打开最近..."),
-    ("menu.file.open_remote", "打开远程..."),
-    ("menu.file.add_folder_to_project", "添加文件夹到项目…"),
-    ("menu.file.save", "保存"),
-    ("menu.file.save_as", "另存为…"),
-    ("menu.file.save_all", "全部保存"),
-    ("menu.file.close_editor", "关闭编辑器"),
-    ("menu.file.close_window", "关闭窗口"),
-    ("menu.edit", "编辑"),
-    ("menu.edit.undo", "撤销"),
-    ("menu.edit.redo", "重做"),
-    ("menu.edit.cut", "剪切"),
-    ("menu.edit.copy", "复制"),
-    ("menu.edit.copy_trim", "复制并修剪"),
-    ("menu.edit.paste", "粘贴"),
-    ("menu.edit.find", "查找"),
-    ("menu.edit.find_in_project", "在项目中查找"),
-    ("menu.edit.toggle_line_comment", "切换行注释"),
-    ("menu.selection", "选择"),
-    ("menu.selection.select_all", "全选"),
-    ("menu.selection.expand", "扩展选区"),
-    ("menu.selection.shrink", "收缩选区"),
-    ("menu.selection.select_next_sibling", "选择下一个同级"),
-    ("menu.selection.select_previous_sibling", "选择上一个同级"),
-    ("menu.selection.add_cursor_above", "在上方添加光标"),
-    ("menu.selection.add_cursor_below", "在下方添加光标"),
-    ("menu.selection.select_next_occurrence", "选择下一个匹配"),
-    ("menu.selection.move_line_up", "上移一行"),
-    ("menu.selection.move_line_down", "下移一行"),
-    ("menu.selection.duplicate_selection", "复制选区"),
-    ("menu.view", "视图"),
-    ("menu.view.zoom_in", "放大"),
-    ("menu.view.zoom_out", "缩小"),
-    ("menu.view.reset_zoom", "重置缩放"),
-    ("menu.view.toggle_left_dock", "切换左侧停靠栏"),
-    ("menu.view.toggle_right_dock", "切换右侧停靠栏"),
-    ("menu.view.toggle_bottom_dock", "切换底部停靠栏"),
-    ("menu.view.close_all_docks", "关闭所有停靠栏"),
-    ("menu.view.editor_layout", "编辑器布局"),
-    ("menu.view.split_up", "向上分屏"),
-    ("menu.view.split_down", "向下分屏"),
-    ("menu.view.split_left", "向左分屏"),
-    ("menu.view.split_right", "向右分屏"),
-    ("menu.view.project_panel", "项目面板"),
-    ("menu.view.outline_panel", "大纲面板"),
-    ("menu.view.collab_panel", "协作面板"),
-    ("menu.view.terminal_panel", "终端面板"),
-    ("menu.view.debugger_panel", "调试面板"),
-    ("menu.view.diagnostics", "诊断"),
-    ("menu.go", "前往"),
-    ("menu.go.back", "后退"),
-    ("menu.go.forward", "前进"),
-    ("menu.go.command_palette", "命令面板..."),
-    ("menu.go.go_to_file", "跳转到文件..."),
-    ("menu.go.go_to_symbol_in_editor", "跳转到编辑器符号..."),
-    ("menu.go.go_to_line_column", "跳转到行/列..."),
-    ("menu.go.go_to_definition", "跳转到定义"),
-    ("menu.go.go_to_declaration", "跳转到声明"),
-    ("menu.go.go_to_type_definition", "跳转到类型定义"),
-    ("menu.go.find_all_references", "查找所有引用"),
-    ("menu.go.next_problem", "下一个问题"),
-    ("menu.go.previous_problem", "上一个问题"),
-    ("menu.run", "运行"),
-    ("menu.run.spawn_task", "启动任务"),
-    ("menu.run.start_debugger", "启动调试器"),
-    ("menu.run.edit_tasks", "编辑 tasks.json..."),
-    ("menu.run.edit_debug", "编辑 debug.json..."),
-    ("menu.run.continue", "继续"),
-    ("menu.run.step_over", "单步跳过"),
-    ("menu.run.step_into", "单步进入"),
-    ("menu.run.step_out", "单步跳出"),
-    ("menu.run.toggle_breakpoint", "切换断点"),
-    ("menu.run.edit_breakpoint", "编辑断点"),
-    ("menu.run.clear_all_breakpoints", "清除所有断点"),
-    ("menu.window", "窗口"),
-    ("menu.window.minimize", "最小化"),
-    ("menu.window.zoom", "缩放"),
-    ("menu.help", "帮助"),
-    ("menu.help.view_release_notes", "查看发行说明"),
-    ("menu.help.view_telemetry", "查看遥测数据"),
-    ("menu.help.view_dependency_licenses", "查看依赖许可"),
-    ("menu.help.show_welcome", "显示欢迎页"),
-    ("menu.help.give_feedback", "提交反馈..."),
-    ("menu.help.documentation", "文档"),
-    ("menu.help.zed_twitter", "Zed 推特"),
-    ("menu.help.join_the_team", "加入团队"),
-    ("prompt.restart_required.title", "需要重启"),
-    (
-        "prompt.restart_required.message",
-        "请重启 Zed 以完全应用语言更改。",
-    ),
-    ("prompt.restart_required.restart_now", "立即重启"),
-    ("prompt.restart_required.later", "稍后"),
-];
-
-fn zh_cn_translations() -> &'static TranslationMap {
-    ZH_CN_TRANSLATIONS.get_or_init(|| {
-        let mut map = HashMap::with_capacity(ZH_CN_ENTRIES.len());
-        for (key, value) in ZH_CN_ENTRIES {
-            map.insert(*key, *value);
-        }
-        map
+use std::panic::Location;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use util::ResultExt as _;
+
+#[derive(RustEmbed)]
+#[folder = "../../assets"]
+#[include = "locales/**/*"]
+#[exclude = "*.DS_Store"]
+struct LocaleAssets;
+
+/// Translations bundled with Zed, overlaid by catalogs from extensions and then by
+/// catalogs from the user's locales directory.
+#[derive(Default)]
+struct Catalogs {
+    bundled: HashMap<Language, Catalog>,
+    extensions: BTreeMap<Arc<str>, HashMap<Language, Catalog>>,
+    user: HashMap<Language, Catalog>,
+}
+
+impl Catalogs {
+    fn layers(&self) -> impl Iterator<Item = &HashMap<Language, Catalog>> {
+        [&self.user]
+            .into_iter()
+            .chain(self.extensions.values())
+            .chain([&self.bundled])
+    }
+
+    fn lookup(&self, language: &Language, key: &str) -> Option<SharedString> {
+        self.layers()
+            .find_map(|catalogs| catalogs.get(language)?.get(key))
+            .cloned()
+    }
+
+    fn has_translations(&self, language: &Language) -> bool {
+        self.layers().any(|catalogs| {
+            catalogs
+                .get(language)
+                .is_some_and(|catalog| !catalog.is_empty())
+        })
+    }
+}
+
+/// Private internals for macros.
+#[doc(hidden)]
+pub mod __private {
+    pub use inventory;
+}
+
+static LANGUAGE: RwLock<Language> = RwLock::new(Language::SIMPLIFIED_CHINESE);
+static CATALOGS: LazyLock<RwLock<Catalogs>> = LazyLock::new(|| {
+    RwLock::new(Catalogs {
+        bundled: load_bundled_catalogs(),
+        extensions: BTreeMap::default(),
+        user: HashMap::default(),
     })
+});
+
+/// Loads every `locales/<language>/*.json` file embedded in the binary.
+///
+/// Files within a language directory are merged in path order, so later files
+/// override keys defined by earlier ones.
+fn load_bundled_catalogs() -> HashMap<Language, Catalog> {
+    let mut paths = LocaleAssets::iter()
+        .filter(|path| path.ends_with(".json"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut catalogs = HashMap::<Language, Catalog>::default();
+    for path in paths {
+        let Some(language) = path
+            .strip_prefix("locales/")
+            .and_then(|rest| rest.split('/').next())
+            .and_then(Language::from_code)
+        else {
+            log::warn!("ignoring translation catalog with an invalid language tag: {path}");
+            continue;
+        };
+        let content = util::asset_str::<LocaleAssets>(&path);
+        match Catalog::parse(&content) {
+            Ok(catalog) => catalogs.entry(language).or_default().merge(catalog),
+            Err(error) => {
+                log::error!("failed to parse bundled translation catalog {path}: {error:?}")
+            }
+        }
+    }
+    catalogs
+}
+
+fn lookup(language: &Language, key: &str) -> Option<SharedString> {
+    CATALOGS.read().lookup(language, key)
+}
+
+enum Translation {
+    Translated(SharedString),
+    Pseudo,
+    Default,
+}
+
+/// Looks up `key` in the current language, recording the caller when it has to fall
+/// back to the default text.
+#[track_caller]
+fn translate(key: &str, default: &str) -> Translation {
+    let language = current_language();
+    if language == Language::PSEUDO {
+        return Translation::Pseudo;
+    }
+    match lookup(&language, key) {
+        Some(value) => Translation::Translated(value),
+        None => {
+            if language != Language::ENGLISH {
+                missing::record(&language, key, default, Location::caller());
+            }
+            Translation::Default
+        }
+    }
 }
 
+#[track_caller]
 pub fn translate_static(key: &str, default: &'static str) -> Cow<'static, str> {
-    match *LANGUAGE.read() {
-        Language::English => Cow::Borrowed(default),
-        Language::SimplifiedChinese => zh_cn_translations()
-            .get(key)
-            .map(|value| Cow::Borrowed(*value))
-            .unwrap_or_else(|| Cow::Borrowed(default)),
+    match translate(key, default) {
+        Translation::Translated(value) => Cow::Owned(value.to_string()),
+        Translation::Pseudo => Cow::Owned(pseudolocalize(default)),
+        Translation::Default => Cow::Borrowed(default),
     }
 }
 
+#[track_caller]
 pub fn translate_owned(key: &str, default: &str) -> String {
-    match *LANGUAGE.read() {
-        Language::English => default.to_string(),
-        Language::SimplifiedChinese => zh_cn_translations()
-            .get(key)
-            .copied()
-            .unwrap_or(default)
-            .to_string(),
+    match translate(key, default) {
+        Translation::Translated(value) => value.to_string(),
+        Translation::Pseudo => pseudolocalize(default),
+        Translation::Default => default.to_string(),
     }
 }
 
+#[track_caller]
 pub fn shared(key: &str, default: &'static str) -> SharedString {
-    match translate_static(key, default) {
-        Cow::Borrowed(value) => SharedString::new_static(value),
-        Cow::Owned(value) => SharedString::from(value),
+    match translate(key, default) {
+        Translation::Translated(value) => value,
+        Translation::Pseudo => pseudolocalize(default).into(),
+        Translation::Default => SharedString::new_static(default),
     }
 }
 
+/// Translates `key` and substitutes `args` into the resulting message.
+///
+/// Messages use a subset of ICU MessageFormat, so counts and variants are handled by the
+/// translation rather than by concatenating pieces at the call site:
+///
+/// ```ignore
+/// localization::format(
+///     "git_panel.changes",
+///     "{count, plural, =0 {No Changes} one {# Change} other {# Changes}}",
+///     &[("count", entry_count.into())],
+/// )
+/// ```
+#[track_caller]
+pub fn format(key: &str, default: &str, args: &[(&str, MessageArg)]) -> String {
+    match translate(key, default) {
+        Translation::Translated(pattern) => {
+            let language = current_language();
+            match Message::parse(&pattern).and_then(|message| message.format(&language, args)) {
+                Ok(formatted) => return formatted,
+                Err(error) => log::error!("failed to format translation for {key:?}: {error:?}"),
+            }
+        }
+        Translation::Pseudo => {
+            let formatted = Message::parse(default).and_then(|mut message| {
+                message.accent_text();
+                message.format(&Language::ENGLISH, args)
+            });
+            match formatted {
+                Ok(formatted) => return pseudo::wrap(&formatted),
+                Err(error) => {
+                    log::error!("failed to format default message for {key:?}: {error:?}")
+                }
+            }
+        }
+        Translation::Default => {}
+    }
+
+    Message::parse(default)
+        .and_then(|message| message.format(&Language::ENGLISH, args))
+        .unwrap_or_else(|error| {
+            log::error!("failed to format default message for {key:?}: {error:?}");
+            default.to_string()
+        })
+}
+
+#[track_caller]
+pub fn format_shared(key: &str, default: &str, args: &[(&str, MessageArg)]) -> SharedString {
+    format(key, default, args).into()
+}
+
 pub fn set_language(language: Language) {
     *LANGUAGE.write() = language;
 }
 
 pub fn current_language() -> Language {
-    *LANGUAGE.read()
+    LANGUAGE.read().clone()
+}
+
+/// Switches the interface language for the duration of a test, restoring the previous
+/// language when the returned guard is dropped.
+///
+/// The language is global, so the guard also keeps other tests that switch it from
+/// running concurrently.
+#[cfg(any(test, feature = "test-support"))]
+pub fn set_language_for_test(language: Language) -> TestLanguageGuard {
+    let lock = lock_language_for_test();
+    let previous = current_language();
+    set_language(language);
+    TestLanguageGuard {
+        previous,
+        _lock: lock,
+    }
+}
+
+/// Keeps other tests from switching the global language until the guard is dropped.
+#[cfg(any(test, feature = "test-support"))]
+fn lock_language_for_test() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    LOCK.lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+#[cfg(any(test, feature = "test-support"))]
+pub struct TestLanguageGuard {
+    previous: Language,
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl Drop for TestLanguageGuard {
+    fn drop(&mut self) {
+        set_language(self.previous.clone());
+    }
 }
 
 pub fn init(cx: &mut gpui::App) {
+    cx.set_global(LanguageEventChannel {});
     LocalizationSettings::register(cx);
     apply_settings(cx);
 
@@ -223,53 +284,313 @@ pub fn init(cx: &mut gpui::App) {
     .detach();
 }
 
+/// Notifies observers whenever the interface language or the loaded translations change.
+///
+/// Text produced through this crate is looked up at render time, so windows are refreshed
+/// automatically. Anything that caches translated text outside of rendering, such as the
+/// application menus, should observe this global and rebuild:
+///
+/// ```ignore
+/// cx.observe_global::<localization::LanguageEventChannel>(|cx| cx.set_menus(app_menus()))
+///     .detach();
+/// ```
+pub struct LanguageEventChannel {}
+
+impl Global for LanguageEventChannel {}
+
+impl LanguageEventChannel {
+    pub fn trigger_language_changed(cx: &mut App) {
+        if cx.has_global::<Self>() {
+            cx.update_global(|_event_channel: &mut Self, _| {
+                /* triggers language change observers */
+            });
+        }
+        cx.refresh_windows();
+    }
+}
+
+/// Switches the interface language immediately, without waiting for the settings
+/// file to be written and reloaded.
+pub fn switch_language(language: Language, cx: &mut App) {
+    if current_language() != language {
+        set_language(language);
+        LanguageEventChannel::trigger_language_changed(cx);
+    }
+}
+
+/// Loads translation catalogs from the user's locales directory and reloads them
+/// whenever a file in it changes.
+///
+/// Catalogs are read from `<config dir>/locales/<language>/*.json` and take precedence
+/// over the ones bundled with Zed, which lets translators iterate on wording without
+/// rebuilding.
+pub fn watch_user_catalogs(fs: Arc<dyn Fs>, cx: &mut App) {
+    cx.spawn(async move |cx| {
+        let locales_dir = paths::locales_dir();
+        if fs.metadata(locales_dir).await.ok().flatten().is_none() {
+            fs.create_dir(locales_dir)
+                .await
+                .with_context(|| format!("Failed to create locales dir at path {locales_dir:?}"))?;
+        }
+
+        let (mut events, watcher) = fs.watch(locales_dir, Duration::from_millis(100)).await;
+        loop {
+            let user_catalogs = load_user_catalogs(fs.as_ref(), locales_dir).await;
+            // Add language subdirectories since fs.watch is not recursive on Linux.
+            for directory in user_catalogs.directories {
+                watcher.add(&directory).log_err();
+            }
+            CATALOGS.write().user = user_catalogs.catalogs;
+            cx.update(reload_catalogs)?;
+
+            if events.next().await.is_none() {
+                break;
+            }
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+struct UserCatalogs {
+    catalogs: HashMap<Language, Catalog>,
+    directories: Vec<PathBuf>,
+}
+
+async fn load_user_catalogs(fs: &dyn Fs, locales_dir: &Path) -> UserCatalogs {
+    let mut result = UserCatalogs {
+        catalogs: HashMap::default(),
+        directories: Vec::new(),
+    };
+    let Some(mut entries) = fs.read_dir(locales_dir).await.log_err() else {
+        return result;
+    };
+
+    while let Some(entry) = entries.next().await {
+        let Some(directory) = entry.log_err() else {
+            continue;
+        };
+        if !fs.is_dir(&directory).await {
+            continue;
+        }
+        let Some(language) = directory
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(Language::from_code)
+        else {
+            log::warn!("ignoring translation catalogs with an invalid language tag: {directory:?}");
+            continue;
+        };
+        result.directories.push(directory.clone());
+
+        let mut files = Vec::new();
+        if let Some(mut file_entries) = fs.read_dir(&directory).await.log_err() {
+            while let Some(file) = file_entries.next().await {
+                if let Some(file) = file.log_err()
+                    && file
+                        .extension()
+                        .is_some_and(|extension| extension == "json")
+                {
+                    files.push(file);
+                }
+            }
+        }
+        files.sort();
+
+        let catalog = result.catalogs.entry(language).or_default();
+        for file in files {
+            match fs
+                .load(&file)
+                .await
+                .and_then(|content| Catalog::parse(&content))
+            {
+                Ok(file_catalog) => catalog.merge(file_catalog),
+                Err(error) => log::error!("failed to load translation catalog {file:?}: {error:?}"),
+            }
+        }
+    }
+
+    result
+}
+
+/// Adds a translation catalog contributed by an extension.
+///
+/// Extension catalogs take precedence over the bundled ones but are overridden by the
+/// user's own catalogs. Call [`reload_catalogs`] once the extension has been loaded.
+pub fn add_extension_catalog(extension_id: Arc<str>, language: Language, catalog: Catalog) {
+    CATALOGS
+        .write()
+        .extensions
+        .entry(extension_id)
+        .or_default()
+        .entry(language)
+        .or_default()
+        .merge(catalog);
+}
+
+/// Removes every translation catalog contributed by the given extensions.
+pub fn remove_extension_catalogs(extension_ids: &[Arc<str>]) {
+    let mut catalogs = CATALOGS.write();
+    for extension_id in extension_ids {
+        catalogs.extensions.remove(extension_id);
+    }
+}
+
+/// Re-resolves the interface language after catalogs were added or removed, since
+/// the preferred language may have become available or gone away, and notifies observers.
+pub fn reload_catalogs(cx: &mut App) {
+    if let Some(settings) = LocalizationSettings::try_get(cx) {
+        set_language(settings.language());
+    }
+    LanguageEventChannel::trigger_language_changed(cx);
+}
+
 fn apply_settings(cx: &mut gpui::App) {
-    let settings = LocalizationSettings::get_global(cx);
-    set_language(settings.language);
+    let language = LocalizationSettings::get_global(cx).language();
+    switch_language(language, cx);
+}
+
+/// Returns whether translations are available for `language`, either bundled with
+/// Zed, contributed by an extension, or provided in the user's locales directory.
+///
+/// English and the [pseudo-locale](Language::PSEUDO) are always available.
+pub fn is_language_available(language: &Language) -> bool {
+    *language == Language::ENGLISH
+        || *language == Language::PSEUDO
+        || CATALOGS.read().has_translations(language)
+}
+
+/// Returns every language with installed translations, including English.
+pub fn available_languages() -> Vec<Language> {
+    let catalogs = CATALOGS.read();
+    let mut languages = catalogs
+        .layers()
+        .flatten()
+        .filter(|(_, catalog)| !catalog.is_empty())
+        .map(|(language, _)| language.clone())
+        .chain([Language::ENGLISH])
+        .collect::<Vec<_>>();
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+/// Returns the name of `language` in that language, e.g. "日本語" for Japanese.
+///
+/// Each catalog provides its own name under the `language.name` key.
+pub fn display_name(language: &Language) -> SharedString {
+    if *language == Language::ENGLISH {
+        return SharedString::new_static("English");
+    }
+    if *language == Language::PSEUDO {
+        return SharedString::new_static("Pseudo-locale");
+    }
+    lookup(language, "language.name").unwrap_or_else(|| language.code().to_string().into())
+}
+
+/// Returns how to refer to `language` when asking a language model to write in it,
+/// e.g. "Japanese (日本語)".
+///
+/// Models follow an English name most reliably, so the languages bundled with Zed are
+/// named in English, followed by their native name to tell apart variants such as
+/// Simplified and Traditional Chinese. Other languages are named by their language
+/// tag, followed by their catalog's name for the language if one is installed.
+pub fn language_name_for_prompt(language: &Language) -> String {
+    let english_name = match language.code() {
+        "zh-CN" => "Simplified Chinese",
+        "zh-Hant" => "Traditional Chinese",
+        "ja" => "Japanese",
+        "ko" => "Korean",
+        _ if *language == Language::ENGLISH || *language == Language::PSEUDO => {
+            return "English".to_string();
+        }
+        code => {
+            return match lookup(language, "language.name") {
+                Some(name) => format!("{code} ({name})"),
+                None => code.to_string(),
+            };
+        }
+    };
+    format!("{english_name} ({})", display_name(language))
+}
+
+/// Resolves a `ui_language` preference to a concrete language, negotiating the
+/// requested locales against the available catalogs.
+pub fn language_for_preference(preference: &UiLanguagePreference) -> Language {
+    match preference {
+        UiLanguagePreference::Auto => negotiate(&system_locales(), is_language_available),
+        UiLanguagePreference::Locale(tag) => {
+            negotiate(std::slice::from_ref(tag), is_language_available)
+        }
+    }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct LocalizationSettings {
-    language: Language,
+    preference: UiLanguagePreference,
+    system_locales: Vec<String>,
+    /// Whether fuzzy matching also matches Chinese text by its pinyin.
+    pub pinyin_matching: bool,
 }
 
 impl LocalizationSettings {
+    /// The interface language selected by these settings, resolved against the
+    /// catalogs that are currently available.
     pub fn language(&self) -> Language {
-        self.language
+        match self.preference {
+            UiLanguagePreference::Auto => negotiate(&self.system_locales, is_language_available),
+            UiLanguagePreference::Locale(_) => language_for_preference(&self.preference),
+        }
+    }
+
+    pub fn preference(&self) -> &UiLanguagePreference {
+        &self.preference
+    }
+
+    /// The locales reported by the operating system when the interface language
+    /// follows the system. Empty otherwise.
+    pub fn system_locales(&self) -> &[String] {
+        &self.system_locales
     }
 }
 
 impl Settings for LocalizationSettings {
     fn from_settings(content: &SettingsContent, _: &mut gpui::App) -> Self {
-        use settings::UiLanguagePreference;
-
         let preference = content
             .ui_language
-            .unwrap_or(UiLanguagePreference::SimplifiedChinese);
-
-        let language = match preference {
-            UiLanguagePreference::SimplifiedChinese | UiLanguagePreference::Auto => {
-                Language::SimplifiedChinese
-            }
-            UiLanguagePreference::English => Language::English,
+            .clone()
+            .unwrap_or_else(|| UiLanguagePreference::Locale("zh-CN".into()));
+
+        let system_locales = if preference == UiLanguagePreference::Auto {
+            let system_locales = system_locales();
+            log::debug!(
+                "resolved system locales {system_locales:?} to interface language {}",
+                negotiate(&system_locales, is_language_available)
+            );
+            system_locales
+        } else {
+            Vec::new()
         };
 
-        Self { language }
+        Self {
+            preference,
+            system_locales,
+            pinyin_matching: content.pinyin_matching.unwrap_or(true),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Mutex;
-
-    static TEST_GUARD: Mutex<()> = Mutex::new(());
+    use std::cell::Cell;
+    use std::rc::Rc;
 
     #[test]
     fn defaults_to_simplified_chinese() {
-        let _guard = TEST_GUARD.lock().unwrap();
+        let _guard = lock_language_for_test();
         let original = current_language();
-        set_language(Language::SimplifiedChinese);
+        set_language(Language::SIMPLIFIED_CHINESE);
 
         assert_eq!(
             translate_owned("command.apply_all", "Apply All"),
@@ -281,9 +602,9 @@ mod tests {
 
     #[test]
     fn english_returns_default_text() {
-        let _guard = TEST_GUARD.lock().unwrap();
+        let _guard = lock_language_for_test();
         let original = current_language();
-        set_language(Language::English);
+        set_language(Language::ENGLISH);
 
         assert_eq!(
             translate_owned("command.apply_all", "Apply All"),
@@ -295,9 +616,9 @@ mod tests {
 
     #[test]
     fn missing_key_falls_back_to_default() {
-        let _guard = TEST_GUARD.lock().unwrap();
+        let _guard = lock_language_for_test();
         let original = current_language();
-        set_language(Language::SimplifiedChinese);
+        set_language(Language::SIMPLIFIED_CHINESE);
 
         assert_eq!(translate_owned("missing.key", "Fallback"), "Fallback");
 
@@ -305,32 +626,277 @@ mod tests {
     }
 
     #[test]
-    fn restart_prompt_translations_present() {
-        let _guard = TEST_GUARD.lock().unwrap();
+    fn menu_translations_present() {
+        let _guard = lock_language_for_test();
         let original = current_language();
-        set_language(Language::SimplifiedChinese);
+        set_language(Language::SIMPLIFIED_CHINESE);
 
+        assert_eq!(translate_owned("menu.file.save", "Save"), "保存");
+        assert_eq!(translate_owned("menu.view.zoom_in", "Zoom In"), "放大");
         assert_eq!(
-            translate_owned("prompt.restart_required.restart_now", "Restart Now",),
-            "立即重启"
+            translate_owned("menu.help.documentation", "Documentation"),
+            "文档"
         );
 
         set_language(original);
     }
 
     #[test]
-    fn menu_translations_present() {
-        let _guard = TEST_GUARD.lock().unwrap();
+    fn bundled_catalogs_are_loaded_from_assets() {
+        let catalogs = load_bundled_catalogs();
+        let zh_cn = catalogs
+            .get(&Language::SIMPLIFIED_CHINESE)
+            .expect("zh-CN catalog should be bundled");
+        assert_eq!(zh_cn.get("menu.file.save").unwrap(), "保存");
+    }
+
+    #[test]
+    fn bundled_catalogs_cover_the_same_keys() {
+        let catalogs = load_bundled_catalogs();
+        let reference = &catalogs[&Language::SIMPLIFIED_CHINESE];
+        for language in [
+            Language::TRADITIONAL_CHINESE,
+            Language::JAPANESE,
+            Language::KOREAN,
+        ] {
+            let catalog = &catalogs[&language];
+            let mut missing = reference
+                .keys()
+                .filter(|key| catalog.get(key).is_none())
+                .collect::<Vec<_>>();
+            missing.sort();
+            assert!(missing.is_empty(), "{language} is missing {missing:?}");
+            assert!(
+                available_languages().contains(&language),
+                "{language} should be available"
+            );
+        }
+    }
+
+    #[test]
+    fn user_catalogs_override_bundled_text() {
+        let _guard = lock_language_for_test();
         let original = current_language();
-        set_language(Language::SimplifiedChinese);
+        set_language(Language::SIMPLIFIED_CHINESE);
+
+        let mut user = HashMap::default();
+        user.insert(
+            Language::SIMPLIFIED_CHINESE,
+            Catalog::parse(
+                r#"{
+                    // Comments are allowed in catalogs.
+                    "menu.file.save": "存储",
+                }"#,
+            )
+            .unwrap(),
+        );
+        let previous = std::mem::replace(&mut CATALOGS.write().user, user);
+
+        assert_eq!(translate_owned("menu.file.save", "Save"), "存储");
+        assert_eq!(translate_owned("menu.edit.undo", "Undo"), "撤销");
 
+        CATALOGS.write().user = previous;
+        set_language(original);
+    }
+
+    #[test]
+    fn extension_catalogs_add_languages_and_override_bundled_text() {
+        let _guard = lock_language_for_test();
+        let original = current_language();
+        let esperanto = Language::from_code("eo").unwrap();
+        let extension_id: Arc<str> = "esperanto-language-pack".into();
+        assert!(!is_language_available(&esperanto));
+        assert_eq!(language_name_for_prompt(&esperanto), "eo");
+
+        add_extension_catalog(
+            extension_id.clone(),
+            esperanto.clone(),
+            Catalog::parse(r#"{ "language.name": "Esperanto", "menu.file.save": "Konservi" }"#)
+                .unwrap(),
+        );
+        add_extension_catalog(
+            extension_id.clone(),
+            Language::SIMPLIFIED_CHINESE,
+            Catalog::parse(r#"{ "menu.file.save": "存盘" }"#).unwrap(),
+        );
+        assert!(is_language_available(&esperanto));
+        assert!(available_languages().contains(&esperanto));
+        assert_eq!(display_name(&esperanto), "Esperanto");
+        assert_eq!(language_name_for_prompt(&esperanto), "eo (Esperanto)");
+        assert_eq!(
+            language_name_for_prompt(&Language::SIMPLIFIED_CHINESE),
+            "Simplified Chinese (简体中文)"
+        );
+        assert_eq!(language_name_for_prompt(&Language::ENGLISH), "English");
+
+        set_language(esperanto.clone());
+        assert_eq!(translate_owned("menu.file.save", "Save"), "Konservi");
+        assert_eq!(translate_owned("menu.edit.undo", "Undo"), "Undo");
+
+        set_language(Language::SIMPLIFIED_CHINESE);
+        assert_eq!(translate_owned("menu.file.save", "Save"), "存盘");
+        assert_eq!(translate_owned("menu.edit.undo", "Undo"), "撤销");
+
+        remove_extension_catalogs(&[extension_id]);
+        assert!(!is_language_available(&esperanto));
         assert_eq!(translate_owned("menu.file.save", "Save"), "保存");
-        assert_eq!(translate_owned("menu.view.zoom_in", "Zoom In"), "放大");
+
+        set_language(original);
+    }
+
+    #[test]
+    fn format_uses_translated_plural_rules() {
+        let _guard = lock_language_for_test();
+        let original = current_language();
+        let default = "{count, plural, =0 {No Changes} one {# Change} other {# Changes}}";
+
+        set_language(Language::ENGLISH);
         assert_eq!(
-            translate_owned("menu.help.documentation", "Documentation"),
-            "文档"
+            format("git_panel.changes", default, &[("count", 1.into())]),
+            "1 Change"
+        );
+        assert_eq!(
+            format("git_panel.changes", default, &[("count", 3.into())]),
+            "3 Changes"
         );
 
+        set_language(Language::SIMPLIFIED_CHINESE);
+        assert_eq!(
+            format("git_panel.changes", default, &[("count", 0.into())]),
+            "无更改"
+        );
+        assert_eq!(
+            format("git_panel.changes", default, &[("count", 3.into())]),
+            "3 项更改"
+        );
+
+        set_language(original);
+    }
+
+    #[test]
+    fn format_falls_back_to_default_for_missing_keys() {
+        let _guard = lock_language_for_test();
+        let original = current_language();
+        set_language(Language::SIMPLIFIED_CHINESE);
+
+        assert_eq!(
+            format(
+                "missing.key",
+                "Restart {app} now?",
+                &[("app", "Zed".into())]
+            ),
+            "Restart Zed now?"
+        );
+
+        set_language(original);
+    }
+
+    #[test]
+    fn pseudo_locale_accents_every_translated_string() {
+        let _guard = lock_language_for_test();
+        let original = current_language();
+        set_language(Language::PSEUDO);
+
+        assert_eq!(shared("menu.file.save", "Save"), "[Šåṽé~~]");
+        assert_eq!(
+            format(
+                "git_panel.changes",
+                "{count, plural, one {# Change} other {# Changes}}",
+                &[("count", 3.into())]
+            ),
+            "[3 Çĥåñĝéš~~~]"
+        );
+        assert!(
+            missing_translations()
+                .iter()
+                .all(|entry| entry.language != Language::PSEUDO)
+        );
+
+        set_language(original);
+    }
+
+    #[test]
+    fn missing_translations_are_recorded_with_call_sites() {
+        let _guard = lock_language_for_test();
+        let original = current_language();
+        missing::clear();
+
+        set_language(Language::ENGLISH);
+        translate_owned("test.untranslated", "Untranslated");
+        assert!(missing_translations().is_empty());
+
+        set_language(Language::JAPANESE);
+        translate_owned("test.untranslated", "Untranslated");
+        translate_owned("test.untranslated", "Untranslated");
+        shared("menu.file.save", "Save");
+
+        let missing = missing_translations();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].key, "test.untranslated");
+        assert_eq!(missing[0].language, Language::JAPANESE);
+        assert_eq!(missing[0].call_sites.len(), 2);
+        assert!(
+            missing[0]
+                .call_sites
+                .iter()
+                .all(|call_site| call_site.file.ends_with("lib.rs"))
+        );
+        assert!(missing_translations_report().contains("`test.untranslated`"));
+
+        missing::clear();
+        set_language(original);
+    }
+
+    #[test]
+    fn actions_are_translated_without_recording_missing_keys() {
+        let _guard = lock_language_for_test();
+        let original = current_language();
+        missing::clear();
+
+        set_language(Language::SIMPLIFIED_CHINESE);
+        assert_eq!(
+            action_display_name("editor::Backspace", "editor: backspace").unwrap(),
+            "编辑器: 退格"
+        );
+        assert!(action_display_name("test::Untranslated", "test: untranslated").is_none());
+        assert!(action_documentation("test::Untranslated", "Does nothing.").is_none());
+        assert!(missing_translations().is_empty());
+
+        set_language(Language::ENGLISH);
+        assert!(action_display_name("editor::Backspace", "editor: backspace").is_none());
+
+        set_language(Language::PSEUDO);
+        assert_eq!(
+            action_display_name("editor::Backspace", "editor: backspace").unwrap(),
+            pseudolocalize("editor: backspace")
+        );
+
+        set_language(original);
+    }
+
+    #[gpui::test]
+    fn switching_language_notifies_observers(cx: &mut gpui::TestAppContext) {
+        let _guard = lock_language_for_test();
+        let original = current_language();
+        set_language(Language::SIMPLIFIED_CHINESE);
+
+        let notifications = Rc::new(Cell::new(0));
+        cx.update(|cx| {
+            cx.set_global(LanguageEventChannel {});
+            let notifications = notifications.clone();
+            cx.observe_global::<LanguageEventChannel>(move |_| {
+                notifications.set(notifications.get() + 1);
+            })
+            .detach();
+
+            switch_language(Language::ENGLISH, cx);
+            assert_eq!(current_language(), Language::ENGLISH);
+            assert_eq!(shared("menu.file.save", "Save"), "Save");
+
+            switch_language(Language::ENGLISH, cx);
+        });
+        assert_eq!(notifications.get(), 1);
+
         set_language(original);
     }
 }
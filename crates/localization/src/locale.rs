@@ -0,0 +1,216 @@
+use crate::Language;
+
+/// Returns the locales requested by the operating system, most preferred first.
+///
+/// On Linux and FreeBSD this follows the POSIX environment (`LANGUAGE`, `LC_ALL`,
+/// `LC_MESSAGES`, `LANG`); elsewhere it asks the platform for the user's locale.
+pub fn system_locales() -> Vec<String> {
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+    {
+        let locales = requested_locales_from_env(|name| std::env::var(name).ok());
+        if !locales.is_empty() {
+            return locales;
+        }
+    }
+
+    sys_locale::get_locales().collect()
+}
+
+/// Resolves the POSIX locale environment into a list of requested locales.
+///
+/// `LC_ALL` overrides `LC_MESSAGES`, which overrides `LANG`. The GNU `LANGUAGE`
+/// priority list is honored unless the locale is `C` or `POSIX`.
+pub fn requested_locales_from_env(var: impl Fn(&str) -> Option<String>) -> Vec<String> {
+    let primary = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .find_map(|name| var(name).filter(|value| !value.is_empty()));
+
+    let mut locales = Vec::new();
+    if let Some(primary) = &primary
+        && is_posix_default(primary)
+    {
+        return locales;
+    }
+
+    if let Some(language) = var("LANGUAGE") {
+        locales.extend(
+            language
+                .split(':')
+                .filter(|locale| !locale.is_empty())
+                .map(str::to_string),
+        );
+    }
+    locales.extend(primary);
+    locales
+}
+
+fn is_posix_default(locale: &str) -> bool {
+    let locale = strip_posix_suffixes(locale);
+    locale == "C" || locale == "POSIX"
+}
+
+/// Removes the codeset and modifier from a POSIX locale such as `zh_CN.UTF-8@pinyin`.
+fn strip_posix_suffixes(locale: &str) -> &str {
+    let end = locale.find(['.', '@']).unwrap_or(locale.len());
+    &locale[..end]
+}
+
+/// Returns the tags to try for `locale`, from most to least specific, ending with English.
+///
+/// Scripts are inferred for Chinese so that traditional locales never fall back to
+/// simplified text: `zh_TW` yields `zh-Hant-TW`, `zh-TW`, `zh-Hant`, `en`.
+pub fn fallback_chain(locale: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let normalized = strip_posix_suffixes(locale.trim()).replace('_', "-");
+
+    let mut subtags = normalized.split('-').filter(|subtag| !subtag.is_empty());
+    let Some(language) = subtags.next().map(str::to_ascii_lowercase) else {
+        chain.push(Language::ENGLISH.code().to_string());
+        return chain;
+    };
+
+    let mut script = None;
+    let mut region = None;
+    for subtag in subtags {
+        if script.is_none() && region.is_none() && subtag.len() == 4 {
+            let mut chars = subtag.chars();
+            script = chars.next().map(|first| {
+                first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()
+            });
+        } else if region.is_none() && (subtag.len() == 2 || subtag.len() == 3) {
+            region = Some(subtag.to_ascii_uppercase());
+        }
+    }
+
+    let script = script.or_else(|| default_script(&language, region.as_deref()));
+    let is_default_script = script == default_script(&language, None);
+
+    if let (Some(script), Some(region)) = (&script, &region) {
+        chain.push(format!("{language}-{script}-{region}"));
+    }
+    if let Some(region) = &region {
+        chain.push(format!("{language}-{region}"));
+    }
+    if let Some(script) = &script {
+        chain.push(format!("{language}-{script}"));
+    }
+    if is_default_script {
+        chain.push(language);
+    }
+
+    let english = Language::ENGLISH.code().to_string();
+    if !chain.contains(&english) {
+        chain.push(english);
+    }
+    chain
+}
+
+fn default_script(language: &str, region: Option<&str>) -> Option<String> {
+    match (language, region) {
+        ("zh", Some("TW" | "HK" | "MO")) => Some("Hant".to_string()),
+        ("zh", _) => Some("Hans".to_string()),
+        _ => None,
+    }
+}
+
+/// Picks the first language in the fallback chains of `requested` that satisfies
+/// `is_available`, defaulting to English.
+pub fn negotiate(requested: &[String], is_available: impl Fn(&Language) -> bool) -> Language {
+    requested
+        .iter()
+        .flat_map(|locale| fallback_chain(locale))
+        .filter_map(|tag| Language::from_code(&tag))
+        .find(|language| is_available(language))
+        .unwrap_or(Language::ENGLISH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collections::HashMap;
+
+    #[test]
+    fn fallback_chain_infers_chinese_scripts() {
+        assert_eq!(
+            fallback_chain("zh_TW.UTF-8"),
+            ["zh-Hant-TW", "zh-TW", "zh-Hant", "en"]
+        );
+        assert_eq!(
+            fallback_chain("zh_CN.UTF-8"),
+            ["zh-Hans-CN", "zh-CN", "zh-Hans", "zh", "en"]
+        );
+        assert_eq!(fallback_chain("zh-Hant"), ["zh-Hant", "en"]);
+        assert_eq!(fallback_chain("de_DE@euro"), ["de-DE", "de", "en"]);
+        assert_eq!(fallback_chain(""), ["en"]);
+    }
+
+    #[test]
+    fn negotiation_never_picks_simplified_for_traditional_locales() {
+        let bundled = |language: &Language| {
+            [
+                Language::ENGLISH,
+                Language::SIMPLIFIED_CHINESE,
+                Language::TRADITIONAL_CHINESE,
+                Language::JAPANESE,
+            ]
+            .contains(language)
+        };
+        assert_eq!(
+            negotiate(&["zh_CN.UTF-8".into()], bundled),
+            Language::SIMPLIFIED_CHINESE
+        );
+        assert_eq!(
+            negotiate(&["zh-SG".into()], bundled),
+            Language::SIMPLIFIED_CHINESE
+        );
+        assert_eq!(
+            negotiate(&["zh_TW.UTF-8".into()], bundled),
+            Language::TRADITIONAL_CHINESE
+        );
+        assert_eq!(
+            negotiate(&["zh_HK".into()], bundled),
+            Language::TRADITIONAL_CHINESE
+        );
+        assert_eq!(
+            negotiate(&["ja_JP.UTF-8".into()], bundled),
+            Language::JAPANESE
+        );
+        assert_eq!(negotiate(&["fr_FR".into()], bundled), Language::ENGLISH);
+        assert_eq!(
+            negotiate(&["fr_FR".into(), "zh_CN".into()], bundled),
+            Language::SIMPLIFIED_CHINESE
+        );
+        assert_eq!(
+            negotiate(&["zh_TW".into()], |language: &Language| {
+                [Language::ENGLISH, Language::SIMPLIFIED_CHINESE].contains(language)
+            }),
+            Language::ENGLISH
+        );
+    }
+
+    #[test]
+    fn posix_environment_precedence() {
+        let env = |vars: &[(&str, &str)]| {
+            let vars = vars
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>();
+            requested_locales_from_env(move |name| vars.get(name).cloned())
+        };
+
+        assert_eq!(
+            env(&[("LANG", "en_US.UTF-8"), ("LC_MESSAGES", "zh_CN.UTF-8")]),
+            ["zh_CN.UTF-8"]
+        );
+        assert_eq!(
+            env(&[("LANG", "en_US.UTF-8"), ("LC_ALL", "zh_TW.UTF-8")]),
+            ["zh_TW.UTF-8"]
+        );
+        assert_eq!(
+            env(&[("LANG", "zh_CN.UTF-8"), ("LANGUAGE", "zh_TW:en")]),
+            ["zh_TW", "en", "zh_CN.UTF-8"]
+        );
+        assert!(env(&[("LC_ALL", "C.UTF-8"), ("LANGUAGE", "zh_CN")]).is_empty());
+        assert!(env(&[]).is_empty());
+    }
+}
@@ -0,0 +1,341 @@
+//! Formatting of translated messages, which use the subset of ICU MessageFormat
+//! parsed by the `message_format` crate.
+
+use crate::Language;
+use anyhow::{Context as _, Result};
+use gpui::SharedString;
+use message_format::{Part, Selector};
+use std::borrow::Cow;
+use std::fmt::Write as _;
+
+pub use message_format::PluralCategory;
+
+/// A value substituted into a message.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MessageArg<'a> {
+    Str(Cow<'a, str>),
+    Number(f64),
+}
+
+impl MessageArg<'_> {
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            MessageArg::Number(number) => Some(*number),
+            MessageArg::Str(text) => text.parse().ok(),
+        }
+    }
+}
+
+impl std::fmt::Display for MessageArg<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageArg::Str(text) => f.write_str(text),
+            MessageArg::Number(number) => write!(f, "{number}"),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for MessageArg<'a> {
+    fn from(value: &'a str) -> Self {
+        MessageArg::Str(Cow::Borrowed(value))
+    }
+}
+
+impl From<String> for MessageArg<'_> {
+    fn from(value: String) -> Self {
+        MessageArg::Str(Cow::Owned(value))
+    }
+}
+
+impl<'a> From<&'a String> for MessageArg<'a> {
+    fn from(value: &'a String) -> Self {
+        MessageArg::Str(Cow::Borrowed(value.as_str()))
+    }
+}
+
+impl<'a> From<&'a SharedString> for MessageArg<'a> {
+    fn from(value: &'a SharedString) -> Self {
+        MessageArg::Str(Cow::Borrowed(value.as_ref()))
+    }
+}
+
+macro_rules! impl_number_arg {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for MessageArg<'_> {
+                fn from(value: $ty) -> Self {
+                    MessageArg::Number(value as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_number_arg!(i32, i64, u32, u64, usize, f32, f64);
+
+/// Returns the cardinal plural category of `number` in `language`, following the
+/// CLDR rules for integers. Fractional numbers are treated as `other`.
+pub fn plural_category(language: &Language, number: f64) -> PluralCategory {
+    if number.fract() != 0.0 || number < 0.0 {
+        return PluralCategory::Other;
+    }
+    let n = number as u64;
+    let (mod10, mod100) = (n % 10, n % 100);
+
+    match language.primary_subtag() {
+        "zh" | "ja" | "ko" | "vi" | "th" | "id" | "ms" => PluralCategory::Other,
+        "fr" => {
+            if n <= 1 {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+        "ru" | "uk" | "be" => {
+            if mod10 == 1 && mod100 != 11 {
+                PluralCategory::One
+            } else if (2..=4).contains(&mod10) && !(12..=14).contains(&mod100) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Many
+            }
+        }
+        "pl" => {
+            if n == 1 {
+                PluralCategory::One
+            } else if (2..=4).contains(&mod10) && !(12..=14).contains(&mod100) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Many
+            }
+        }
+        "ar" => match n {
+            0 => PluralCategory::Zero,
+            1 => PluralCategory::One,
+            2 => PluralCategory::Two,
+            _ if (3..=10).contains(&mod100) => PluralCategory::Few,
+            _ if (11..=99).contains(&mod100) => PluralCategory::Many,
+            _ => PluralCategory::Other,
+        },
+        _ => {
+            if n == 1 {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+    }
+}
+
+/// A parsed message pattern.
+#[derive(Debug, PartialEq)]
+pub struct Message {
+    parts: Vec<Part>,
+}
+
+impl Message {
+    pub fn parse(pattern: &str) -> Result<Self> {
+        Ok(Self {
+            parts: message_format::parse(pattern)?,
+        })
+    }
+
+    pub fn format(&self, language: &Language, args: &[(&str, MessageArg)]) -> Result<String> {
+        let mut output = String::new();
+        format_parts(&self.parts, language, args, None, &mut output)?;
+        Ok(output)
+    }
+
+    /// Accents the literal text of the message for pseudo-localization, leaving
+    /// arguments and variant selectors intact.
+    pub(crate) fn accent_text(&mut self) {
+        accent_parts(&mut self.parts);
+    }
+}
+
+fn accent_parts(parts: &mut [Part]) {
+    for part in parts {
+        match part {
+            Part::Text(text) => *text = crate::pseudo::accent(text),
+            Part::Argument(_) | Part::Pound => {}
+            Part::Plural { variants, .. } | Part::Select { variants, .. } => {
+                for (_, variant) in variants {
+                    accent_parts(variant);
+                }
+            }
+        }
+    }
+}
+
+fn find_arg<'a, 'b>(args: &'a [(&str, MessageArg<'b>)], name: &str) -> Result<&'a MessageArg<'b>> {
+    args.iter()
+        .find(|(arg_name, _)| *arg_name == name)
+        .map(|(_, value)| value)
+        .with_context(|| format!("missing message argument {name:?}"))
+}
+
+fn format_parts(
+    parts: &[Part],
+    language: &Language,
+    args: &[(&str, MessageArg)],
+    plural_number: Option<f64>,
+    output: &mut String,
+) -> Result<()> {
+    for part in parts {
+        match part {
+            Part::Text(text) => output.push_str(text),
+            Part::Argument(name) => write!(output, "{}", find_arg(args, name)?)?,
+            Part::Pound => match plural_number {
+                Some(number) => write!(output, "{number}")?,
+                None => output.push('#'),
+            },
+            Part::Plural { argument, variants } => {
+                let number = find_arg(args, argument)?
+                    .as_number()
+                    .with_context(|| format!("plural argument {argument:?} is not a number"))?;
+                let category = plural_category(language, number);
+                let variant = variants
+                    .iter()
+                    .find(|(selector, _)| *selector == Selector::Exact(number))
+                    .or_else(|| {
+                        variants
+                            .iter()
+                            .find(|(selector, _)| *selector == Selector::Category(category))
+                    })
+                    .or_else(|| {
+                        variants.iter().find(|(selector, _)| {
+                            *selector == Selector::Category(PluralCategory::Other)
+                        })
+                    })
+                    .with_context(|| format!("plural {argument:?} has no 'other' variant"))?;
+                format_parts(&variant.1, language, args, Some(number), output)?;
+            }
+            Part::Select { argument, variants } => {
+                let value = find_arg(args, argument)?.to_string();
+                let variant = variants
+                    .iter()
+                    .find(|(selector, _)| matches!(selector, Selector::Keyword(k) if *k == value))
+                    .or_else(|| {
+                        variants
+                            .iter()
+                            .find(|(selector, _)| matches!(selector, Selector::Keyword(k) if k == "other"))
+                    })
+                    .with_context(|| format!("select {argument:?} has no 'other' variant"))?;
+                format_parts(&variant.1, language, args, plural_number, output)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(pattern: &str, language: &Language, args: &[(&str, MessageArg)]) -> String {
+        Message::parse(pattern)
+            .unwrap()
+            .format(language, args)
+            .unwrap()
+    }
+
+    #[test]
+    fn substitutes_named_arguments() {
+        assert_eq!(
+            format(
+                "Restart {app} now?",
+                &Language::ENGLISH,
+                &[("app", "Zed".into())]
+            ),
+            "Restart Zed now?"
+        );
+        assert_eq!(
+            format(
+                "{count} of {total}",
+                &Language::ENGLISH,
+                &[("count", 2.into()), ("total", 5.into())]
+            ),
+            "2 of 5"
+        );
+    }
+
+    #[test]
+    fn selects_plural_variants() {
+        let pattern = "{count, plural, =0 {no files} one {# file} other {# files}} changed";
+        assert_eq!(
+            format(pattern, &Language::ENGLISH, &[("count", 0.into())]),
+            "no files changed"
+        );
+        assert_eq!(
+            format(pattern, &Language::ENGLISH, &[("count", 1.into())]),
+            "1 file changed"
+        );
+        assert_eq!(
+            format(pattern, &Language::ENGLISH, &[("count", 12.into())]),
+            "12 files changed"
+        );
+        assert_eq!(
+            format(
+                pattern,
+                &Language::SIMPLIFIED_CHINESE,
+                &[("count", 1.into())]
+            ),
+            "1 files changed"
+        );
+    }
+
+    #[test]
+    fn plural_rules_follow_cldr() {
+        let russian = Language::from_code("ru").unwrap();
+        assert_eq!(plural_category(&russian, 1.0), PluralCategory::One);
+        assert_eq!(plural_category(&russian, 3.0), PluralCategory::Few);
+        assert_eq!(plural_category(&russian, 11.0), PluralCategory::Many);
+        assert_eq!(plural_category(&russian, 21.0), PluralCategory::One);
+        assert_eq!(
+            plural_category(&Language::JAPANESE, 1.0),
+            PluralCategory::Other
+        );
+        assert_eq!(
+            plural_category(&Language::ENGLISH, 1.5),
+            PluralCategory::Other
+        );
+    }
+
+    #[test]
+    fn selects_keyword_variants() {
+        let pattern = "{gender, select, female {She} male {He} other {They}} replied";
+        assert_eq!(
+            format(pattern, &Language::ENGLISH, &[("gender", "female".into())]),
+            "She replied"
+        );
+        assert_eq!(
+            format(pattern, &Language::ENGLISH, &[("gender", "unknown".into())]),
+            "They replied"
+        );
+    }
+
+    #[test]
+    fn quotes_escape_syntax_characters() {
+        assert_eq!(
+            format(
+                "'{'{name}'}' isn''t #",
+                &Language::ENGLISH,
+                &[("name", "x".into())]
+            ),
+            "{x} isn't #"
+        );
+    }
+
+    #[test]
+    fn reports_malformed_patterns() {
+        assert!(Message::parse("{count, plural, one {# file}").is_err());
+        assert!(Message::parse("unbalanced }").is_err());
+        assert!(Message::parse("{count, ordinal, other {#}}").is_err());
+        assert!(
+            Message::parse("{name}")
+                .unwrap()
+                .format(&Language::ENGLISH, &[])
+                .is_err()
+        );
+    }
+}
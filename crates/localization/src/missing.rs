@@ -0,0 +1,143 @@
+//! Tracks translation keys that fell back to their default text, so translation
+//! coverage can be measured from a running instance.
+
+use crate::{Language, display_name, lookup};
+use collections::{BTreeMap, BTreeSet, HashMap};
+use gpui::SharedString;
+use parking_lot::Mutex;
+use std::fmt::Write as _;
+use std::panic::Location;
+use std::sync::LazyLock;
+
+/// A translation key that was requested in a language without a translation for it.
+#[derive(Clone, Debug)]
+pub struct MissingTranslation {
+    pub language: Language,
+    pub key: SharedString,
+    pub default: SharedString,
+    /// Where the key was looked up, in source order.
+    pub call_sites: BTreeSet<CallSite>,
+}
+
+/// The source location of a call to one of the translation helpers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CallSite {
+    pub file: &'static str,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl From<&'static Location<'static>> for CallSite {
+    fn from(location: &'static Location<'static>) -> Self {
+        Self {
+            file: location.file(),
+            line: location.line(),
+            column: location.column(),
+        }
+    }
+}
+
+impl std::fmt::Display for CallSite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+static MISSING: LazyLock<Mutex<HashMap<Language, BTreeMap<SharedString, MissingTranslation>>>> =
+    LazyLock::new(Default::default);
+
+pub(crate) fn record(
+    language: &Language,
+    key: &str,
+    default: &str,
+    location: &'static Location<'static>,
+) {
+    let mut missing = MISSING.lock();
+    let keys = missing.entry(language.clone()).or_default();
+    if let Some(entry) = keys.get_mut(key) {
+        entry.call_sites.insert(location.into());
+        return;
+    }
+
+    let key = SharedString::from(key.to_string());
+    keys.insert(
+        key.clone(),
+        MissingTranslation {
+            language: language.clone(),
+            key,
+            default: default.to_string().into(),
+            call_sites: BTreeSet::from_iter([location.into()]),
+        },
+    );
+}
+
+/// Returns the keys that have fallen back to their default text since startup, sorted
+/// by language and key.
+///
+/// Keys that have since gained a translation, e.g. because a catalog was edited, are
+/// left out.
+pub fn missing_translations() -> Vec<MissingTranslation> {
+    let missing = MISSING.lock();
+    let mut languages = missing.keys().collect::<Vec<_>>();
+    languages.sort();
+    languages
+        .into_iter()
+        .flat_map(|language| missing[language].values())
+        .filter(|entry| lookup(&entry.language, &entry.key).is_none())
+        .cloned()
+        .collect()
+}
+
+/// Renders [`missing_translations`] as a Markdown report.
+pub fn missing_translations_report() -> String {
+    let missing = missing_translations();
+    let mut report = String::from("# Missing Translations\n\n");
+    if missing.is_empty() {
+        report.push_str(
+            "Every string looked up so far has a translation. Open more of the interface \
+             to check the text it shows.\n",
+        );
+        return report;
+    }
+
+    report.push_str(
+        "These keys fell back to their default English text since Zed started. Text that \
+         doesn't go through `localization` at all isn't listed here; set `ui_language` to \
+         `en-XA` to render translatable text in pseudo-localized form and spot the rest.\n",
+    );
+
+    let mut current_language = None;
+    for entry in &missing {
+        if current_language != Some(&entry.language) {
+            let count = missing
+                .iter()
+                .filter(|other| other.language == entry.language)
+                .count();
+            writeln!(
+                report,
+                "\n## {} ({})\n\n{count} missing {}.",
+                display_name(&entry.language),
+                entry.language,
+                if count == 1 { "key" } else { "keys" },
+            )
+            .ok();
+            current_language = Some(&entry.language);
+        }
+
+        writeln!(
+            report,
+            "\n### `{}`\n\nDefault: {:?}\n",
+            entry.key, entry.default
+        )
+        .ok();
+        for call_site in &entry.call_sites {
+            writeln!(report, "- {call_site}").ok();
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+pub(crate) fn clear() {
+    MISSING.lock().clear();
+}
@@ -0,0 +1,114 @@
+//! Formatting numbers and byte sizes for the interface language.
+
+use crate::{Language, current_language};
+
+const BYTE_UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+
+/// Formats `value` with `fraction_digits` digits after the decimal separator, grouping
+/// the digits of its integer part as is customary in the interface language, e.g.
+/// `1,234.5` in English and `1.234,5` in German.
+pub fn format_number(value: f64, fraction_digits: usize) -> String {
+    format_number_for(&current_language(), value, fraction_digits)
+}
+
+/// Formats a size in bytes with binary units, e.g. `1.5 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    format_bytes_for(&current_language(), bytes)
+}
+
+fn format_number_for(language: &Language, value: f64, fraction_digits: usize) -> String {
+    let (decimal_separator, group_separator) = separators(language);
+    let digits = format!("{:.*}", fraction_digits, value.abs());
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (digits.as_str(), None),
+    };
+
+    let mut formatted = String::with_capacity(digits.len() + integer.len() / 3 + 1);
+    if value < 0.0 && digits.bytes().any(|digit| matches!(digit, b'1'..=b'9')) {
+        formatted.push('-');
+    }
+    for (ix, digit) in integer.char_indices() {
+        if ix > 0 && (integer.len() - ix).is_multiple_of(3) {
+            formatted.push(group_separator);
+        }
+        formatted.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        formatted.push(decimal_separator);
+        formatted.push_str(fraction);
+    }
+    formatted
+}
+
+fn format_bytes_for(language: &Language, bytes: u64) -> String {
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    // Compare the rounded size, so that 1,048,575 bytes are shown as `1 MiB` rather than
+    // `1024 KiB`.
+    while (size * 10.).round() >= 10_240. && unit < BYTE_UNITS.len() - 1 {
+        size /= 1024.;
+        unit += 1;
+    }
+
+    let size = (size * 10.).round() / 10.;
+    let fraction_digits = if size.fract() == 0. { 0 } else { 1 };
+    format!(
+        "{} {}",
+        format_number_for(language, size, fraction_digits),
+        BYTE_UNITS[unit]
+    )
+}
+
+/// Returns the decimal and group separators of `language`, following CLDR.
+fn separators(language: &Language) -> (char, char) {
+    match language.primary_subtag() {
+        "da" | "de" | "el" | "es" | "id" | "it" | "nl" | "pt" | "tr" | "vi" => (',', '.'),
+        "be" | "cs" | "fi" | "fr" | "hu" | "nb" | "pl" | "ru" | "sk" | "sv" | "uk" => {
+            (',', '\u{a0}')
+        }
+        _ => ('.', ','),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_numbers_with_language_separators() {
+        let german = Language::from_code("de").unwrap();
+        let french = Language::from_code("fr").unwrap();
+
+        assert_eq!(format_number_for(&Language::ENGLISH, 0., 0), "0");
+        assert_eq!(format_number_for(&Language::ENGLISH, 999., 0), "999");
+        assert_eq!(
+            format_number_for(&Language::ENGLISH, 1234567.891, 2),
+            "1,234,567.89"
+        );
+        assert_eq!(format_number_for(&Language::ENGLISH, -1234., 1), "-1,234.0");
+        assert_eq!(format_number_for(&Language::ENGLISH, -0.01, 1), "0.0");
+        assert_eq!(
+            format_number_for(&Language::SIMPLIFIED_CHINESE, 12345.6, 1),
+            "12,345.6"
+        );
+        assert_eq!(format_number_for(&german, 12345.6, 1), "12.345,6");
+        assert_eq!(format_number_for(&french, 12345.6, 1), "12\u{a0}345,6");
+    }
+
+    #[test]
+    fn formats_byte_sizes() {
+        let german = Language::from_code("de").unwrap();
+
+        assert_eq!(format_bytes_for(&Language::ENGLISH, 0), "0 B");
+        assert_eq!(format_bytes_for(&Language::ENGLISH, 1_023), "1,023 B");
+        assert_eq!(format_bytes_for(&Language::ENGLISH, 1_024), "1 KiB");
+        assert_eq!(format_bytes_for(&Language::ENGLISH, 1_536), "1.5 KiB");
+        assert_eq!(format_bytes_for(&Language::ENGLISH, 1_048_575), "1 MiB");
+        assert_eq!(
+            format_bytes_for(&Language::ENGLISH, 17_179_869_184),
+            "16 GiB"
+        );
+        assert_eq!(format_bytes_for(&german, 1_536), "1,5 KiB");
+    }
+}
@@ -0,0 +1,93 @@
+//! Pseudo-localization, used to spot interface text that bypasses translation.
+//!
+//! When the interface language is [`Language::PSEUDO`](crate::Language::PSEUDO), every
+//! string that goes through this crate is rendered with accented letters, wrapped in
+//! brackets and padded to simulate the expansion of longer languages. Plain ASCII text
+//! in the interface was therefore never translated, and a missing closing bracket means
+//! the text gets truncated.
+
+/// Accents, brackets and pads `text`, e.g. `Save` becomes `[Šåṽé~~]`.
+pub fn pseudolocalize(text: &str) -> String {
+    wrap(&accent(text))
+}
+
+/// Replaces ASCII letters with accented look-alikes, keeping the text readable.
+pub(crate) fn accent(text: &str) -> String {
+    text.chars().map(accented).collect()
+}
+
+/// Brackets `text` and pads it by roughly a third of its length.
+pub(crate) fn wrap(text: &str) -> String {
+    let padding = text.chars().count().div_ceil(3);
+    format!("[{text}{}]", "~".repeat(padding))
+}
+
+fn accented(ch: char) -> char {
+    match ch {
+        'a' => 'å',
+        'b' => 'ƀ',
+        'c' => 'ç',
+        'd' => 'ð',
+        'e' => 'é',
+        'f' => 'ƒ',
+        'g' => 'ĝ',
+        'h' => 'ĥ',
+        'i' => 'î',
+        'j' => 'ĵ',
+        'k' => 'ķ',
+        'l' => 'ļ',
+        'm' => 'ɱ',
+        'n' => 'ñ',
+        'o' => 'ö',
+        'p' => 'þ',
+        'q' => 'ǫ',
+        'r' => 'ŕ',
+        's' => 'š',
+        't' => 'ţ',
+        'u' => 'û',
+        'v' => 'ṽ',
+        'w' => 'ŵ',
+        'x' => 'ẋ',
+        'y' => 'ý',
+        'z' => 'ž',
+        'A' => 'Å',
+        'B' => 'Ɓ',
+        'C' => 'Ç',
+        'D' => 'Ð',
+        'E' => 'É',
+        'F' => 'Ƒ',
+        'G' => 'Ĝ',
+        'H' => 'Ĥ',
+        'I' => 'Î',
+        'J' => 'Ĵ',
+        'K' => 'Ķ',
+        'L' => 'Ļ',
+        'M' => 'Ṁ',
+        'N' => 'Ñ',
+        'O' => 'Ö',
+        'P' => 'Þ',
+        'Q' => 'Ǫ',
+        'R' => 'Ŕ',
+        'S' => 'Š',
+        'T' => 'Ţ',
+        'U' => 'Û',
+        'V' => 'Ṽ',
+        'W' => 'Ŵ',
+        'X' => 'Ẋ',
+        'Y' => 'Ý',
+        'Z' => 'Ž',
+        _ => ch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accents_and_pads_text() {
+        assert_eq!(pseudolocalize("Save"), "[Šåṽé~~]");
+        assert_eq!(pseudolocalize("Save 2 files…"), "[Šåṽé 2 ƒîļéš…~~~~~]");
+        assert_eq!(pseudolocalize(""), "[]");
+    }
+}
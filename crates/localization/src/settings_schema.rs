@@ -0,0 +1,256 @@
+//! Translations of settings documentation, shown when hovering keys in `settings.json`,
+//! and of the titles of the editor's settings controls.
+//!
+//! The settings JSON schema is generated from doc comments, so its descriptions are
+//! translated after generation under keys derived from the schema itself:
+//!
+//! - `settings_schema.<property>` for top-level settings, e.g. `settings_schema.ui_language`.
+//! - `settings_schema.<definition>.<property>` for the fields of other types, with the
+//!   definition name in snake case, e.g. `settings_schema.git_settings.inline_blame`.
+//! - `settings_schema.<definition>.<variant>` for the variants of enums, e.g.
+//!   `settings_schema.cursor_shape.block`.
+//!
+//! The titles of settings controls are translated under `setting.<path>` keys, where the
+//! path is the setting's location in the settings file, e.g.
+//! `setting.git.inline_blame.enabled`.
+//!
+//! As with actions, the English text comes from the settings types rather than a call
+//! site, so settings without a translation are not reported as missing.
+
+use crate::{Language, current_language, lookup, pseudolocalize};
+use gpui::SharedString;
+use heck::ToSnakeCase as _;
+use serde_json::Value;
+
+const KEY_PREFIX: &str = "settings_schema";
+
+/// Definitions whose properties are top-level settings. `SettingsContent` is flattened
+/// into the root of the schema and also used for release channel, platform and profile
+/// overrides.
+const ROOT_DEFINITIONS: &[&str] = &["UserSettingsContent", "SettingsContent"];
+
+/// Replaces the descriptions in the settings JSON `schema` with their translations in
+/// the interface language.
+pub fn localize_settings_schema(schema: &mut Value) {
+    localize_settings_schema_for(&current_language(), schema);
+}
+
+fn localize_settings_schema_for(language: &Language, schema: &mut Value) {
+    if *language == Language::ENGLISH {
+        return;
+    }
+
+    localize_schema(language, schema, KEY_PREFIX);
+    for definitions_key in ["$defs", "definitions"] {
+        let Some(Value::Object(definitions)) = schema.get_mut(definitions_key) else {
+            continue;
+        };
+        for (name, definition) in definitions.iter_mut() {
+            let key = if ROOT_DEFINITIONS.contains(&name.as_str()) {
+                KEY_PREFIX.to_string()
+            } else {
+                format!("{KEY_PREFIX}.{}", name.to_snake_case())
+            };
+            localize_schema(language, definition, &key);
+        }
+    }
+}
+
+/// Returns the title of the control for the setting at `path`.
+pub fn setting_title(path: &[impl AsRef<str>], title: &'static str) -> SharedString {
+    translate_setting(&setting_key(path), title).unwrap_or_else(|| SharedString::new_static(title))
+}
+
+fn setting_key(path: &[impl AsRef<str>]) -> String {
+    let mut key = String::from("setting");
+    for component in path {
+        key.push('.');
+        key.push_str(component.as_ref());
+    }
+    key
+}
+
+fn translate_setting(key: &str, english: &str) -> Option<SharedString> {
+    let language = current_language();
+    if language == Language::PSEUDO {
+        Some(pseudolocalize(english).into())
+    } else {
+        lookup(&language, key)
+    }
+}
+
+fn localize_schema(language: &Language, schema: &mut Value, key: &str) {
+    if let Some(Value::String(description)) = schema.get_mut("description")
+        && let Some(translation) = translate_description(language, key, description)
+    {
+        *description = translation;
+    }
+    localize_children(language, schema, key);
+}
+
+/// Localizes the schemas nested in the one documented by `key`.
+fn localize_children(language: &Language, schema: &mut Value, key: &str) {
+    let Value::Object(schema) = schema else {
+        return;
+    };
+
+    if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+        for (name, property) in properties.iter_mut() {
+            localize_schema(language, property, &format!("{key}.{name}"));
+        }
+    }
+
+    for variants_key in ["oneOf", "anyOf"] {
+        let Some(Value::Array(variants)) = schema.get_mut(variants_key) else {
+            continue;
+        };
+        for variant in variants {
+            match variant.get("const").and_then(Value::as_str) {
+                Some(name) => {
+                    let variant_key = format!("{key}.{name}");
+                    localize_schema(language, variant, &variant_key);
+                }
+                // Variants without a value of their own, such as the `null` of optional
+                // settings, describe the same setting as their parent.
+                None => localize_children(language, variant, key),
+            }
+        }
+    }
+
+    if let Some(Value::Array(values)) = schema.get("enum") {
+        let descriptions = values
+            .iter()
+            .map(|value| lookup(language, &format!("{key}.{}", value.as_str()?)))
+            .collect::<Vec<_>>();
+        if descriptions.iter().any(Option::is_some) {
+            let descriptions = descriptions
+                .into_iter()
+                .map(|description| description.unwrap_or_default().to_string())
+                .collect();
+            schema.insert("enumDescriptions".into(), descriptions);
+        }
+    }
+
+    if let Some(items) = schema.get_mut("items") {
+        localize_children(language, items, key);
+    }
+}
+
+fn translate_description(language: &Language, key: &str, english: &str) -> Option<String> {
+    if *language == Language::PSEUDO {
+        Some(pseudolocalize(english))
+    } else {
+        lookup(language, key).map(|translation| translation.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn localizes_settings_schema_descriptions() {
+        let mut schema = json!({
+            "title": "UserSettingsContent",
+            "type": "object",
+            "properties": {
+                "ui_language": {
+                    "description": "Preferred language for Zed's user interface.",
+                    "anyOf": [{ "$ref": "#/$defs/UiLanguagePreference" }, { "type": "null" }]
+                },
+                "unknown_setting": { "description": "Not translated." },
+            },
+            "$defs": {
+                "SettingsContent": {
+                    "type": "object",
+                    "properties": {
+                        "cursor_shape": {
+                            "description": "Cursor shape for the default editor.",
+                            "anyOf": [{ "$ref": "#/$defs/CursorShape" }, { "type": "null" }]
+                        }
+                    }
+                },
+                "CursorShape": {
+                    "description": "The shape of a selection cursor.",
+                    "oneOf": [
+                        { "type": "string", "const": "bar", "description": "A vertical bar" },
+                        { "type": "string", "const": "custom", "description": "Not translated." }
+                    ]
+                },
+                "GutterContent": {
+                    "type": "object",
+                    "properties": {
+                        "line_numbers": {
+                            "description": "Whether to show line numbers in the gutter.",
+                            "type": ["boolean", "null"]
+                        }
+                    }
+                },
+                "InlineBlameSettings": {
+                    "type": "object",
+                    "properties": {
+                        "enabled": {
+                            "description": "Whether or not to show git blame data inline.",
+                            "type": ["boolean", "null"]
+                        }
+                    }
+                }
+            }
+        });
+
+        let mut english = schema.clone();
+        localize_settings_schema_for(&Language::ENGLISH, &mut english);
+        assert_eq!(english, schema);
+
+        localize_settings_schema_for(&Language::SIMPLIFIED_CHINESE, &mut schema);
+        assert_eq!(
+            schema["properties"]["ui_language"]["description"],
+            "Zed 用户界面的首选语言。"
+        );
+        assert_eq!(
+            schema["properties"]["unknown_setting"]["description"],
+            "Not translated."
+        );
+        assert_eq!(
+            schema["$defs"]["SettingsContent"]["properties"]["cursor_shape"]["description"],
+            "默认编辑器的光标形状。\n可以是 \"bar\"、\"block\"、\"underline\" 或 \"hollow\"。\n\n默认值：bar"
+        );
+        assert_eq!(
+            schema["$defs"]["CursorShape"]["oneOf"][0]["description"],
+            "竖线"
+        );
+        assert_eq!(
+            schema["$defs"]["CursorShape"]["oneOf"][1]["description"],
+            "Not translated."
+        );
+        assert_eq!(
+            schema["$defs"]["GutterContent"]["properties"]["line_numbers"]["description"],
+            "是否在行号栏中显示行号。\n\n默认值：true"
+        );
+        assert_eq!(
+            schema["$defs"]["InlineBlameSettings"]["properties"]["enabled"]["description"],
+            "是否在当前聚焦的行内显示 Git Blame 信息。\n\n默认值：true"
+        );
+
+        let mut plain_enum = json!({
+            "$defs": {
+                "CursorShape": { "type": "string", "enum": ["bar", "custom"] },
+                "Other": { "type": "string", "enum": ["custom"] },
+            }
+        });
+        localize_settings_schema_for(&Language::SIMPLIFIED_CHINESE, &mut plain_enum);
+        assert_eq!(
+            plain_enum["$defs"]["CursorShape"]["enumDescriptions"],
+            json!(["竖线", ""])
+        );
+        assert_eq!(plain_enum["$defs"]["Other"].get("enumDescriptions"), None);
+
+        let mut pseudo = json!({ "properties": { "a": { "description": "Setting" } } });
+        localize_settings_schema_for(&Language::PSEUDO, &mut pseudo);
+        assert_eq!(
+            pseudo["properties"]["a"]["description"],
+            pseudolocalize("Setting")
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use extension::{ExtensionHostProxy, ExtensionTranslationProxy};
+use fs::Fs;
+use gpui::{App, BackgroundExecutor, Task};
+use localization::{Catalog, Language};
+
+pub fn init(extension_host_proxy: Arc<ExtensionHostProxy>, executor: BackgroundExecutor) {
+    extension_host_proxy.register_translation_proxy(TranslationRegistryProxy { executor });
+}
+
+struct TranslationRegistryProxy {
+    executor: BackgroundExecutor,
+}
+
+impl ExtensionTranslationProxy for TranslationRegistryProxy {
+    fn load_translations(
+        &self,
+        extension_id: Arc<str>,
+        language: Arc<str>,
+        catalog_path: PathBuf,
+        fs: Arc<dyn Fs>,
+    ) -> Task<Result<()>> {
+        self.executor.spawn(async move {
+            let language = Language::from_code(&language).with_context(|| {
+                format!("extension {extension_id} has an invalid language tag {language:?}")
+            })?;
+            let content = fs.load(&catalog_path).await?;
+            let catalog = Catalog::parse(&content)
+                .with_context(|| format!("parsing translation catalog {catalog_path:?}"))?;
+            localization::add_extension_catalog(extension_id, language, catalog);
+            Ok(())
+        })
+    }
+
+    fn remove_translations(&self, extension_ids: &[Arc<str>]) {
+        localization::remove_extension_catalogs(extension_ids);
+    }
+
+    fn reload_translations(&self, cx: &mut App) {
+        localization::reload_catalogs(cx);
+    }
+}
@@ -1136,6 +1136,11 @@ impl LanguageServer {
         &self.process_name
     }
 
+    /// Get the OS process id of the language server, if it runs as a local child process.
+    pub fn process_id(&self) -> Option<u32> {
+        self.server.lock().as_ref().map(|server| server.id())
+    }
+
     /// Get the reported capabilities of the running language server.
     pub fn capabilities(&self) -> ServerCapabilities {
         self.capabilities.read().clone()
@@ -0,0 +1,326 @@
+//! The parser for the subset of ICU MessageFormat used by translated messages. It's
+//! shared by `localization`, which formats messages at runtime, and the `t!` macro,
+//! which checks default texts while compiling, so that both accept the same messages.
+//!
+//! Supported syntax:
+//!
+//! - `{name}` inserts the argument `name`.
+//! - `{count, plural, =0 {no files} one {# file} other {# files}}` picks a variant by
+//!   the language's plural rules. Exact `=N` matches take precedence, and `#` inside a
+//!   variant is replaced with the number.
+//! - `{gender, select, female {…} male {…} other {…}}` picks a variant by the
+//!   argument's string value.
+//! - `'{'` and `'}'` produce literal braces, and `''` produces a literal apostrophe.
+//!
+//! Every plural and select must have an `other` variant.
+
+use anyhow::{Context as _, Result, anyhow, bail};
+use std::collections::BTreeSet;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+/// CLDR plural categories.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        Some(match keyword {
+            "zero" => PluralCategory::Zero,
+            "one" => PluralCategory::One,
+            "two" => PluralCategory::Two,
+            "few" => PluralCategory::Few,
+            "many" => PluralCategory::Many,
+            "other" => PluralCategory::Other,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Selector {
+    Exact(f64),
+    Category(PluralCategory),
+    Keyword(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Part {
+    Text(String),
+    Argument(String),
+    /// The number of the enclosing plural.
+    Pound,
+    Plural {
+        argument: String,
+        variants: Vec<(Selector, Vec<Part>)>,
+    },
+    Select {
+        argument: String,
+        variants: Vec<(Selector, Vec<Part>)>,
+    },
+}
+
+/// Parses a message pattern into its parts.
+pub fn parse(pattern: &str) -> Result<Vec<Part>> {
+    let mut parser = Parser {
+        source: pattern,
+        chars: pattern.char_indices().peekable(),
+    };
+    let parts = parser.parse_parts(false)?;
+    if let Some((offset, _)) = parser.chars.peek() {
+        bail!("unexpected '}}' at offset {offset}");
+    }
+    Ok(parts)
+}
+
+/// Returns the names of the arguments referenced by `parts`, including those that
+/// plurals and selects choose by.
+pub fn argument_names(parts: &[Part]) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    collect_argument_names(parts, &mut names);
+    names
+}
+
+fn collect_argument_names(parts: &[Part], names: &mut BTreeSet<String>) {
+    for part in parts {
+        match part {
+            Part::Text(_) | Part::Pound => {}
+            Part::Argument(argument) => {
+                names.insert(argument.clone());
+            }
+            Part::Plural { argument, variants } | Part::Select { argument, variants } => {
+                names.insert(argument.clone());
+                for (_, variant) in variants {
+                    collect_argument_names(variant, names);
+                }
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl Parser<'_> {
+    /// Parses text and placeholders until the end of input, or until a closing brace
+    /// if `nested` is set. The closing brace is not consumed.
+    fn parse_parts(&mut self, nested: bool) -> Result<Vec<Part>> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        while let Some(&(offset, ch)) = self.chars.peek() {
+            match ch {
+                '{' => {
+                    self.chars.next();
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(self.parse_placeholder()?);
+                }
+                '}' => {
+                    if nested {
+                        break;
+                    }
+                    bail!("unexpected '}}' at offset {offset}");
+                }
+                '#' if nested => {
+                    self.chars.next();
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Pound);
+                }
+                '\'' => {
+                    self.chars.next();
+                    self.parse_quoted(&mut text);
+                }
+                _ => {
+                    self.chars.next();
+                    text.push(ch);
+                }
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        Ok(parts)
+    }
+
+    /// Handles the text following an apostrophe, which has already been consumed.
+    fn parse_quoted(&mut self, text: &mut String) {
+        match self.chars.peek() {
+            Some((_, '\'')) => {
+                self.chars.next();
+                text.push('\'');
+            }
+            Some((_, '{' | '}' | '#')) => {
+                while let Some((_, ch)) = self.chars.next() {
+                    if ch == '\'' {
+                        if let Some((_, '\'')) = self.chars.peek() {
+                            self.chars.next();
+                            text.push('\'');
+                            continue;
+                        }
+                        break;
+                    }
+                    text.push(ch);
+                }
+            }
+            _ => text.push('\''),
+        }
+    }
+
+    fn parse_placeholder(&mut self) -> Result<Part> {
+        let argument = self.parse_word()?;
+        match self.next_significant() {
+            Some('}') => return Ok(Part::Argument(argument)),
+            Some(',') => {}
+            other => bail!("expected ',' or '}}' after argument {argument:?}, found {other:?}"),
+        }
+
+        let kind = self.parse_word()?;
+        match self.next_significant() {
+            Some(',') => {}
+            other => bail!("expected ',' after {kind:?}, found {other:?}"),
+        }
+
+        let is_plural = match kind.as_str() {
+            "plural" => true,
+            "select" => false,
+            _ => bail!("unsupported placeholder type {kind:?}"),
+        };
+
+        let mut variants = Vec::new();
+        let mut has_other = false;
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some((_, '}')) => {
+                    self.chars.next();
+                    break;
+                }
+                None => bail!("unterminated {kind} for argument {argument:?}"),
+                _ => {}
+            }
+
+            let keyword = self.parse_word()?;
+            has_other |= keyword == "other";
+            let selector = if is_plural {
+                if let Some(exact) = keyword.strip_prefix('=') {
+                    Selector::Exact(
+                        exact
+                            .parse()
+                            .with_context(|| format!("invalid plural selector {keyword:?}"))?,
+                    )
+                } else {
+                    Selector::Category(
+                        PluralCategory::from_keyword(&keyword)
+                            .ok_or_else(|| anyhow!("unknown plural category {keyword:?}"))?,
+                    )
+                }
+            } else {
+                Selector::Keyword(keyword)
+            };
+
+            match self.next_significant() {
+                Some('{') => {}
+                other => bail!("expected '{{' to start a variant, found {other:?}"),
+            }
+            let parts = self.parse_parts(true)?;
+            match self.chars.next() {
+                Some((_, '}')) => {}
+                _ => bail!("unterminated variant in {kind} for argument {argument:?}"),
+            }
+            variants.push((selector, parts));
+        }
+
+        if !has_other {
+            bail!("{kind} {argument:?} has no 'other' variant");
+        }
+        Ok(if is_plural {
+            Part::Plural { argument, variants }
+        } else {
+            Part::Select { argument, variants }
+        })
+    }
+
+    fn parse_word(&mut self) -> Result<String> {
+        self.skip_whitespace();
+        let start = self.chars.peek().map_or(self.source.len(), |(ix, _)| *ix);
+        let mut end = start;
+        while let Some(&(offset, ch)) = self.chars.peek() {
+            if ch.is_alphanumeric() || matches!(ch, '_' | '-' | '=' | '.') {
+                self.chars.next();
+                end = offset + ch.len_utf8();
+            } else {
+                break;
+            }
+        }
+        if start == end {
+            bail!("expected an identifier at offset {start}");
+        }
+        Ok(self.source[start..end].to_string())
+    }
+
+    fn next_significant(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.next().map(|(_, ch)| ch)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, ch)| ch.is_whitespace()).is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(pattern: &str) -> BTreeSet<String> {
+        argument_names(&parse(pattern).unwrap())
+    }
+
+    #[test]
+    fn finds_argument_names() {
+        assert_eq!(
+            names("Show {count} warnings in {path}"),
+            BTreeSet::from(["count".to_string(), "path".to_string()])
+        );
+        assert_eq!(
+            names("{count, plural, =0 {No Changes} one {# Change} other {# {kind} Changes}}"),
+            BTreeSet::from(["count".to_string(), "kind".to_string()])
+        );
+        assert!(names("'{literal}' braces").is_empty());
+        assert!(names("isn't {quoted}").contains("quoted"));
+    }
+
+    #[test]
+    fn parses_quotes() {
+        assert_eq!(
+            parse("'{'{name}'}' isn''t #").unwrap(),
+            [
+                Part::Text("{".into()),
+                Part::Argument("name".into()),
+                Part::Text("} isn't #".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_patterns() {
+        assert!(parse("{count, plural, one {# file}").is_err());
+        assert!(parse("{count, plural, one {# file}}").is_err());
+        assert!(parse("{gender, select, female {She}}").is_err());
+        assert!(parse("{count, ordinal, other {#}}").is_err());
+        assert!(parse("unbalanced }").is_err());
+        assert!(parse("{unterminated").is_err());
+    }
+}
@@ -4877,8 +4877,8 @@ impl Panel for OutlinePanel {
             .then_some(IconName::ListTree)
     }
 
-    fn icon_tooltip(&self, _window: &Window, _: &App) -> Option<&'static str> {
-        Some("Outline Panel")
+    fn icon_tooltip(&self, _window: &Window, _: &App) -> Option<SharedString> {
+        Some(localization::t!("outline_panel.tooltip", "Outline Panel"))
     }
 
     fn toggle_action(&self) -> Box<dyn Action> {
@@ -0,0 +1,93 @@
+use std::collections::VecDeque;
+
+use gpui::{Hsla, PathBuilder, Pixels, canvas, point, px};
+use ui::prelude::*;
+
+/// Number of samples kept per series; five minutes at the default refresh interval.
+pub(crate) const HISTORY_LEN: usize = 150;
+
+/// A fixed-capacity series of samples, dropping the oldest value once full.
+#[derive(Clone, Debug)]
+pub(crate) struct History<T> {
+    samples: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T: Copy> History<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = T> + ExactSizeIterator + '_ {
+        self.samples.iter().copied()
+    }
+
+    pub fn latest(&self) -> Option<T> {
+        self.samples.back().copied()
+    }
+}
+
+impl<T: Copy> Default for History<T> {
+    fn default() -> Self {
+        Self::new(HISTORY_LEN)
+    }
+}
+
+/// Draws `values` as a line scaled to `max`, right-aligned so the newest sample
+/// is always at the right edge regardless of how much history has accumulated.
+pub(crate) fn sparkline(values: Vec<f32>, max: f32, color: Hsla) -> impl IntoElement {
+    canvas(
+        |_, _, _| {},
+        move |bounds, _, window, _| {
+            if values.len() < 2 || max <= 0.0 {
+                return;
+            }
+
+            let step = bounds.size.width / (HISTORY_LEN - 1) as f32;
+            let origin_x = bounds.right() - step * (values.len() - 1) as f32;
+            let y_for = |value: f32| -> Pixels {
+                let ratio = (value / max).clamp(0.0, 1.0);
+                bounds.bottom() - (bounds.size.height - px(1.)) * ratio
+            };
+
+            let mut builder = PathBuilder::stroke(px(1.));
+            for (ix, value) in values.iter().enumerate() {
+                let position = point(origin_x + step * ix as f32, y_for(*value));
+                if ix == 0 {
+                    builder.move_to(position);
+                } else {
+                    builder.line_to(position);
+                }
+            }
+            if let Ok(path) = builder.build() {
+                window.paint_path(path, color);
+            }
+        },
+    )
+    .size_full()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_drops_oldest_samples() {
+        let mut history = History::new(3);
+        for value in 1..=5 {
+            history.push(value);
+        }
+        assert_eq!(history.iter().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(history.latest(), Some(5));
+    }
+}
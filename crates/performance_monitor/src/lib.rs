@@ -1,100 +1,89 @@
-use std::{fmt::Write, thread, time::Duration};
+mod history;
+mod metrics;
+mod panel;
+mod processes;
+mod report;
 
-use gpui::{AsyncApp, Context, Render, WeakEntity, Window};
-use human_bytes::human_bytes;
-use log::warn;
-use sysinfo::{ProcessesToUpdate, System};
+use std::time::Duration;
+
+use gpui::{Action, App, Context, Entity, Render, Subscription, Window, actions};
+use localization::{format_bytes, format_number};
+use schemars::JsonSchema;
+use serde::Deserialize;
 use ui::{ButtonLike, Color, Label, LabelSize, Tooltip, h_flex, prelude::*};
-use workspace::{StatusItemView, item::ItemHandle};
+use workspace::{StatusItemView, Workspace, item::ItemHandle};
+
+use crate::metrics::{MetricsSnapshot, MetricsStore};
+
+pub use panel::PerformancePanel;
 
 const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
 const WARN_MEMORY_RATIO: f32 = 0.70;
 const CRITICAL_MEMORY_RATIO: f32 = 0.85;
 const WARN_CPU_RATIO: f32 = 0.70;
 const CRITICAL_CPU_RATIO: f32 = 0.90;
+/// How far back the status bar looks for slow frames and main thread stalls.
+const JANK_WINDOW: Duration = Duration::from_secs(60);
+
+actions!(
+    performance_monitor,
+    [
+        /// Opens the performance panel with resource history and Zed's child processes.
+        OpenPerformancePanel
+    ]
+);
 
-#[derive(Clone, Debug, Default)]
-struct MetricsSnapshot {
-    process_rss: u64,
-    process_virtual: u64,
-    system_used_memory: u64,
-    system_total_memory: u64,
-    process_cpu_percent: f32,
+/// Opens a plain-text report of recent slow frames and main thread stalls,
+/// suitable for attaching to bug reports.
+#[derive(Clone, Default, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = performance_monitor)]
+#[serde(deny_unknown_fields)]
+pub struct OpenSlowFramesReport {
+    /// How many seconds of history to include. Defaults to 60.
+    #[serde(default)]
+    pub seconds: Option<u64>,
+}
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &OpenPerformancePanel, window, cx| {
+                panel::open(workspace, window, cx);
+            })
+            .register_action(|workspace, action: &OpenSlowFramesReport, window, cx| {
+                report::open(workspace, action, window, cx);
+            });
+    })
+    .detach();
 }
 
 pub struct PerformanceMonitor {
-    snapshot: Option<MetricsSnapshot>,
-    peak_rss: u64,
+    metrics: Entity<MetricsStore>,
+    _metrics_subscription: Subscription,
 }
 
 impl PerformanceMonitor {
     pub fn new(cx: &mut Context<Self>) -> Self {
-        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
-            let pid = match sysinfo::get_current_pid() {
-                Ok(pid) => pid,
-                Err(err) => {
-                    warn!("performance_monitor: failed to determine current pid: {err}");
-                    return;
-                }
-            };
-
-            let mut system = System::new_all();
-
-            loop {
-                system.refresh_memory();
-                system.refresh_processes(ProcessesToUpdate::All);
-                if let Some(process) = system.process(pid) {
-                    let cpu_percent = normalize_process_cpu(process.cpu_usage());
-                    let snapshot = MetricsSnapshot {
-                        process_rss: process.memory(),
-                        process_virtual: process.virtual_memory(),
-                        system_used_memory: system.used_memory(),
-                        system_total_memory: system.total_memory(),
-                        process_cpu_percent: cpu_percent,
-                    };
-
-                    if this
-                        .update(cx, |monitor, cx| {
-                            monitor.peak_rss = monitor.peak_rss.max(snapshot.process_rss);
-                            monitor.snapshot = Some(snapshot);
-                            cx.notify();
-                        })
-                        .is_err()
-                    {
-                        break;
-                    }
-                } else {
-                    warn!("performance_monitor: failed to locate process {pid:?}");
-                }
-
-                cx.background_executor().timer(REFRESH_INTERVAL).await;
-            }
-        })
-        .detach();
-
-        Self {
-            snapshot: None,
-            peak_rss: 0,
-        }
-    }
-}
-
-impl Default for PerformanceMonitor {
-    fn default() -> Self {
+        let metrics = MetricsStore::global(cx);
+        let metrics_subscription = cx.observe(&metrics, |_, _, cx| cx.notify());
         Self {
-            snapshot: None,
-            peak_rss: 0,
+            metrics,
+            _metrics_subscription: metrics_subscription,
         }
     }
 }
 
 impl Render for PerformanceMonitor {
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
-        let Some(snapshot) = self.snapshot.clone() else {
-            return Label::new("监控初始化中…")
-                .size(LabelSize::Small)
-                .color(Color::Muted)
-                .into_any_element();
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let metrics = self.metrics.read(cx);
+        let Some(snapshot) = metrics.snapshot().cloned() else {
+            return Label::new(localization::t!(
+                "performance_monitor.initializing",
+                "Initializing monitor…"
+            ))
+            .size(LabelSize::Small)
+            .color(Color::Muted)
+            .into_any_element();
         };
 
         let memory_fraction = fraction(snapshot.process_rss, snapshot.system_total_memory);
@@ -110,7 +99,27 @@ impl Render for PerformanceMonitor {
         );
         let cpu_label = format!("CPU {}", format_percent(snapshot.process_cpu_percent));
 
-        let tooltip_text = build_tooltip(&snapshot, self.peak_rss);
+        let children = metrics.children();
+        let children_rss = children.iter().map(|child| child.rss).sum();
+        let recent_slow_frames = gpui::recent_slow_frames(JANK_WINDOW).len();
+        let recent_stalls = gpui::recent_main_thread_stalls(JANK_WINDOW).len();
+        let tooltip_text = build_tooltip(
+            &snapshot,
+            metrics.peak_rss(),
+            children.len(),
+            children_rss,
+            recent_slow_frames,
+            recent_stalls,
+        );
+        let jank_label = (recent_slow_frames + recent_stalls > 0).then(|| {
+            Label::new(localization::t!(
+                "performance_monitor.jank",
+                "Jank {count}",
+                count = recent_slow_frames + recent_stalls,
+            ))
+            .size(LabelSize::Small)
+            .color(Color::Warning)
+        });
 
         ButtonLike::new("performance-monitor")
             .child(
@@ -125,9 +134,13 @@ impl Render for PerformanceMonitor {
                         Label::new(cpu_label)
                             .size(LabelSize::Small)
                             .color(cpu_color),
-                    ),
+                    )
+                    .children(jank_label),
             )
             .tooltip(Tooltip::text(tooltip_text))
+            .on_click(|_, window, cx| {
+                window.dispatch_action(Box::new(OpenPerformancePanel), cx);
+            })
             .into_any_element()
     }
 }
@@ -143,16 +156,6 @@ impl StatusItemView for PerformanceMonitor {
     }
 }
 
-fn normalize_process_cpu(raw_cpu_percent: f32) -> f32 {
-    let cpu_count = thread::available_parallelism()
-        .map(|n| n.get())
-        .unwrap_or(1) as f32;
-    if cpu_count == 0.0 {
-        return 0.0;
-    }
-    (raw_cpu_percent / cpu_count).clamp(0.0, 100.0)
-}
-
 fn fraction(numerator: u64, denominator: u64) -> f32 {
     if denominator == 0 {
         0.0
@@ -161,21 +164,13 @@ fn fraction(numerator: u64, denominator: u64) -> f32 {
     }
 }
 
-fn format_bytes(bytes: u64) -> String {
-    if bytes == 0 {
-        "0 B".to_string()
-    } else {
-        human_bytes(bytes as f64)
-    }
-}
-
-fn format_percent(value: f32) -> String {
+pub(crate) fn format_percent(value: f32) -> String {
     if value >= 99.5 {
         "100%".to_string()
     } else if value >= 10.0 {
-        format!("{value:.0}%")
+        format!("{}%", format_number(value.into(), 0))
     } else {
-        format!("{value:.1}%")
+        format!("{}%", format_number(value.into(), 1))
     }
 }
 
@@ -189,48 +184,81 @@ fn color_for_usage(fraction: f32, warn_threshold: f32, critical_threshold: f32)
     }
 }
 
-fn build_tooltip(snapshot: &MetricsSnapshot, peak_rss: u64) -> String {
-    let mut tooltip = String::new();
-    let _ = writeln!(
-        tooltip,
-        "进程物理内存: {}",
-        format_bytes(snapshot.process_rss)
-    );
+fn build_tooltip(
+    snapshot: &MetricsSnapshot,
+    peak_rss: u64,
+    child_count: usize,
+    children_rss: u64,
+    recent_slow_frames: usize,
+    recent_stalls: usize,
+) -> String {
+    let mut lines = vec![localization::t!(
+        "performance_monitor.process_memory",
+        "Process memory (RSS): {value}",
+        value = format_bytes(snapshot.process_rss),
+    )];
     if peak_rss > 0 {
-        let _ = writeln!(tooltip, "峰值物理内存: {}", format_bytes(peak_rss));
+        lines.push(localization::t!(
+            "performance_monitor.peak_memory",
+            "Peak memory (RSS): {value}",
+            value = format_bytes(peak_rss),
+        ));
     }
-    let _ = writeln!(
-        tooltip,
-        "进程虚拟内存: {}",
-        format_bytes(snapshot.process_virtual)
-    );
+    lines.push(localization::t!(
+        "performance_monitor.virtual_memory",
+        "Process virtual memory: {value}",
+        value = format_bytes(snapshot.process_virtual),
+    ));
     let memory_fraction = fraction(snapshot.process_rss, snapshot.system_total_memory);
-    let _ = writeln!(
-        tooltip,
-        "系统内存: {} / {}",
-        format_bytes(snapshot.system_used_memory),
-        format_bytes(snapshot.system_total_memory)
-    );
-    let _ = write!(
-        tooltip,
-        "CPU 占用: {} (阈值 {:.0}%/{:.0}%)",
-        format_percent(snapshot.process_cpu_percent),
-        WARN_CPU_RATIO * 100.0,
-        CRITICAL_CPU_RATIO * 100.0
-    );
-    let _ = write!(
-        tooltip,
-        "\nRAM 占用: {} (阈值 {:.0}%/{:.0}%)",
-        format_percent(memory_fraction * 100.0),
-        WARN_MEMORY_RATIO * 100.0,
-        CRITICAL_MEMORY_RATIO * 100.0
-    );
-    tooltip
+    lines.push(localization::t!(
+        "performance_monitor.system_memory",
+        "System memory: {used} / {total}",
+        used = format_bytes(snapshot.system_used_memory),
+        total = format_bytes(snapshot.system_total_memory),
+    ));
+    lines.push(localization::t!(
+        "performance_monitor.cpu_usage",
+        "CPU usage: {usage} (thresholds {warn}%/{critical}%)",
+        usage = format_percent(snapshot.process_cpu_percent),
+        warn = format!("{:.0}", WARN_CPU_RATIO * 100.0),
+        critical = format!("{:.0}", CRITICAL_CPU_RATIO * 100.0),
+    ));
+    lines.push(localization::t!(
+        "performance_monitor.memory_usage",
+        "RAM usage: {usage} (thresholds {warn}%/{critical}%)",
+        usage = format_percent(memory_fraction * 100.0),
+        warn = format!("{:.0}", WARN_MEMORY_RATIO * 100.0),
+        critical = format!("{:.0}", CRITICAL_MEMORY_RATIO * 100.0),
+    ));
+    if child_count > 0 {
+        lines.push(localization::t!(
+            "performance_monitor.child_processes",
+            "{count, plural, one {# child process} other {# child processes}}: {memory}",
+            count = child_count,
+            memory = format_bytes(children_rss),
+        ));
+    }
+    lines.push(localization::t!(
+        "performance_monitor.recent_slow_frames",
+        "Slow frames in the last minute: {count}",
+        count = recent_slow_frames,
+    ));
+    lines.push(localization::t!(
+        "performance_monitor.recent_stalls",
+        "Main thread stalls in the last minute: {count}",
+        count = recent_stalls,
+    ));
+    lines.push(localization::t!(
+        "performance_monitor.open_panel_hint",
+        "Click to show history and child processes"
+    ));
+    lines.join("\n")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::metrics::normalize_process_cpu;
 
     #[test]
     fn normalize_cpu_does_not_exceed_100() {
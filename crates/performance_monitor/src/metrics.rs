@@ -0,0 +1,297 @@
+use std::thread;
+
+use collections::{HashMap, HashSet};
+use gpui::{
+    App, AppContext as _, AsyncApp, Context, Entity, Global, ResponsivenessCounts, Task, WeakEntity,
+};
+use log::warn;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+
+use crate::REFRESH_INTERVAL;
+use crate::history::History;
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MetricsSnapshot {
+    pub process_rss: u64,
+    pub process_virtual: u64,
+    pub system_used_memory: u64,
+    pub system_total_memory: u64,
+    pub process_cpu_percent: f32,
+}
+
+/// A process spawned (directly or transitively) by Zed.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ChildProcess {
+    pub pid: u32,
+    pub parent_pid: u32,
+    pub name: String,
+    pub command: String,
+    pub rss: u64,
+    pub cpu_percent: f32,
+}
+
+struct Sample {
+    snapshot: MetricsSnapshot,
+    children: Vec<ChildProcess>,
+}
+
+struct GlobalMetricsStore(Entity<MetricsStore>);
+
+impl Global for GlobalMetricsStore {}
+
+/// Periodically samples Zed and its child processes, shared by every window.
+pub(crate) struct MetricsStore {
+    snapshot: Option<MetricsSnapshot>,
+    peak_rss: u64,
+    rss_history: History<u64>,
+    cpu_history: History<f32>,
+    children_rss_history: History<u64>,
+    children: Vec<ChildProcess>,
+    child_rss_history: HashMap<u32, History<u64>>,
+    responsiveness: ResponsivenessCounts,
+    slow_frame_history: History<u64>,
+    stall_history: History<u64>,
+    _sampling_task: Task<()>,
+}
+
+impl MetricsStore {
+    pub fn global(cx: &mut App) -> Entity<Self> {
+        if let Some(store) = cx.try_global::<GlobalMetricsStore>() {
+            return store.0.clone();
+        }
+        let store = cx.new(Self::new);
+        cx.set_global(GlobalMetricsStore(store.clone()));
+        store
+    }
+
+    fn new(cx: &mut Context<Self>) -> Self {
+        let root_pid = match sysinfo::get_current_pid() {
+            Ok(pid) => Some(pid),
+            Err(err) => {
+                warn!("performance_monitor: failed to determine current pid: {err}");
+                None
+            }
+        };
+
+        let sampling_task = match root_pid {
+            Some(pid) => cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+                let mut system = System::new();
+                loop {
+                    let (sample, returned_system) = cx
+                        .background_spawn(async move {
+                            let sample = take_sample(&mut system, pid);
+                            (sample, system)
+                        })
+                        .await;
+                    system = returned_system;
+
+                    match sample {
+                        Some(sample) => {
+                            if this
+                                .update(cx, |store, cx| store.push_sample(sample, cx))
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                        None => warn!("performance_monitor: failed to locate process {pid:?}"),
+                    }
+
+                    cx.background_executor().timer(REFRESH_INTERVAL).await;
+                }
+            }),
+            None => Task::ready(()),
+        };
+
+        Self {
+            snapshot: None,
+            peak_rss: 0,
+            rss_history: History::default(),
+            cpu_history: History::default(),
+            children_rss_history: History::default(),
+            children: Vec::new(),
+            child_rss_history: HashMap::default(),
+            responsiveness: gpui::responsiveness_counts(),
+            slow_frame_history: History::default(),
+            stall_history: History::default(),
+            _sampling_task: sampling_task,
+        }
+    }
+
+    fn push_sample(&mut self, sample: Sample, cx: &mut Context<Self>) {
+        let Sample { snapshot, children } = sample;
+
+        self.peak_rss = self.peak_rss.max(snapshot.process_rss);
+        self.rss_history.push(snapshot.process_rss);
+        self.cpu_history.push(snapshot.process_cpu_percent);
+        self.children_rss_history
+            .push(children.iter().map(|child| child.rss).sum());
+
+        let live_pids = children
+            .iter()
+            .map(|child| child.pid)
+            .collect::<HashSet<_>>();
+        self.child_rss_history
+            .retain(|pid, _| live_pids.contains(pid));
+        for child in &children {
+            self.child_rss_history
+                .entry(child.pid)
+                .or_default()
+                .push(child.rss);
+        }
+
+        let responsiveness = gpui::responsiveness_counts();
+        self.slow_frame_history
+            .push(responsiveness.slow_frames - self.responsiveness.slow_frames);
+        self.stall_history
+            .push(responsiveness.main_thread_stalls - self.responsiveness.main_thread_stalls);
+        self.responsiveness = responsiveness;
+
+        self.snapshot = Some(snapshot);
+        self.children = children;
+        cx.notify();
+    }
+
+    pub fn snapshot(&self) -> Option<&MetricsSnapshot> {
+        self.snapshot.as_ref()
+    }
+
+    pub fn peak_rss(&self) -> u64 {
+        self.peak_rss
+    }
+
+    pub fn rss_history(&self) -> &History<u64> {
+        &self.rss_history
+    }
+
+    pub fn cpu_history(&self) -> &History<f32> {
+        &self.cpu_history
+    }
+
+    pub fn children_rss_history(&self) -> &History<u64> {
+        &self.children_rss_history
+    }
+
+    /// Slow frames drawn during each sampling interval.
+    pub fn slow_frame_history(&self) -> &History<u64> {
+        &self.slow_frame_history
+    }
+
+    /// Main thread stalls during each sampling interval.
+    pub fn stall_history(&self) -> &History<u64> {
+        &self.stall_history
+    }
+
+    pub fn children(&self) -> &[ChildProcess] {
+        &self.children
+    }
+
+    pub fn child_rss_history(&self, pid: u32) -> Option<&History<u64>> {
+        self.child_rss_history.get(&pid)
+    }
+
+    /// Terminates a child process. Only descendants of Zed can be killed this way.
+    pub fn kill_process(&mut self, pid: u32, cx: &mut Context<Self>) {
+        if !self.children.iter().any(|child| child.pid == pid) {
+            warn!("performance_monitor: refusing to kill {pid}, which is not a child of Zed");
+            return;
+        }
+
+        cx.background_spawn(async move {
+            let pid = Pid::from_u32(pid);
+            let mut system = System::new();
+            system.refresh_processes(ProcessesToUpdate::Some(&[pid]));
+            match system.process(pid) {
+                Some(process) => {
+                    if !process.kill() {
+                        warn!("performance_monitor: failed to kill process {pid}");
+                    }
+                }
+                None => warn!("performance_monitor: process {pid} already exited"),
+            }
+        })
+        .detach();
+    }
+}
+
+fn take_sample(system: &mut System, pid: Pid) -> Option<Sample> {
+    system.refresh_memory();
+    system.refresh_processes(ProcessesToUpdate::All);
+
+    let process = system.process(pid)?;
+    let snapshot = MetricsSnapshot {
+        process_rss: process.memory(),
+        process_virtual: process.virtual_memory(),
+        system_used_memory: system.used_memory(),
+        system_total_memory: system.total_memory(),
+        process_cpu_percent: normalize_process_cpu(process.cpu_usage()),
+    };
+
+    Some(Sample {
+        snapshot,
+        children: child_processes(system, pid),
+    })
+}
+
+/// Collects every descendant of `root` in breadth-first order, skipping the
+/// thread entries that some platforms report alongside processes.
+fn child_processes(system: &System, root: Pid) -> Vec<ChildProcess> {
+    let mut threads = HashSet::default();
+    for process in system.processes().values() {
+        if let Some(tasks) = process.tasks() {
+            threads.extend(tasks.iter().filter(|task| **task != process.pid()).copied());
+        }
+    }
+
+    let mut children_by_parent = HashMap::<Pid, Vec<Pid>>::default();
+    for (pid, process) in system.processes() {
+        if threads.contains(pid) {
+            continue;
+        }
+        if let Some(parent) = process.parent() {
+            children_by_parent.entry(parent).or_default().push(*pid);
+        }
+    }
+
+    let mut result = Vec::new();
+    let mut queue = vec![root];
+    while !queue.is_empty() {
+        let mut next = Vec::new();
+        for parent in queue {
+            let Some(children) = children_by_parent.get(&parent) else {
+                continue;
+            };
+            for pid in children {
+                let Some(process) = system.process(*pid) else {
+                    continue;
+                };
+                result.push(ChildProcess {
+                    pid: pid.as_u32(),
+                    parent_pid: parent.as_u32(),
+                    name: process.name().to_string_lossy().into_owned(),
+                    command: process
+                        .cmd()
+                        .iter()
+                        .map(|arg| arg.to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    rss: process.memory(),
+                    cpu_percent: normalize_process_cpu(process.cpu_usage()),
+                });
+                next.push(*pid);
+            }
+        }
+        queue = next;
+    }
+    result
+}
+
+pub(crate) fn normalize_process_cpu(raw_cpu_percent: f32) -> f32 {
+    let cpu_count = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1) as f32;
+    if cpu_count == 0.0 {
+        return 0.0;
+    }
+    (raw_cpu_percent / cpu_count).clamp(0.0, 100.0)
+}
@@ -0,0 +1,476 @@
+use collections::HashSet;
+use gpui::{App, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription, Window};
+use localization::{format_bytes, t};
+use project::{LanguageServerSelector, Project};
+use ui::{Divider, IconButton, IconButtonShape, Tooltip, prelude::*};
+use workspace::{
+    Workspace, WorkspaceId,
+    item::{Item, ItemEvent},
+};
+
+use crate::history::sparkline;
+use crate::metrics::{ChildProcess, MetricsStore};
+use crate::processes::{ProcessGroup, ProcessOwner, group_processes, process_owners};
+use crate::{JANK_WINDOW, OpenSlowFramesReport, format_percent};
+
+/// A workspace item showing Zed's resource history and the processes it spawned.
+pub struct PerformancePanel {
+    project: Entity<Project>,
+    metrics: Entity<MetricsStore>,
+    focus_handle: FocusHandle,
+    _metrics_subscription: Subscription,
+}
+
+impl PerformancePanel {
+    pub fn new(workspace: &Workspace, cx: &mut Context<Self>) -> Self {
+        let metrics = MetricsStore::global(cx);
+        let metrics_subscription = cx.observe(&metrics, |_, _, cx| cx.notify());
+        Self {
+            project: workspace.project().clone(),
+            metrics,
+            focus_handle: cx.focus_handle(),
+            _metrics_subscription: metrics_subscription,
+        }
+    }
+
+    fn kill(&mut self, pid: u32, cx: &mut Context<Self>) {
+        self.metrics
+            .update(cx, |metrics, cx| metrics.kill_process(pid, cx));
+    }
+
+    fn restart(&mut self, owner: &ProcessOwner, window: &mut Window, cx: &mut Context<Self>) {
+        match owner {
+            ProcessOwner::LanguageServer { name } => {
+                self.project.update(cx, |project, cx| {
+                    let buffers = project.buffer_store().read(cx).buffers().collect();
+                    project.restart_language_servers_for_buffers(
+                        buffers,
+                        HashSet::from_iter([LanguageServerSelector::Name(name.clone())]),
+                        cx,
+                    );
+                });
+            }
+            ProcessOwner::Task { id, .. } => {
+                window.dispatch_action(
+                    Box::new(zed_actions::Rerun {
+                        task_id: Some(id.0.clone()),
+                        allow_concurrent_runs: Some(true),
+                        use_new_terminal: Some(false),
+                        reevaluate_context: false,
+                    }),
+                    cx,
+                );
+            }
+            ProcessOwner::DebugAdapter { session_id, .. } => {
+                let session = self
+                    .project
+                    .read(cx)
+                    .dap_store()
+                    .read(cx)
+                    .session_by_id(*session_id);
+                if let Some(session) = session {
+                    session.update(cx, |session, cx| session.restart(None, cx));
+                }
+            }
+            ProcessOwner::Terminal { .. } => {}
+        }
+    }
+
+    fn render_summary(&self, cx: &App) -> impl IntoElement {
+        let metrics = self.metrics.read(cx);
+        let Some(snapshot) = metrics.snapshot() else {
+            return h_flex()
+                .child(
+                    Label::new(t!(
+                        "performance_monitor.initializing",
+                        "Initializing monitor…"
+                    ))
+                    .color(Color::Muted),
+                )
+                .into_any_element();
+        };
+
+        let children_rss = metrics.children_rss_history().latest().unwrap_or(0);
+        let rss_values = metrics
+            .rss_history()
+            .iter()
+            .map(|rss| rss as f32)
+            .collect::<Vec<_>>();
+        let children_values = metrics
+            .children_rss_history()
+            .iter()
+            .map(|rss| rss as f32)
+            .collect::<Vec<_>>();
+        let memory_max = rss_values
+            .iter()
+            .chain(&children_values)
+            .copied()
+            .fold(0.0, f32::max);
+        let cpu_values = metrics.cpu_history().iter().collect::<Vec<_>>();
+
+        let memory_color = cx.theme().status().info;
+        let cpu_color = cx.theme().status().warning;
+
+        h_flex()
+            .gap_4()
+            .child(render_chart(
+                t!("performance_monitor.panel.zed_memory", "Zed memory (RSS)"),
+                t!(
+                    "performance_monitor.panel.current_and_peak",
+                    "{current} (peak {peak})",
+                    current = format_bytes(snapshot.process_rss),
+                    peak = format_bytes(metrics.peak_rss()),
+                ),
+                sparkline(rss_values, memory_max, memory_color),
+                cx,
+            ))
+            .child(render_chart(
+                t!("performance_monitor.panel.zed_cpu", "Zed CPU"),
+                format_percent(snapshot.process_cpu_percent).into(),
+                sparkline(cpu_values, 100.0, cpu_color),
+                cx,
+            ))
+            .child(render_chart(
+                t!(
+                    "performance_monitor.panel.children_memory",
+                    "Child processes memory (RSS)"
+                ),
+                format_bytes(children_rss).into(),
+                sparkline(children_values, memory_max, memory_color),
+                cx,
+            ))
+            .into_any_element()
+    }
+
+    fn render_responsiveness(&self, cx: &App) -> impl IntoElement {
+        let metrics = self.metrics.read(cx);
+        let slow_frame_values = metrics
+            .slow_frame_history()
+            .iter()
+            .map(|count| count as f32)
+            .collect::<Vec<_>>();
+        let stall_values = metrics
+            .stall_history()
+            .iter()
+            .map(|count| count as f32)
+            .collect::<Vec<_>>();
+        let max = slow_frame_values
+            .iter()
+            .chain(&stall_values)
+            .copied()
+            .fold(1.0, f32::max);
+        let jank_color = cx.theme().status().warning;
+
+        h_flex()
+            .gap_4()
+            .child(render_chart(
+                t!("performance_monitor.panel.slow_frames", "Slow frames"),
+                t!(
+                    "performance_monitor.panel.last_minute",
+                    "{count} in the last minute",
+                    count = gpui::recent_slow_frames(JANK_WINDOW).len(),
+                ),
+                sparkline(slow_frame_values, max, jank_color),
+                cx,
+            ))
+            .child(render_chart(
+                t!(
+                    "performance_monitor.panel.main_thread_stalls",
+                    "Main thread stalls"
+                ),
+                t!(
+                    "performance_monitor.panel.last_minute",
+                    "{count} in the last minute",
+                    count = gpui::recent_main_thread_stalls(JANK_WINDOW).len(),
+                ),
+                sparkline(stall_values, max, jank_color),
+                cx,
+            ))
+            .child(
+                Button::new(
+                    "open-slow-frames-report",
+                    t!(
+                        "performance_monitor.panel.open_report",
+                        "Open Slow Frames Report"
+                    ),
+                )
+                .icon(IconName::FileDoc)
+                .icon_position(IconPosition::Start)
+                .icon_size(IconSize::Small)
+                .on_click(|_, window, cx| {
+                    window.dispatch_action(Box::new(OpenSlowFramesReport::default()), cx)
+                }),
+            )
+    }
+
+    fn render_group(
+        &self,
+        group_ix: usize,
+        group: ProcessGroup,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let restart_button = group
+            .owner
+            .clone()
+            .filter(|owner| !matches!(owner, ProcessOwner::Terminal { .. }))
+            .map(|owner| {
+                IconButton::new(("restart-owner", group_ix), IconName::RotateCw)
+                    .shape(IconButtonShape::Square)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text(t!(
+                        "performance_monitor.panel.restart",
+                        "Restart"
+                    )))
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.restart(&owner, window, cx);
+                    }))
+            });
+
+        let header = h_flex()
+            .gap_2()
+            .py_1()
+            .child(
+                Icon::new(owner_icon(group.owner.as_ref()))
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(Label::new(owner_label(group.owner.as_ref())))
+            .child(
+                Label::new(format!(
+                    "{} · {}",
+                    format_bytes(group.total_rss),
+                    format_percent(group.total_cpu_percent)
+                ))
+                .size(LabelSize::Small)
+                .color(Color::Muted),
+            )
+            .child(div().flex_1())
+            .children(restart_button);
+
+        v_flex().child(header).children(
+            group
+                .rows
+                .into_iter()
+                .map(|row| self.render_process(&row.process, row.depth, cx)),
+        )
+    }
+
+    fn render_process(
+        &self,
+        process: &ChildProcess,
+        depth: usize,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let pid = process.pid;
+        let history = self
+            .metrics
+            .read(cx)
+            .child_rss_history(pid)
+            .map(|history| history.iter().map(|rss| rss as f32).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let max = history.iter().copied().fold(0.0, f32::max);
+        let color = cx.theme().status().info;
+        let hover_background = cx.theme().colors().element_hover;
+
+        h_flex()
+            .id(("process", pid as usize))
+            .gap_2()
+            .pl(px(16. * (depth + 1) as f32))
+            .hover(|style| style.bg(hover_background))
+            .child(
+                div().w(px(200.)).overflow_hidden().child(
+                    Label::new(process.name.clone())
+                        .size(LabelSize::Small)
+                        .truncate(),
+                ),
+            )
+            .child(
+                div().w(px(64.)).child(
+                    Label::new(pid.to_string())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+            )
+            .child(
+                div()
+                    .w(px(80.))
+                    .child(Label::new(format_bytes(process.rss)).size(LabelSize::Small)),
+            )
+            .child(
+                div()
+                    .w(px(56.))
+                    .child(Label::new(format_percent(process.cpu_percent)).size(LabelSize::Small)),
+            )
+            .child(
+                div()
+                    .w(px(120.))
+                    .h(px(16.))
+                    .child(sparkline(history, max, color)),
+            )
+            .child(div().flex_1())
+            .child(
+                IconButton::new(("kill-process", pid as usize), IconName::Close)
+                    .shape(IconButtonShape::Square)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text(t!("performance_monitor.panel.kill", "Kill")))
+                    .on_click(cx.listener(move |this, _, _, cx| this.kill(pid, cx))),
+            )
+            .tooltip(Tooltip::text(process.command.clone()))
+    }
+}
+
+fn render_chart(
+    title: SharedString,
+    value: SharedString,
+    chart: impl IntoElement,
+    cx: &App,
+) -> impl IntoElement {
+    v_flex()
+        .gap_1()
+        .p_2()
+        .w(px(260.))
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().colors().border_variant)
+        .child(
+            h_flex()
+                .justify_between()
+                .child(Label::new(title).size(LabelSize::Small).color(Color::Muted))
+                .child(Label::new(value).size(LabelSize::Small)),
+        )
+        .child(div().h(px(40.)).w_full().child(chart))
+}
+
+fn owner_icon(owner: Option<&ProcessOwner>) -> IconName {
+    match owner {
+        Some(ProcessOwner::LanguageServer { .. }) => IconName::Server,
+        Some(ProcessOwner::Task { .. }) => IconName::PlayFilled,
+        Some(ProcessOwner::Terminal { .. }) => IconName::Terminal,
+        Some(ProcessOwner::DebugAdapter { .. }) => IconName::Debug,
+        None => IconName::Ellipsis,
+    }
+}
+
+fn owner_label(owner: Option<&ProcessOwner>) -> SharedString {
+    match owner {
+        Some(ProcessOwner::LanguageServer { name }) => t!(
+            "performance_monitor.owner.language_server",
+            "Language server: {name}",
+            name = &name.0,
+        ),
+        Some(ProcessOwner::Task { label, .. }) => t!(
+            "performance_monitor.owner.task",
+            "Task: {label}",
+            label = label,
+        ),
+        Some(ProcessOwner::Terminal { title, .. }) => t!(
+            "performance_monitor.owner.terminal",
+            "Terminal: {title}",
+            title = title,
+        ),
+        Some(ProcessOwner::DebugAdapter { label, .. }) => t!(
+            "performance_monitor.owner.debug_adapter",
+            "Debug adapter: {label}",
+            label = label,
+        ),
+        None => t!("performance_monitor.owner.other", "Other processes"),
+    }
+}
+
+impl Render for PerformancePanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let owners = process_owners(&self.project, cx);
+        let groups = group_processes(self.metrics.read(cx).children(), &owners);
+
+        let processes = if groups.is_empty() {
+            Label::new(t!(
+                "performance_monitor.panel.no_child_processes",
+                "Zed has no running child processes."
+            ))
+            .color(Color::Muted)
+            .into_any_element()
+        } else {
+            v_flex()
+                .gap_2()
+                .children(
+                    groups
+                        .into_iter()
+                        .enumerate()
+                        .map(|(ix, group)| self.render_group(ix, group, cx)),
+                )
+                .into_any_element()
+        };
+
+        v_flex()
+            .id("performance-panel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_4()
+            .gap_4()
+            .overflow_y_scroll()
+            .bg(cx.theme().colors().editor_background)
+            .child(self.render_summary(cx))
+            .child(self.render_responsiveness(cx))
+            .child(Divider::horizontal())
+            .child(
+                Headline::new(t!(
+                    "performance_monitor.panel.child_processes",
+                    "Child processes"
+                ))
+                .size(HeadlineSize::Small),
+            )
+            .child(processes)
+    }
+}
+
+impl EventEmitter<ItemEvent> for PerformancePanel {}
+
+impl Focusable for PerformancePanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for PerformancePanel {
+    type Event = ItemEvent;
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        t!("performance_monitor.panel.title", "Performance")
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<WorkspaceId>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Option<Entity<Self>> {
+        None
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+pub(crate) fn open(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+    let existing = workspace
+        .active_pane()
+        .read(cx)
+        .items()
+        .find_map(|item| item.downcast::<PerformancePanel>());
+
+    if let Some(existing) = existing {
+        workspace.activate_item(&existing, true, true, window, cx);
+    } else {
+        let panel = cx.new(|cx| PerformancePanel::new(workspace, cx));
+        workspace.add_item_to_active_pane(Box::new(panel), None, true, window, cx);
+    }
+}
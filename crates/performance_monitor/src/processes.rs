@@ -0,0 +1,279 @@
+use collections::{HashMap, HashSet};
+use dap::client::SessionId;
+use gpui::{App, Entity, SharedString};
+use project::{LanguageServerName, Project};
+use task::TaskId;
+
+use crate::metrics::ChildProcess;
+
+/// The part of Zed that spawned a child process.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ProcessOwner {
+    LanguageServer {
+        name: LanguageServerName,
+    },
+    Task {
+        id: TaskId,
+        label: String,
+    },
+    Terminal {
+        title: String,
+    },
+    DebugAdapter {
+        session_id: SessionId,
+        label: SharedString,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ProcessRow {
+    pub process: ChildProcess,
+    pub depth: usize,
+}
+
+/// A process subtree attributed to one owner. Processes no owner claims are
+/// collected in a group without an owner.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ProcessGroup {
+    pub owner: Option<ProcessOwner>,
+    pub rows: Vec<ProcessRow>,
+    pub total_rss: u64,
+    pub total_cpu_percent: f32,
+}
+
+/// Returns the root process of every process spawned by the project's language
+/// servers, terminals, tasks and debug adapters.
+pub(crate) fn process_owners(project: &Entity<Project>, cx: &App) -> HashMap<u32, ProcessOwner> {
+    let mut owners = HashMap::default();
+    let project = project.read(cx);
+
+    let lsp_store = project.lsp_store().read(cx);
+    for (id, status) in lsp_store.language_server_statuses() {
+        if let Some(pid) = lsp_store
+            .language_server_for_id(id)
+            .and_then(|server| server.process_id())
+        {
+            owners.insert(
+                pid,
+                ProcessOwner::LanguageServer {
+                    name: status.name.clone(),
+                },
+            );
+        }
+    }
+
+    for terminal in project
+        .local_terminal_handles()
+        .iter()
+        .filter_map(|terminal| terminal.upgrade())
+    {
+        let pid = terminal.read(cx).pty_info.pid_getter().fallback_pid();
+        let owner = match terminal.read(cx).task() {
+            Some(task) => ProcessOwner::Task {
+                id: task.spawned_task.id.clone(),
+                label: task.spawned_task.label.clone(),
+            },
+            None => ProcessOwner::Terminal {
+                title: terminal.read(cx).title(true),
+            },
+        };
+        owners.insert(pid, owner);
+    }
+
+    for session in project.dap_store().read(cx).sessions() {
+        let session = session.read(cx);
+        if let Some(pid) = session
+            .adapter_client()
+            .and_then(|client| client.process_id())
+        {
+            owners.insert(
+                pid,
+                ProcessOwner::DebugAdapter {
+                    session_id: session.session_id(),
+                    label: session
+                        .label()
+                        .unwrap_or_else(|| session.adapter().0.clone()),
+                },
+            );
+        }
+    }
+
+    owners
+}
+
+/// Arranges `processes` into one subtree per owner, largest first, followed by
+/// the processes no owner claims.
+pub(crate) fn group_processes(
+    processes: &[ChildProcess],
+    owners: &HashMap<u32, ProcessOwner>,
+) -> Vec<ProcessGroup> {
+    let mut children_by_parent = HashMap::<u32, Vec<&ChildProcess>>::default();
+    for process in processes {
+        children_by_parent
+            .entry(process.parent_pid)
+            .or_default()
+            .push(process);
+    }
+    let known_pids = processes
+        .iter()
+        .map(|process| process.pid)
+        .collect::<HashSet<_>>();
+
+    let mut groups = Vec::new();
+    for process in processes {
+        if let Some(owner) = owners.get(&process.pid) {
+            let mut rows = Vec::new();
+            collect_subtree(process, 0, &children_by_parent, owners, &mut rows);
+            groups.push(new_group(Some(owner.clone()), rows));
+        }
+    }
+    groups.sort_by(|a, b| b.total_rss.cmp(&a.total_rss));
+
+    let mut unowned_rows = Vec::new();
+    for process in processes {
+        if !known_pids.contains(&process.parent_pid) && !owners.contains_key(&process.pid) {
+            collect_subtree(process, 0, &children_by_parent, owners, &mut unowned_rows);
+        }
+    }
+    if !unowned_rows.is_empty() {
+        groups.push(new_group(None, unowned_rows));
+    }
+
+    groups
+}
+
+fn collect_subtree(
+    process: &ChildProcess,
+    depth: usize,
+    children_by_parent: &HashMap<u32, Vec<&ChildProcess>>,
+    owners: &HashMap<u32, ProcessOwner>,
+    rows: &mut Vec<ProcessRow>,
+) {
+    rows.push(ProcessRow {
+        process: process.clone(),
+        depth,
+    });
+    for child in children_by_parent.get(&process.pid).into_iter().flatten() {
+        // Nested owners, such as a terminal started from a task, get their own group.
+        if !owners.contains_key(&child.pid) {
+            collect_subtree(child, depth + 1, children_by_parent, owners, rows);
+        }
+    }
+}
+
+fn new_group(owner: Option<ProcessOwner>, rows: Vec<ProcessRow>) -> ProcessGroup {
+    ProcessGroup {
+        owner,
+        total_rss: rows.iter().map(|row| row.process.rss).sum(),
+        total_cpu_percent: rows.iter().map(|row| row.process.cpu_percent).sum(),
+        rows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, parent_pid: u32, name: &str, rss: u64) -> ChildProcess {
+        ChildProcess {
+            pid,
+            parent_pid,
+            name: name.to_string(),
+            command: name.to_string(),
+            rss,
+            cpu_percent: 0.0,
+        }
+    }
+
+    #[test]
+    fn groups_processes_by_owner() {
+        let root = 1;
+        let processes = vec![
+            process(10, root, "node", 300),
+            process(20, root, "zsh", 10),
+            process(30, root, "git", 5),
+            process(11, 10, "tsserver", 8000),
+            process(21, 20, "cargo", 50),
+            process(22, 21, "rustc", 900),
+        ];
+        let owners = HashMap::from_iter([
+            (
+                10,
+                ProcessOwner::LanguageServer {
+                    name: LanguageServerName("vtsls".into()),
+                },
+            ),
+            (
+                20,
+                ProcessOwner::Task {
+                    id: TaskId("build".into()),
+                    label: "cargo build".into(),
+                },
+            ),
+            (
+                7,
+                ProcessOwner::Task {
+                    id: TaskId("exited".into()),
+                    label: "exited".into(),
+                },
+            ),
+        ]);
+
+        let groups = group_processes(&processes, &owners);
+        let summary = groups
+            .iter()
+            .map(|group| {
+                (
+                    group.owner.clone(),
+                    group.total_rss,
+                    group
+                        .rows
+                        .iter()
+                        .map(|row| (row.process.pid, row.depth))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summary,
+            vec![
+                (owners.get(&10).cloned(), 8300, vec![(10, 0), (11, 1)]),
+                (
+                    owners.get(&20).cloned(),
+                    960,
+                    vec![(20, 0), (21, 1), (22, 2)]
+                ),
+                (None, 5, vec![(30, 0)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_owners_get_their_own_group() {
+        let processes = vec![process(10, 1, "bash", 10), process(11, 10, "node", 500)];
+        let owners = HashMap::from_iter([
+            (
+                10,
+                ProcessOwner::Task {
+                    id: TaskId("outer".into()),
+                    label: "outer".into(),
+                },
+            ),
+            (
+                11,
+                ProcessOwner::Task {
+                    id: TaskId("inner".into()),
+                    label: "inner".into(),
+                },
+            ),
+        ]);
+
+        let groups = group_processes(&processes, &owners);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].owner, owners.get(&11).cloned());
+        assert_eq!(groups[0].total_rss, 500);
+        assert_eq!(groups[1].owner, owners.get(&10).cloned());
+        assert_eq!(groups[1].total_rss, 10);
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    fmt::Write as _,
+    time::{Duration, Instant},
+};
+
+use collections::HashMap;
+use editor::{Editor, MultiBuffer};
+use gpui::{
+    AppContext as _, Context, FrameTiming, MAIN_THREAD_STALL_THRESHOLD, MainThreadStall,
+    ResponsivenessCounts, SLOW_FRAME_THRESHOLD, Window,
+};
+use localization::t;
+use workspace::Workspace;
+
+use crate::OpenSlowFramesReport;
+
+const DEFAULT_REPORT_SECONDS: u64 = 60;
+
+pub(crate) fn open(
+    workspace: &mut Workspace,
+    action: &OpenSlowFramesReport,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let within = Duration::from_secs(action.seconds.unwrap_or(DEFAULT_REPORT_SECONDS));
+    let report = build_report(
+        within,
+        Instant::now(),
+        gpui::responsiveness_counts(),
+        &gpui::recent_slow_frames(within),
+        &gpui::recent_main_thread_stalls(within),
+    );
+
+    workspace
+        .with_local_workspace(window, cx, move |workspace, window, cx| {
+            let project = workspace.project().clone();
+            let buffer = project.update(cx, |project, cx| {
+                project.create_local_buffer(&report, None, false, cx)
+            });
+            let buffer = cx.new(|cx| {
+                MultiBuffer::singleton(buffer, cx).with_title(
+                    t!("performance_monitor.report.title", "Slow Frames Report").to_string(),
+                )
+            });
+            let editor = cx.new(|cx| Editor::for_multibuffer(buffer, Some(project), window, cx));
+            workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+        })
+        .detach_and_log_err(cx);
+}
+
+/// Renders the slow frames and main thread stalls from the last `within` as
+/// plain text, to be attached to bug reports. Not localized, since the
+/// readers are Zed's developers.
+fn build_report(
+    within: Duration,
+    now: Instant,
+    counts: ResponsivenessCounts,
+    slow_frames: &[FrameTiming],
+    stalls: &[MainThreadStall],
+) -> String {
+    let mut report = String::new();
+    writeln!(
+        report,
+        "Slow frames report for the last {} seconds",
+        within.as_secs()
+    )
+    .unwrap();
+    writeln!(
+        report,
+        "Platform: {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+    .unwrap();
+    writeln!(
+        report,
+        "Since launch: {} frames, {} slow (>= {}), {} main thread stalls (>= {})",
+        counts.frames,
+        counts.slow_frames,
+        format_duration(SLOW_FRAME_THRESHOLD),
+        counts.main_thread_stalls,
+        format_duration(MAIN_THREAD_STALL_THRESHOLD),
+    )
+    .unwrap();
+
+    writeln!(report, "\nSlow frames ({}):", slow_frames.len()).unwrap();
+    if slow_frames.is_empty() {
+        writeln!(report, "  none").unwrap();
+    }
+    for frame in slow_frames {
+        writeln!(
+            report,
+            "  {}  window {}  {} (draw {}, present {})",
+            format_age(now, frame.started_at),
+            frame.window_id.as_u64(),
+            format_duration(frame.duration()),
+            format_duration(frame.draw),
+            format_duration(frame.present),
+        )
+        .unwrap();
+    }
+
+    writeln!(report, "\nMain thread stalls ({}):", stalls.len()).unwrap();
+    if stalls.is_empty() {
+        writeln!(report, "  none").unwrap();
+    }
+    for stall in stalls {
+        writeln!(
+            report,
+            "  {}  {}  spawned at {}",
+            format_age(now, stall.started_at),
+            format_duration(stall.duration),
+            stall.spawned_at,
+        )
+        .unwrap();
+    }
+
+    if !stalls.is_empty() {
+        writeln!(report, "\nStalls by spawn location:").unwrap();
+        for (location, summary) in summarize_stalls(stalls) {
+            writeln!(
+                report,
+                "  {location}  {} stalls, {} total, {} max",
+                summary.count,
+                format_duration(summary.total),
+                format_duration(summary.max),
+            )
+            .unwrap();
+        }
+    }
+
+    report
+}
+
+#[derive(Default)]
+struct StallSummary {
+    count: usize,
+    total: Duration,
+    max: Duration,
+}
+
+/// Aggregates stalls by the location their task was spawned at, longest total first.
+fn summarize_stalls(stalls: &[MainThreadStall]) -> Vec<(String, StallSummary)> {
+    let mut summaries = HashMap::<String, StallSummary>::default();
+    for stall in stalls {
+        let summary = summaries.entry(stall.spawned_at.to_string()).or_default();
+        summary.count += 1;
+        summary.total += stall.duration;
+        summary.max = summary.max.max(stall.duration);
+    }
+    let mut summaries = summaries.into_iter().collect::<Vec<_>>();
+    summaries.sort_by(|(a_location, a), (b_location, b)| {
+        b.total
+            .cmp(&a.total)
+            .then_with(|| a_location.cmp(b_location))
+    });
+    summaries
+}
+
+fn format_age(now: Instant, at: Instant) -> String {
+    format!("-{:.3}s", now.saturating_duration_since(at).as_secs_f64())
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::Location;
+
+    #[test]
+    fn report_lists_frames_stalls_and_locations() {
+        let now = Instant::now();
+        let first_location = Location::caller();
+        let second_location = Location::caller();
+        let stall = |location, ago_ms, duration_ms| MainThreadStall {
+            spawned_at: location,
+            started_at: now - Duration::from_millis(ago_ms),
+            duration: Duration::from_millis(duration_ms),
+        };
+
+        let report = build_report(
+            Duration::from_secs(30),
+            now,
+            ResponsivenessCounts {
+                frames: 1000,
+                slow_frames: 3,
+                main_thread_stalls: 4,
+            },
+            &[FrameTiming {
+                window_id: gpui::WindowId::from(7),
+                started_at: now - Duration::from_millis(2500),
+                draw: Duration::from_millis(40),
+                present: Duration::from_millis(2),
+            }],
+            &[
+                stall(first_location, 3000, 60),
+                stall(second_location, 2000, 300),
+                stall(first_location, 1000, 90),
+            ],
+        );
+
+        assert!(report.starts_with("Slow frames report for the last 30 seconds\n"));
+        assert!(report.contains(
+            "Since launch: 1000 frames, 3 slow (>= 33.0 ms), 4 main thread stalls (>= 50.0 ms)"
+        ));
+        assert!(report.contains("  -2.500s  window 7  42.0 ms (draw 40.0 ms, present 2.0 ms)"));
+        assert!(report.contains(&format!(
+            "  -2.000s  300.0 ms  spawned at {second_location}"
+        )));
+
+        let summary = report
+            .split("Stalls by spawn location:\n")
+            .nth(1)
+            .unwrap()
+            .lines()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                format!("  {second_location}  1 stalls, 300.0 ms total, 300.0 ms max"),
+                format!("  {first_location}  2 stalls, 150.0 ms total, 90.0 ms max"),
+            ]
+        );
+    }
+
+    #[test]
+    fn report_without_events() {
+        let report = build_report(
+            Duration::from_secs(60),
+            Instant::now(),
+            ResponsivenessCounts::default(),
+            &[],
+            &[],
+        );
+        assert!(report.contains("Slow frames (0):\n  none\n"));
+        assert!(report.contains("Main thread stalls (0):\n  none\n"));
+        assert!(!report.contains("Stalls by spawn location"));
+    }
+}
@@ -13,10 +13,10 @@ use gpui::{
     App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Subscription, Task, WeakEntity,
 };
 use language::{
-    Buffer, BufferEvent, Capability, DiskState, File as _, Language, Operation,
+    Buffer, BufferEvent, Capability, DiskState, Encoding, File as _, Language, Operation,
     proto::{
-        deserialize_line_ending, deserialize_version, serialize_line_ending, serialize_version,
-        split_operations,
+        deserialize_encoding, deserialize_line_ending, deserialize_version, serialize_encoding,
+        serialize_line_ending, serialize_version, split_operations,
     },
 };
 use rpc::{
@@ -133,6 +133,7 @@ impl RemoteBufferStore {
         let buffer = buffer_handle.read(cx);
         let buffer_id = buffer.remote_id().into();
         let version = buffer.version();
+        let encoding = buffer.encoding();
         let rpc = self.upstream_client.clone();
         let project_id = self.project_id;
         cx.spawn(async move |_, cx| {
@@ -142,6 +143,7 @@ impl RemoteBufferStore {
                     buffer_id,
                     new_path,
                     version: serialize_version(&version),
+                    encoding: Some(serialize_encoding(encoding)),
                 })
                 .await?;
             let version = deserialize_version(&response.version);
@@ -345,6 +347,7 @@ impl RemoteBufferStore {
         &self,
         buffers: HashSet<Entity<Buffer>>,
         push_to_history: bool,
+        encoding: Option<Encoding>,
         cx: &mut Context<BufferStore>,
     ) -> Task<Result<ProjectTransaction>> {
         let request = self.upstream_client.request(proto::ReloadBuffers {
@@ -353,6 +356,7 @@ impl RemoteBufferStore {
                 .iter()
                 .map(|buffer| buffer.read(cx).remote_id().to_proto())
                 .collect(),
+            encoding: encoding.map(serialize_encoding),
         });
 
         cx.spawn(async move |this, cx| {
@@ -378,6 +382,7 @@ impl LocalBufferStore {
 
         let text = buffer.as_rope().clone();
         let line_ending = buffer.line_ending();
+        let encoding = buffer.encoding();
         let version = buffer.version();
         let buffer_id = buffer.remote_id();
         let file = buffer.file().cloned();
@@ -389,7 +394,7 @@ impl LocalBufferStore {
         }
 
         let save = worktree.update(cx, |worktree, cx| {
-            worktree.write_file(path.as_ref(), text, line_ending, cx)
+            worktree.write_file(path.as_ref(), text, line_ending, encoding, cx)
         });
 
         cx.spawn(async move |this, cx| {
@@ -630,6 +635,7 @@ impl LocalBufferStore {
                     .await;
                 cx.insert_entity(reservation, |_| {
                     Buffer::build(text_buffer, Some(loaded.file), Capability::ReadWrite)
+                        .with_encoding(loaded.encoding)
                 })
             })
         });
@@ -704,12 +710,20 @@ impl LocalBufferStore {
         &self,
         buffers: HashSet<Entity<Buffer>>,
         push_to_history: bool,
+        encoding: Option<Encoding>,
         cx: &mut Context<BufferStore>,
     ) -> Task<Result<ProjectTransaction>> {
         cx.spawn(async move |_, cx| {
             let mut project_transaction = ProjectTransaction::default();
             for buffer in buffers {
-                let transaction = buffer.update(cx, |buffer, cx| buffer.reload(cx))?.await?;
+                let transaction = buffer
+                    .update(cx, |buffer, cx| {
+                        if let Some(encoding) = encoding {
+                            buffer.set_encoding(encoding, cx);
+                        }
+                        buffer.reload(cx)
+                    })?
+                    .await?;
                 buffer.update(cx, |buffer, cx| {
                     if let Some(transaction) = transaction {
                         if !push_to_history {
@@ -1400,7 +1414,15 @@ impl BufferStore {
                 buffer.wait_for_version(deserialize_version(&envelope.payload.version))
             })?
             .await?;
-        let buffer_id = buffer.read_with(&cx, |buffer, _| buffer.remote_id())?;
+        let buffer_id = buffer.update(&mut cx, |buffer, cx| {
+            if let Some(encoding) = envelope.payload.encoding {
+                let encoding = deserialize_encoding(Some(encoding));
+                if buffer.encoding() != encoding {
+                    buffer.set_encoding(encoding, cx);
+                }
+            }
+            buffer.remote_id()
+        })?;
 
         if let Some(new_path) = envelope.payload.new_path {
             let new_path = ProjectPath::from_proto(new_path);
@@ -1512,13 +1534,28 @@ impl BufferStore {
         buffers: HashSet<Entity<Buffer>>,
         push_to_history: bool,
         cx: &mut Context<Self>,
+    ) -> Task<Result<ProjectTransaction>> {
+        self.reload_buffers_with_encoding(buffers, push_to_history, None, cx)
+    }
+
+    /// Reloads buffers from disk, first switching them to the given encoding if any.
+    pub fn reload_buffers_with_encoding(
+        &self,
+        buffers: HashSet<Entity<Buffer>>,
+        push_to_history: bool,
+        encoding: Option<Encoding>,
+        cx: &mut Context<Self>,
     ) -> Task<Result<ProjectTransaction>> {
         if buffers.is_empty() {
             return Task::ready(Ok(ProjectTransaction::default()));
         }
         match &self.state {
-            BufferStoreState::Local(this) => this.reload_buffers(buffers, push_to_history, cx),
-            BufferStoreState::Remote(this) => this.reload_buffers(buffers, push_to_history, cx),
+            BufferStoreState::Local(this) => {
+                this.reload_buffers(buffers, push_to_history, encoding, cx)
+            }
+            BufferStoreState::Remote(this) => {
+                this.reload_buffers(buffers, push_to_history, encoding, cx)
+            }
         }
     }
 
@@ -1534,7 +1571,11 @@ impl BufferStore {
                 let buffer_id = BufferId::new(*buffer_id)?;
                 buffers.insert(this.get_existing(buffer_id)?);
             }
-            anyhow::Ok(this.reload_buffers(buffers, false, cx))
+            let encoding = envelope
+                .payload
+                .encoding
+                .map(|encoding| deserialize_encoding(Some(encoding)));
+            anyhow::Ok(this.reload_buffers_with_encoding(buffers, false, encoding, cx))
         })??;
 
         let project_transaction = reload.await?;
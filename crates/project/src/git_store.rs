@@ -1,9 +1,11 @@
 mod conflict_set;
 pub mod git_traversal;
+mod jujutsu;
 
 use crate::{
     ProjectEnvironment, ProjectItem, ProjectPath,
     buffer_store::{BufferStore, BufferStoreEvent},
+    jj_store::{self, JujutsuStore},
     worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
 use anyhow::{Context as _, Result, anyhow, bail};
@@ -37,6 +39,7 @@ use gpui::{
     App, AppContext, AsyncApp, Context, Entity, EventEmitter, SharedString, Subscription, Task,
     WeakEntity,
 };
+use jujutsu::JujutsuWorkingCopy;
 use language::{
     Buffer, BufferEvent, Language, LanguageRegistry,
     proto::{deserialize_version, serialize_version},
@@ -66,7 +69,7 @@ use text::{Bias, BufferId};
 use util::{ResultExt, debug_panic, paths::SanitizedPath, post_inc};
 use worktree::{
     File, PathChange, PathKey, PathProgress, PathSummary, PathTarget, ProjectEntryId,
-    UpdatedGitRepositoriesSet, UpdatedGitRepository, Worktree,
+    UpdatedGitRepositoriesSet, UpdatedGitRepository, Worktree, WorktreeId,
 };
 use zeroize::Zeroize;
 
@@ -81,6 +84,8 @@ pub struct GitStore {
         HashMap<(BufferId, DiffKind), Shared<Task<Result<Entity<BufferDiff>, Arc<anyhow::Error>>>>>,
     diffs: HashMap<BufferId, Entity<BufferGitState>>,
     shared_diffs: HashMap<proto::PeerId, HashMap<BufferId, SharedDiffs>>,
+    jj_store: Entity<JujutsuStore>,
+    jujutsu: HashMap<jj_store::RepositoryId, JujutsuWorkingCopy>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -333,6 +338,7 @@ impl GitStore {
     pub fn local(
         worktree_store: &Entity<WorktreeStore>,
         buffer_store: Entity<BufferStore>,
+        jj_store: Entity<JujutsuStore>,
         environment: Entity<ProjectEnvironment>,
         fs: Arc<dyn Fs>,
         cx: &mut Context<Self>,
@@ -340,6 +346,7 @@ impl GitStore {
         Self::new(
             worktree_store.clone(),
             buffer_store,
+            jj_store,
             GitStoreState::Local {
                 next_repository_id: Arc::new(AtomicU64::new(1)),
                 downstream: None,
@@ -353,6 +360,7 @@ impl GitStore {
     pub fn remote(
         worktree_store: &Entity<WorktreeStore>,
         buffer_store: Entity<BufferStore>,
+        jj_store: Entity<JujutsuStore>,
         upstream_client: AnyProtoClient,
         project_id: u64,
         cx: &mut Context<Self>,
@@ -360,6 +368,7 @@ impl GitStore {
         Self::new(
             worktree_store.clone(),
             buffer_store,
+            jj_store,
             GitStoreState::Remote {
                 upstream_client,
                 upstream_project_id: project_id,
@@ -372,12 +381,14 @@ impl GitStore {
     fn new(
         worktree_store: Entity<WorktreeStore>,
         buffer_store: Entity<BufferStore>,
+        jj_store: Entity<JujutsuStore>,
         state: GitStoreState,
         cx: &mut Context<Self>,
     ) -> Self {
         let _subscriptions = vec![
             cx.subscribe(&worktree_store, Self::on_worktree_store_event),
             cx.subscribe(&buffer_store, Self::on_buffer_store_event),
+            cx.subscribe(&jj_store, Self::on_jj_store_event),
         ];
 
         GitStore {
@@ -390,6 +401,8 @@ impl GitStore {
             loading_diffs: HashMap::default(),
             shared_diffs: HashMap::default(),
             diffs: HashMap::default(),
+            jj_store,
+            jujutsu: HashMap::default(),
         }
     }
 
@@ -570,7 +583,7 @@ impl GitStore {
         let Some((repo, repo_path)) =
             self.repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
         else {
-            return Task::ready(Err(anyhow!("failed to find git repository for buffer")));
+            return self.open_jujutsu_diff(buffer, DiffKind::Unstaged, cx);
         };
 
         let task = self
@@ -626,7 +639,7 @@ impl GitStore {
         let Some((repo, repo_path)) =
             self.repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
         else {
-            return Task::ready(Err(anyhow!("failed to find git repository for buffer")));
+            return self.open_jujutsu_diff(buffer, DiffKind::Uncommitted, cx);
         };
 
         let task = self
@@ -787,7 +800,10 @@ impl GitStore {
         project_path: &ProjectPath,
         cx: &App,
     ) -> Option<FileStatus> {
-        let (repo, repo_path) = self.repository_and_path_for_project_path(project_path, cx)?;
+        let Some((repo, repo_path)) = self.repository_and_path_for_project_path(project_path, cx)
+        else {
+            return self.jujutsu_status_for_project_path(project_path, cx);
+        };
         Some(repo.read(cx).status_for_path(&repo_path)?.status)
     }
 
@@ -2323,10 +2339,20 @@ impl GitStore {
     }
 
     pub fn repo_snapshots(&self, cx: &App) -> HashMap<RepositoryId, RepositorySnapshot> {
-        self.repositories
+        let mut snapshots = self
+            .repositories
             .iter()
             .map(|(id, repo)| (*id, repo.read(cx).snapshot.clone()))
-            .collect()
+            .collect::<HashMap<_, _>>();
+        for jujutsu in self.jujutsu.values() {
+            let jujutsu_snapshot = jujutsu.snapshot();
+            if !snapshots.values().any(|snapshot| {
+                snapshot.work_directory_abs_path == jujutsu_snapshot.work_directory_abs_path
+            }) {
+                snapshots.insert(jujutsu_snapshot.id, jujutsu_snapshot.clone());
+            }
+        }
+        snapshots
     }
 
     fn process_updated_entries(
@@ -0,0 +1,316 @@
+use std::{path::Path, sync::Arc};
+
+use anyhow::{Context as _, Result, anyhow};
+use buffer_diff::BufferDiff;
+use futures::FutureExt as _;
+use git::{
+    repository::RepoPath,
+    status::{FileStatus, StatusCode, TrackedStatus, UnmergedStatus, UnmergedStatusCode},
+};
+use gpui::{App, AppContext as _, Context, Entity, Task};
+use jj::ChangeKind;
+use language::Buffer;
+use rpc::proto;
+use sum_tree::SumTree;
+use text::BufferId;
+use util::ResultExt as _;
+
+use super::{
+    DiffBasesChange, DiffKind, GitStore, GitStoreEvent, RepositoryEvent, RepositoryId,
+    RepositorySnapshot, StatusEntry,
+};
+use crate::{
+    ProjectPath,
+    jj_store::{self, JujutsuStore, JujutsuStoreEvent},
+};
+
+/// Jujutsu repositories are numbered by the jj store rather than the git store,
+/// so their ids are offset to keep them apart from git repositories' ids.
+const JUJUTSU_REPOSITORY_ID_OFFSET: u64 = 1 << 63;
+
+/// A Jujutsu workspace that isn't also a git working tree. Its working-copy
+/// commit is diffed against `@-`, so every change shows up as unstaged.
+pub(super) struct JujutsuWorkingCopy {
+    snapshot: RepositorySnapshot,
+    _reload_diff_bases_task: Task<()>,
+}
+
+impl JujutsuWorkingCopy {
+    pub(super) fn snapshot(&self) -> &RepositorySnapshot {
+        &self.snapshot
+    }
+}
+
+impl GitStore {
+    pub(super) fn on_jj_store_event(
+        &mut self,
+        jj_store: Entity<JujutsuStore>,
+        event: &JujutsuStoreEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match *event {
+            JujutsuStoreEvent::RepositoryAdded(id) | JujutsuStoreEvent::RepositoryUpdated(id) => {
+                let Some(repository) = jj_store.read(cx).repositories().get(&id) else {
+                    return;
+                };
+                let snapshot = repository.read(cx).snapshot().clone();
+                self.update_jujutsu_working_copy(&snapshot, cx);
+                // Reload even if the same files changed, as undoing an
+                // operation or editing another commit can move `@-`.
+                self.reload_jujutsu_diff_bases(id, cx);
+            }
+            JujutsuStoreEvent::RepositoryRemoved(id) => {
+                if let Some(working_copy) = self.jujutsu.remove(&id) {
+                    cx.emit(GitStoreEvent::RepositoryRemoved(working_copy.snapshot.id));
+                }
+            }
+        }
+    }
+
+    fn update_jujutsu_working_copy(
+        &mut self,
+        repository: &jj_store::RepositorySnapshot,
+        cx: &mut Context<Self>,
+    ) {
+        let mut entries = repository
+            .changes
+            .iter()
+            .map(|change| StatusEntry {
+                repo_path: RepoPath(change.path.clone()),
+                status: file_status(change.kind),
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.repo_path.cmp(&b.repo_path));
+        let statuses_by_path = SumTree::from_iter(entries, &());
+
+        if let Some(working_copy) = self.jujutsu.get_mut(&repository.id) {
+            if statuses_by_path == working_copy.snapshot.statuses_by_path {
+                return;
+            }
+            working_copy.snapshot.statuses_by_path = statuses_by_path;
+            working_copy.snapshot.scan_id += 1;
+            cx.emit(GitStoreEvent::RepositoryUpdated(
+                working_copy.snapshot.id,
+                RepositoryEvent::Updated {
+                    full_scan: true,
+                    new_instance: false,
+                },
+                false,
+            ));
+        } else {
+            let id = RepositoryId(JUJUTSU_REPOSITORY_ID_OFFSET + repository.id.0);
+            let mut snapshot = RepositorySnapshot::empty(id, repository.workspace_root.clone());
+            snapshot.statuses_by_path = statuses_by_path;
+            self.jujutsu.insert(
+                repository.id,
+                JujutsuWorkingCopy {
+                    snapshot,
+                    _reload_diff_bases_task: Task::ready(()),
+                },
+            );
+            cx.emit(GitStoreEvent::RepositoryAdded(id));
+        }
+    }
+
+    /// Reloads the `@-` text of every open diff in a local Jujutsu workspace,
+    /// forwarding changes to any downstream collaborators.
+    fn reload_jujutsu_diff_bases(&mut self, id: jj_store::RepositoryId, cx: &mut Context<Self>) {
+        let Some(repository) = self.jj_store.read(cx).repositories().get(&id).cloned() else {
+            return;
+        };
+        let jj_store::RepositoryState::Local { backend } = repository.read(cx).state().clone()
+        else {
+            return;
+        };
+        let buffers = self
+            .diffs
+            .keys()
+            .filter_map(|buffer_id| {
+                let (buffer_repository, repo_path) =
+                    self.jujutsu_path_for_buffer_id(*buffer_id, cx)?;
+                (buffer_repository == repository).then_some((*buffer_id, repo_path))
+            })
+            .collect::<Vec<_>>();
+        let Some(working_copy) = self.jujutsu.get_mut(&id) else {
+            return;
+        };
+
+        working_copy._reload_diff_bases_task = cx.spawn(async move |this, cx| {
+            for (buffer_id, repo_path) in buffers {
+                let backend = backend.clone();
+                let text = cx
+                    .background_spawn(async move { backend.load_parent_text(&repo_path) })
+                    .await;
+                let text = match text {
+                    Ok(text) => text,
+                    Err(error) => {
+                        log::error!("failed to load jj base text: {error:#}");
+                        continue;
+                    }
+                };
+                this.update(cx, |this, cx| {
+                    let Some(diff_state) = this.diffs.get(&buffer_id) else {
+                        return;
+                    };
+                    let Some(buffer) = this.buffer_store.read(cx).get(buffer_id) else {
+                        return;
+                    };
+                    let buffer = buffer.read(cx).text_snapshot();
+                    let downstream_client = this.downstream_client();
+                    diff_state.update(cx, |diff_state, cx| {
+                        let mut normalized_text = text.clone();
+                        if let Some(text) = normalized_text.as_mut() {
+                            text::LineEnding::normalize(text);
+                        }
+                        if diff_state.head_text.as_deref().map(String::as_str)
+                            == normalized_text.as_deref()
+                        {
+                            return;
+                        }
+                        if let Some((client, project_id)) = downstream_client {
+                            client
+                                .send(proto::UpdateDiffBases {
+                                    project_id: project_id.to_proto(),
+                                    buffer_id: buffer_id.to_proto(),
+                                    staged_text: None,
+                                    committed_text: text.clone(),
+                                    mode: proto::update_diff_bases::Mode::IndexMatchesHead as i32,
+                                })
+                                .log_err();
+                        }
+                        diff_state.diff_bases_changed(
+                            buffer,
+                            Some(DiffBasesChange::SetBoth(text)),
+                            cx,
+                        );
+                    });
+                })
+                .ok();
+            }
+        });
+    }
+
+    /// Returns the Jujutsu workspace containing the buffer, unless a git
+    /// repository already covers it, as in colocated workspaces.
+    fn jujutsu_path_for_buffer_id(
+        &self,
+        buffer_id: BufferId,
+        cx: &App,
+    ) -> Option<(Entity<jj_store::Repository>, Arc<Path>)> {
+        let buffer = self.buffer_store.read(cx).get(buffer_id)?;
+        let project_path = buffer.read(cx).project_path(cx)?;
+        self.jujutsu_path_for_project_path(&project_path, cx)
+    }
+
+    fn jujutsu_path_for_project_path(
+        &self,
+        project_path: &ProjectPath,
+        cx: &App,
+    ) -> Option<(Entity<jj_store::Repository>, Arc<Path>)> {
+        if self
+            .repository_and_path_for_project_path(project_path, cx)
+            .is_some()
+        {
+            return None;
+        }
+        let abs_path = self.worktree_store.read(cx).absolutize(project_path, cx)?;
+        self.jj_store
+            .read(cx)
+            .repository_and_path_for_abs_path(&abs_path, cx)
+    }
+
+    /// Opens a diff against `@-` for a buffer in a Jujutsu workspace. Jujutsu
+    /// has no index, so `@-` is the base of both the unstaged and uncommitted diffs.
+    pub(super) fn open_jujutsu_diff(
+        &mut self,
+        buffer: Entity<Buffer>,
+        kind: DiffKind,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<BufferDiff>>> {
+        let buffer_id = buffer.read(cx).remote_id();
+        let Some((repository, path)) = self.jujutsu_path_for_buffer_id(buffer_id, cx) else {
+            return Task::ready(Err(anyhow!("failed to find git repository for buffer")));
+        };
+        let state = repository.read(cx).state().clone();
+
+        let task = self
+            .loading_diffs
+            .entry((buffer_id, kind))
+            .or_insert_with(|| {
+                let diff_bases = match state {
+                    jj_store::RepositoryState::Local { backend } => {
+                        cx.background_spawn(async move {
+                            let text = backend.load_parent_text(&path)?;
+                            anyhow::Ok(DiffBasesChange::SetBoth(text))
+                        })
+                    }
+                    jj_store::RepositoryState::Remote { project_id, client } => {
+                        cx.spawn(async move |_, _| {
+                            use proto::open_uncommitted_diff_response::Mode;
+
+                            let response = client
+                                .request(proto::OpenUncommittedDiff {
+                                    project_id: project_id.to_proto(),
+                                    buffer_id: buffer_id.to_proto(),
+                                })
+                                .await?;
+                            let mode = Mode::from_i32(response.mode).context("Invalid mode")?;
+                            Ok(match mode {
+                                Mode::IndexMatchesHead => {
+                                    DiffBasesChange::SetBoth(response.committed_text)
+                                }
+                                Mode::IndexAndHead => DiffBasesChange::SetEach {
+                                    head: response.committed_text,
+                                    index: response.staged_text,
+                                },
+                            })
+                        })
+                    }
+                };
+                cx.spawn(async move |this, cx| {
+                    Self::open_diff_internal(this, kind, diff_bases.await, buffer, cx)
+                        .await
+                        .map_err(Arc::new)
+                })
+                .shared()
+            })
+            .clone();
+
+        cx.background_spawn(async move { task.await.map_err(|e| anyhow!("{e}")) })
+    }
+
+    pub(super) fn jujutsu_status_for_project_path(
+        &self,
+        project_path: &ProjectPath,
+        cx: &App,
+    ) -> Option<FileStatus> {
+        let (repository, path) = self.jujutsu_path_for_project_path(project_path, cx)?;
+        let working_copy = self.jujutsu.get(&repository.read(cx).id)?;
+        Some(
+            working_copy
+                .snapshot
+                .status_for_path(&RepoPath(path))?
+                .status,
+        )
+    }
+}
+
+fn file_status(kind: ChangeKind) -> FileStatus {
+    let worktree_status = match kind {
+        ChangeKind::Added => StatusCode::Added,
+        ChangeKind::Modified => StatusCode::Modified,
+        ChangeKind::Removed => StatusCode::Deleted,
+        ChangeKind::Conflicted => {
+            return UnmergedStatus {
+                first_head: UnmergedStatusCode::Updated,
+                second_head: UnmergedStatusCode::Updated,
+            }
+            .into();
+        }
+    };
+    TrackedStatus {
+        index_status: StatusCode::Unmodified,
+        worktree_status,
+    }
+    .into()
+}
@@ -0,0 +1,1174 @@
+use std::{
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Context as _, Result};
+use client::ProjectId;
+use collections::{HashMap, HashSet};
+use fs::Fs;
+use futures::StreamExt as _;
+use gpui::{App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, SharedString, Task};
+use jj::{
+    Bookmark, BookmarkChange, ChangeKind, CommitDiff, CommitFile, DEFAULT_LOG_REVSET,
+    JujutsuRepository, LogEdge, LogEdgeKind, LogEntry, Mutation, OperationDiff, OperationLogEntry,
+    RealJujutsuRepository, WorkingCopyChange,
+};
+use rpc::{
+    AnyProtoClient, TypedEnvelope,
+    proto::{self, FromProto, ToProto},
+};
+use util::ResultExt as _;
+use worktree::{Worktree, WorktreeId};
+
+use crate::worktree_store::{WorktreeStore, WorktreeStoreEvent};
+
+const OPERATION_LOG_WATCH_LATENCY: Duration = Duration::from_millis(100);
+
+/// Tracks the Jujutsu workspaces containing the project's worktrees. Worktrees
+/// in the same workspace share a repository.
+pub struct JujutsuStore {
+    state: JujutsuStoreState,
+    repositories: HashMap<RepositoryId, Entity<Repository>>,
+    worktree_ids: HashMap<RepositoryId, HashSet<WorktreeId>>,
+    _subscription: gpui::Subscription,
+}
+
+enum JujutsuStoreState {
+    Local {
+        fs: Arc<dyn Fs>,
+        next_repository_id: u64,
+        downstream: Option<(AnyProtoClient, ProjectId)>,
+    },
+    Remote {
+        upstream_client: AnyProtoClient,
+        upstream_project_id: u64,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RepositoryId(pub u64);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JujutsuStoreEvent {
+    RepositoryAdded(RepositoryId),
+    RepositoryUpdated(RepositoryId),
+    RepositoryRemoved(RepositoryId),
+}
+
+impl EventEmitter<JujutsuStoreEvent> for JujutsuStore {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RepositorySnapshot {
+    pub id: RepositoryId,
+    pub workspace_root: Arc<Path>,
+    /// The hex ID of the latest operation, which changes with every jj
+    /// command, including ones that leave the working copy alone.
+    pub operation_id: String,
+    /// The files changed in the working-copy commit, as of `operation_id`.
+    pub changes: Vec<WorkingCopyChange>,
+    /// The revset `jj log` shows in this workspace.
+    pub log_revset: String,
+}
+
+impl RepositorySnapshot {
+    /// Returns the path of `abs_path` relative to the workspace root.
+    pub fn abs_path_to_repo_path(&self, abs_path: &Path) -> Option<Arc<Path>> {
+        abs_path
+            .strip_prefix(&self.workspace_root)
+            .ok()
+            .map(Arc::from)
+    }
+
+    fn to_proto(
+        &self,
+        project_id: u64,
+        worktree_ids: &HashSet<WorktreeId>,
+    ) -> proto::UpdateJujutsuRepository {
+        proto::UpdateJujutsuRepository {
+            project_id,
+            id: self.id.0,
+            workspace_root: self.workspace_root.to_proto(),
+            operation_id: self.operation_id.clone(),
+            changes: self
+                .changes
+                .iter()
+                .map(|change| proto::JujutsuChange {
+                    path: change.path.to_proto(),
+                    kind: change_kind_to_proto(change.kind) as i32,
+                })
+                .collect(),
+            worktree_ids: worktree_ids
+                .iter()
+                .map(|worktree_id| worktree_id.to_proto())
+                .collect(),
+            log_revset: self.log_revset.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum RepositoryState {
+    Local {
+        backend: Arc<dyn JujutsuRepository>,
+    },
+    Remote {
+        project_id: ProjectId,
+        client: AnyProtoClient,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RepositoryEvent {
+    Updated,
+}
+
+/// A Jujutsu workspace containing some of the project's worktrees.
+pub struct Repository {
+    snapshot: RepositorySnapshot,
+    state: RepositoryState,
+    _reload_task: Task<()>,
+    _watch_task: Task<()>,
+}
+
+impl EventEmitter<RepositoryEvent> for Repository {}
+
+impl Deref for Repository {
+    type Target = RepositorySnapshot;
+
+    fn deref(&self) -> &Self::Target {
+        &self.snapshot
+    }
+}
+
+impl JujutsuStore {
+    pub fn init(client: &AnyProtoClient) {
+        client.add_entity_message_handler(Self::handle_update_repository);
+        client.add_entity_message_handler(Self::handle_remove_repository);
+        client.add_entity_request_handler(Self::handle_get_bookmarks);
+        client.add_entity_request_handler(Self::handle_log);
+        client.add_entity_request_handler(Self::handle_load_commit_diff);
+        client.add_entity_request_handler(Self::handle_mutate);
+        client.add_entity_request_handler(Self::handle_operation_log);
+        client.add_entity_request_handler(Self::handle_load_operation_diff);
+    }
+
+    pub fn local(
+        worktree_store: &Entity<WorktreeStore>,
+        fs: Arc<dyn Fs>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            state: JujutsuStoreState::Local {
+                fs,
+                next_repository_id: 1,
+                downstream: None,
+            },
+            repositories: HashMap::default(),
+            worktree_ids: HashMap::default(),
+            _subscription: cx.subscribe(worktree_store, Self::on_worktree_store_event),
+        }
+    }
+
+    pub fn remote(
+        worktree_store: &Entity<WorktreeStore>,
+        upstream_client: AnyProtoClient,
+        upstream_project_id: u64,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            state: JujutsuStoreState::Remote {
+                upstream_client,
+                upstream_project_id,
+            },
+            repositories: HashMap::default(),
+            worktree_ids: HashMap::default(),
+            _subscription: cx.subscribe(worktree_store, Self::on_worktree_store_event),
+        }
+    }
+
+    pub fn shared(&mut self, project_id: u64, client: AnyProtoClient, cx: &mut Context<Self>) {
+        let JujutsuStoreState::Local { downstream, .. } = &mut self.state else {
+            return;
+        };
+        *downstream = Some((client, ProjectId(project_id)));
+        self.broadcast(cx);
+    }
+
+    pub fn unshared(&mut self, _: &mut Context<Self>) {
+        if let JujutsuStoreState::Local { downstream, .. } = &mut self.state {
+            *downstream = None;
+        }
+    }
+
+    /// Sends every repository downstream, such as to a collaborator who just joined.
+    pub(crate) fn broadcast(&self, cx: &App) {
+        for id in self.repositories.keys() {
+            self.send_downstream_update(*id, cx);
+        }
+    }
+
+    pub fn repositories(&self) -> &HashMap<RepositoryId, Entity<Repository>> {
+        &self.repositories
+    }
+
+    pub fn repository_for_worktree(&self, worktree_id: WorktreeId) -> Option<Entity<Repository>> {
+        let (id, _) = self
+            .worktree_ids
+            .iter()
+            .find(|(_, worktree_ids)| worktree_ids.contains(&worktree_id))?;
+        self.repositories.get(id).cloned()
+    }
+
+    /// Returns the innermost Jujutsu workspace containing `abs_path`, along
+    /// with the path relative to its root.
+    pub fn repository_and_path_for_abs_path(
+        &self,
+        abs_path: &Path,
+        cx: &App,
+    ) -> Option<(Entity<Repository>, Arc<Path>)> {
+        self.repositories
+            .values()
+            .filter_map(|repository| {
+                let repo_path = repository.read(cx).abs_path_to_repo_path(abs_path)?;
+                Some((repository.clone(), repo_path))
+            })
+            .max_by_key(|(repository, _)| repository.read(cx).workspace_root.clone())
+    }
+
+    /// Tracks the Jujutsu workspace of a local worktree, reloading it whenever
+    /// its operation log changes. A worktree in an already tracked workspace
+    /// shares its repository.
+    pub fn add_local_repository(
+        &mut self,
+        worktree_id: WorktreeId,
+        backend: Arc<dyn JujutsuRepository>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(id) = self.repository_id_for_workspace_root(backend.workspace_root(), cx) {
+            self.add_worktree(id, worktree_id, cx);
+            return;
+        }
+        let JujutsuStoreState::Local {
+            fs,
+            next_repository_id,
+            ..
+        } = &mut self.state
+        else {
+            return;
+        };
+        let id = RepositoryId(*next_repository_id);
+        *next_repository_id += 1;
+        let fs = fs.clone();
+        let repository = cx.new(|cx| Repository::local(id, backend, fs, cx));
+        self.worktree_ids
+            .insert(id, HashSet::from_iter([worktree_id]));
+        self.insert_repository(id, repository, cx);
+    }
+
+    fn repository_id_for_workspace_root(
+        &self,
+        workspace_root: &Path,
+        cx: &App,
+    ) -> Option<RepositoryId> {
+        self.repositories
+            .iter()
+            .find(|(_, repository)| *repository.read(cx).workspace_root == *workspace_root)
+            .map(|(id, _)| *id)
+    }
+
+    fn add_worktree(&mut self, id: RepositoryId, worktree_id: WorktreeId, cx: &App) {
+        if self.worktree_ids.entry(id).or_default().insert(worktree_id) {
+            self.send_downstream_update(id, cx);
+        }
+    }
+
+    fn remove_worktree(&mut self, worktree_id: WorktreeId, cx: &mut Context<Self>) {
+        let Some((&id, worktree_ids)) = self
+            .worktree_ids
+            .iter_mut()
+            .find(|(_, worktree_ids)| worktree_ids.contains(&worktree_id))
+        else {
+            return;
+        };
+        worktree_ids.remove(&worktree_id);
+        if worktree_ids.is_empty() {
+            self.remove_repository(id, cx);
+        } else {
+            self.send_downstream_update(id, cx);
+        }
+    }
+
+    fn insert_repository(
+        &mut self,
+        id: RepositoryId,
+        repository: Entity<Repository>,
+        cx: &mut Context<Self>,
+    ) {
+        cx.subscribe(&repository, move |this, _, _: &RepositoryEvent, cx| {
+            this.send_downstream_update(id, cx);
+            cx.emit(JujutsuStoreEvent::RepositoryUpdated(id));
+        })
+        .detach();
+        self.repositories.insert(id, repository);
+        self.send_downstream_update(id, cx);
+        cx.emit(JujutsuStoreEvent::RepositoryAdded(id));
+    }
+
+    fn remove_repository(&mut self, id: RepositoryId, cx: &mut Context<Self>) {
+        self.worktree_ids.remove(&id);
+        if self.repositories.remove(&id).is_none() {
+            return;
+        }
+        if let JujutsuStoreState::Local {
+            downstream: Some((client, project_id)),
+            ..
+        } = &self.state
+        {
+            client
+                .send(proto::RemoveJujutsuRepository {
+                    project_id: project_id.to_proto(),
+                    id: id.0,
+                })
+                .log_err();
+        }
+        cx.emit(JujutsuStoreEvent::RepositoryRemoved(id));
+    }
+
+    fn send_downstream_update(&self, id: RepositoryId, cx: &App) {
+        let JujutsuStoreState::Local {
+            downstream: Some((client, project_id)),
+            ..
+        } = &self.state
+        else {
+            return;
+        };
+        let Some(repository) = self.repositories.get(&id) else {
+            return;
+        };
+        let worktree_ids = self.worktree_ids.get(&id).cloned().unwrap_or_default();
+        client
+            .send(
+                repository
+                    .read(cx)
+                    .snapshot
+                    .to_proto(project_id.to_proto(), &worktree_ids),
+            )
+            .log_err();
+    }
+
+    fn on_worktree_store_event(
+        &mut self,
+        _: Entity<WorktreeStore>,
+        event: &WorktreeStoreEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            WorktreeStoreEvent::WorktreeAdded(worktree) => {
+                self.discover_repository(worktree, cx);
+            }
+            WorktreeStoreEvent::WorktreeRemoved(_, worktree_id) => {
+                if matches!(self.state, JujutsuStoreState::Local { .. }) {
+                    self.remove_worktree(*worktree_id, cx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn discover_repository(&mut self, worktree: &Entity<Worktree>, cx: &mut Context<Self>) {
+        let JujutsuStoreState::Local { fs, .. } = &self.state else {
+            return;
+        };
+        let worktree = worktree.read(cx);
+        if !worktree.is_local() || !worktree.is_visible() {
+            return;
+        }
+        let fs = fs.clone();
+        let worktree_id = worktree.id();
+        let abs_path = worktree.abs_path();
+        cx.spawn(async move |this, cx| {
+            let Some(workspace_root) = find_workspace_root(fs.as_ref(), &abs_path).await else {
+                return Ok(());
+            };
+            // Worktrees in a workspace that's already tracked share its repository.
+            let is_tracked = this.update(cx, |this, cx| {
+                let Some(id) = this.repository_id_for_workspace_root(&workspace_root, cx) else {
+                    return false;
+                };
+                this.add_worktree(id, worktree_id, cx);
+                true
+            })?;
+            if is_tracked {
+                return Ok(());
+            }
+            let backend = cx
+                .background_spawn(async move { RealJujutsuRepository::new(&workspace_root) })
+                .await
+                .with_context(|| format!("loading jj workspace for {abs_path:?}"))?;
+            this.update(cx, |this, cx| {
+                this.add_local_repository(worktree_id, Arc::new(backend), cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    async fn handle_update_repository(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateJujutsuRepository>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            let JujutsuStoreState::Remote {
+                upstream_client,
+                upstream_project_id,
+            } = &this.state
+            else {
+                return;
+            };
+            let update = envelope.payload;
+            let id = RepositoryId(update.id);
+            this.worktree_ids.insert(
+                id,
+                update
+                    .worktree_ids
+                    .into_iter()
+                    .map(WorktreeId::from_proto)
+                    .collect(),
+            );
+            let snapshot = RepositorySnapshot {
+                id,
+                workspace_root: Arc::<Path>::from_proto(update.workspace_root),
+                operation_id: update.operation_id,
+                changes: update
+                    .changes
+                    .into_iter()
+                    .map(|change| WorkingCopyChange {
+                        path: Arc::<Path>::from_proto(change.path),
+                        kind: change_kind_from_proto(change.kind()),
+                    })
+                    .collect(),
+                log_revset: update.log_revset,
+            };
+
+            if let Some(repository) = this.repositories.get(&id) {
+                repository.update(cx, |repository, cx| {
+                    repository.snapshot = snapshot;
+                    cx.emit(RepositoryEvent::Updated);
+                });
+            } else {
+                let state = RepositoryState::Remote {
+                    project_id: ProjectId(*upstream_project_id),
+                    client: upstream_client.clone(),
+                };
+                let repository = cx.new(|_| Repository {
+                    snapshot,
+                    state,
+                    _reload_task: Task::ready(()),
+                    _watch_task: Task::ready(()),
+                });
+                this.insert_repository(id, repository, cx);
+            }
+        })
+    }
+
+    async fn handle_remove_repository(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::RemoveJujutsuRepository>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            this.remove_repository(RepositoryId(envelope.payload.id), cx);
+        })
+    }
+
+    async fn handle_get_bookmarks(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::JujutsuGetBookmarks>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::JujutsuBookmarksResponse> {
+        let id = RepositoryId(envelope.payload.repository_id);
+        let bookmarks = this
+            .update(&mut cx, |this, cx| {
+                let repository = this.repositories.get(&id)?;
+                Some(repository.read(cx).bookmarks(cx))
+            })?
+            .context("missing jj repository")?
+            .await?;
+        let (local_bookmarks, remote_bookmarks): (Vec<_>, Vec<_>) = bookmarks
+            .into_iter()
+            .partition(|bookmark| bookmark.remote.is_none());
+        Ok(proto::JujutsuBookmarksResponse {
+            bookmarks: local_bookmarks
+                .into_iter()
+                .map(|bookmark| bookmark.ref_name.to_string())
+                .collect(),
+            remote_bookmarks: remote_bookmarks
+                .into_iter()
+                .filter_map(|bookmark| {
+                    Some(proto::JujutsuRemoteBookmark {
+                        name: bookmark.ref_name.to_string(),
+                        remote: bookmark.remote?.to_string(),
+                        is_tracked: bookmark.is_tracked,
+                    })
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_log(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::JujutsuLog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::JujutsuLogResponse> {
+        let id = RepositoryId(envelope.payload.repository_id);
+        let revset = envelope.payload.revset;
+        let limit = envelope.payload.limit as usize;
+        let entries = this
+            .update(&mut cx, |this, cx| {
+                let repository = this.repositories.get(&id)?;
+                Some(repository.read(cx).log(revset, limit, cx))
+            })?
+            .context("missing jj repository")?
+            .await?;
+        Ok(proto::JujutsuLogResponse {
+            entries: entries.into_iter().map(log_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_load_commit_diff(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::JujutsuLoadCommitDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::JujutsuCommitDiffResponse> {
+        let id = RepositoryId(envelope.payload.repository_id);
+        let commit_id = envelope.payload.commit_id;
+        let diff = this
+            .update(&mut cx, |this, cx| {
+                let repository = this.repositories.get(&id)?;
+                Some(repository.read(cx).commit_diff(commit_id, cx))
+            })?
+            .context("missing jj repository")?
+            .await?;
+        Ok(proto::JujutsuCommitDiffResponse {
+            files: diff
+                .files
+                .into_iter()
+                .map(|file| proto::JujutsuCommitFile {
+                    path: file.path.to_proto(),
+                    old_text: file.old_text,
+                    new_text: file.new_text,
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_mutate(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::JujutsuMutate>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let id = RepositoryId(envelope.payload.repository_id);
+        let mutation =
+            mutation_from_proto(envelope.payload.variant.context("missing jj mutation")?);
+        this.update(&mut cx, |this, cx| {
+            let repository = this.repositories.get(&id)?;
+            Some(repository.update(cx, |repository, cx| repository.mutate(mutation, cx)))
+        })?
+        .context("missing jj repository")?
+        .await?;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_operation_log(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::JujutsuOperationLog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::JujutsuOperationLogResponse> {
+        let id = RepositoryId(envelope.payload.repository_id);
+        let limit = envelope.payload.limit as usize;
+        let operations = this
+            .update(&mut cx, |this, cx| {
+                let repository = this.repositories.get(&id)?;
+                Some(repository.read(cx).operation_log(limit, cx))
+            })?
+            .context("missing jj repository")?
+            .await?;
+        Ok(proto::JujutsuOperationLogResponse {
+            operations: operations
+                .into_iter()
+                .map(|operation| proto::JujutsuOperation {
+                    id: operation.id.to_string(),
+                    description: operation.description.to_string(),
+                    timestamp: operation.timestamp,
+                    user: operation.user.to_string(),
+                    is_root: operation.is_root,
+                })
+                .collect(),
+        })
+    }
+
+    async fn handle_load_operation_diff(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::JujutsuLoadOperationDiff>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::JujutsuOperationDiffResponse> {
+        let id = RepositoryId(envelope.payload.repository_id);
+        let operation_id = envelope.payload.operation_id;
+        let limit = envelope.payload.limit as usize;
+        let diff = this
+            .update(&mut cx, |this, cx| {
+                let repository = this.repositories.get(&id)?;
+                Some(repository.read(cx).operation_diff(operation_id, limit, cx))
+            })?
+            .context("missing jj repository")?
+            .await?;
+        Ok(proto::JujutsuOperationDiffResponse {
+            added_commits: diff
+                .added_commits
+                .into_iter()
+                .map(log_entry_to_proto)
+                .collect(),
+            removed_commits: diff
+                .removed_commits
+                .into_iter()
+                .map(log_entry_to_proto)
+                .collect(),
+            bookmark_changes: diff
+                .bookmark_changes
+                .into_iter()
+                .map(|change| proto::JujutsuBookmarkChange {
+                    name: change.name.to_string(),
+                    old_target: change.old_target.map(|target| target.to_string()),
+                    new_target: change.new_target.map(|target| target.to_string()),
+                })
+                .collect(),
+        })
+    }
+}
+
+impl Repository {
+    fn local(
+        id: RepositoryId,
+        backend: Arc<dyn JujutsuRepository>,
+        fs: Arc<dyn Fs>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let workspace_root = Arc::<Path>::from(backend.workspace_root());
+        let watch_task = cx.spawn({
+            let workspace_root = workspace_root.clone();
+            async move |this, cx| {
+                let op_heads_dir = op_heads_dir(fs.as_ref(), &workspace_root).await;
+                let (mut events, _watcher) =
+                    fs.watch(&op_heads_dir, OPERATION_LOG_WATCH_LATENCY).await;
+                while events.next().await.is_some() {
+                    if this.update(cx, |this, cx| this.reload(cx)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        let log_revset = backend.log_revset().unwrap_or_else(|error| {
+            log::error!("failed to read jj's revsets.log config: {error:#}");
+            DEFAULT_LOG_REVSET.to_string()
+        });
+        let mut this = Self {
+            snapshot: RepositorySnapshot {
+                id,
+                workspace_root,
+                operation_id: String::new(),
+                changes: Vec::new(),
+                log_revset,
+            },
+            state: RepositoryState::Local { backend },
+            _reload_task: Task::ready(()),
+            _watch_task: watch_task,
+        };
+        this.reload(cx);
+        this
+    }
+
+    pub fn snapshot(&self) -> &RepositorySnapshot {
+        &self.snapshot
+    }
+
+    pub fn state(&self) -> &RepositoryState {
+        &self.state
+    }
+
+    /// Rereads the working-copy status at the latest operation, emitting an
+    /// update whenever the operation changed.
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        let RepositoryState::Local { backend } = &self.state else {
+            return;
+        };
+        let backend = backend.clone();
+        self._reload_task = cx.spawn(async move |this, cx| {
+            let status = cx
+                .background_spawn(async move { backend.working_copy_status() })
+                .await;
+            let status = match status {
+                Ok(status) => status,
+                Err(error) => {
+                    log::error!("failed to load jj working copy status: {error:#}");
+                    return;
+                }
+            };
+            this.update(cx, |this, cx| {
+                if this.snapshot.operation_id != status.operation_id {
+                    this.snapshot.operation_id = status.operation_id;
+                    this.snapshot.changes = status.changes;
+                    cx.emit(RepositoryEvent::Updated);
+                }
+            })
+            .ok();
+        });
+    }
+
+    pub fn bookmarks(&self, cx: &App) -> Task<Result<Vec<Bookmark>>> {
+        match &self.state {
+            RepositoryState::Local { backend } => {
+                let backend = backend.clone();
+                cx.background_spawn(async move { backend.list_bookmarks() })
+            }
+            RepositoryState::Remote { project_id, client } => {
+                let request = client.request(proto::JujutsuGetBookmarks {
+                    project_id: project_id.to_proto(),
+                    repository_id: self.id.0,
+                });
+                cx.spawn(async move |_| {
+                    let response = request.await?;
+                    let local_bookmarks = response.bookmarks.into_iter().map(|ref_name| Bookmark {
+                        ref_name: SharedString::from(ref_name),
+                        remote: None,
+                        is_tracked: false,
+                    });
+                    let remote_bookmarks =
+                        response
+                            .remote_bookmarks
+                            .into_iter()
+                            .map(|bookmark| Bookmark {
+                                ref_name: SharedString::from(bookmark.name),
+                                remote: Some(SharedString::from(bookmark.remote)),
+                                is_tracked: bookmark.is_tracked,
+                            });
+                    Ok(local_bookmarks.chain(remote_bookmarks).collect())
+                })
+            }
+        }
+    }
+
+    /// Evaluates `revset` at the latest operation, returning at most `limit`
+    /// commits, children before their parents.
+    pub fn log(&self, revset: String, limit: usize, cx: &App) -> Task<Result<Vec<LogEntry>>> {
+        match &self.state {
+            RepositoryState::Local { backend } => {
+                let backend = backend.clone();
+                cx.background_spawn(async move { backend.log(&revset, limit) })
+            }
+            RepositoryState::Remote { project_id, client } => {
+                let request = client.request(proto::JujutsuLog {
+                    project_id: project_id.to_proto(),
+                    repository_id: self.id.0,
+                    revset,
+                    limit: limit as u64,
+                });
+                cx.spawn(async move |_| {
+                    let response = request.await?;
+                    Ok(response
+                        .entries
+                        .into_iter()
+                        .map(log_entry_from_proto)
+                        .collect())
+                })
+            }
+        }
+    }
+
+    /// Loads the changes a commit makes to its parents.
+    pub fn commit_diff(&self, commit_id: String, cx: &App) -> Task<Result<CommitDiff>> {
+        match &self.state {
+            RepositoryState::Local { backend } => {
+                let backend = backend.clone();
+                cx.background_spawn(async move { backend.commit_diff(&commit_id) })
+            }
+            RepositoryState::Remote { project_id, client } => {
+                let request = client.request(proto::JujutsuLoadCommitDiff {
+                    project_id: project_id.to_proto(),
+                    repository_id: self.id.0,
+                    commit_id,
+                });
+                cx.spawn(async move |_| {
+                    let response = request.await?;
+                    Ok(CommitDiff {
+                        files: response
+                            .files
+                            .into_iter()
+                            .map(|file| CommitFile {
+                                path: Arc::<Path>::from_proto(file.path),
+                                old_text: file.old_text,
+                                new_text: file.new_text,
+                            })
+                            .collect(),
+                    })
+                })
+            }
+        }
+    }
+
+    /// Lists at most `limit` operations leading up to the latest one, newest first.
+    pub fn operation_log(&self, limit: usize, cx: &App) -> Task<Result<Vec<OperationLogEntry>>> {
+        match &self.state {
+            RepositoryState::Local { backend } => {
+                let backend = backend.clone();
+                cx.background_spawn(async move { backend.operation_log(limit) })
+            }
+            RepositoryState::Remote { project_id, client } => {
+                let request = client.request(proto::JujutsuOperationLog {
+                    project_id: project_id.to_proto(),
+                    repository_id: self.id.0,
+                    limit: limit as u64,
+                });
+                cx.spawn(async move |_| {
+                    let response = request.await?;
+                    Ok(response
+                        .operations
+                        .into_iter()
+                        .map(|operation| OperationLogEntry {
+                            id: operation.id.into(),
+                            description: operation.description.into(),
+                            timestamp: operation.timestamp,
+                            user: operation.user.into(),
+                            is_root: operation.is_root,
+                        })
+                        .collect())
+                })
+            }
+        }
+    }
+
+    /// Loads how an operation changed the repository, listing at most `limit`
+    /// added and removed commits.
+    pub fn operation_diff(
+        &self,
+        operation_id: String,
+        limit: usize,
+        cx: &App,
+    ) -> Task<Result<OperationDiff>> {
+        match &self.state {
+            RepositoryState::Local { backend } => {
+                let backend = backend.clone();
+                cx.background_spawn(async move { backend.operation_diff(&operation_id, limit) })
+            }
+            RepositoryState::Remote { project_id, client } => {
+                let request = client.request(proto::JujutsuLoadOperationDiff {
+                    project_id: project_id.to_proto(),
+                    repository_id: self.id.0,
+                    operation_id,
+                    limit: limit as u64,
+                });
+                cx.spawn(async move |_| {
+                    let response = request.await?;
+                    Ok(OperationDiff {
+                        added_commits: response
+                            .added_commits
+                            .into_iter()
+                            .map(log_entry_from_proto)
+                            .collect(),
+                        removed_commits: response
+                            .removed_commits
+                            .into_iter()
+                            .map(log_entry_from_proto)
+                            .collect(),
+                        bookmark_changes: response
+                            .bookmark_changes
+                            .into_iter()
+                            .map(|change| BookmarkChange {
+                                name: change.name.into(),
+                                old_target: change.old_target.map(Into::into),
+                                new_target: change.new_target.map(Into::into),
+                            })
+                            .collect(),
+                    })
+                })
+            }
+        }
+    }
+
+    /// Applies `mutation` in a new jj operation, updating the working copy on
+    /// disk if the working-copy commit changes.
+    pub fn mutate(&mut self, mutation: Mutation, cx: &mut Context<Self>) -> Task<Result<()>> {
+        match &self.state {
+            RepositoryState::Local { backend } => {
+                let backend = backend.clone();
+                cx.spawn(async move |this, cx| {
+                    cx.background_spawn(async move { backend.mutate(mutation) })
+                        .await?;
+                    this.update(cx, |this, cx| this.reload(cx))
+                })
+            }
+            RepositoryState::Remote { project_id, client } => {
+                let request = client.request(proto::JujutsuMutate {
+                    project_id: project_id.to_proto(),
+                    repository_id: self.id.0,
+                    variant: Some(mutation_to_proto(mutation)),
+                });
+                cx.spawn(async move |_, _| {
+                    request.await?;
+                    Ok(())
+                })
+            }
+        }
+    }
+}
+
+async fn find_workspace_root(fs: &dyn Fs, path: &Path) -> Option<PathBuf> {
+    for ancestor in path.ancestors() {
+        if fs.is_dir(&ancestor.join(".jj")).await {
+            return Some(ancestor.to_path_buf());
+        }
+    }
+    None
+}
+
+/// Returns the directory jj updates whenever an operation is committed.
+async fn op_heads_dir(fs: &dyn Fs, workspace_root: &Path) -> PathBuf {
+    let jj_dir = workspace_root.join(".jj");
+    let mut repo_dir = jj_dir.join("repo");
+    // Secondary workspaces point at the main workspace's repository from a file.
+    if fs.is_file(&repo_dir).await
+        && let Some(target) = fs.load(&repo_dir).await.log_err()
+    {
+        repo_dir = jj_dir.join(target.trim());
+    }
+    repo_dir.join("op_heads").join("heads")
+}
+
+fn change_kind_to_proto(kind: ChangeKind) -> proto::JujutsuChangeKind {
+    match kind {
+        ChangeKind::Added => proto::JujutsuChangeKind::Added,
+        ChangeKind::Modified => proto::JujutsuChangeKind::Modified,
+        ChangeKind::Removed => proto::JujutsuChangeKind::Removed,
+        ChangeKind::Conflicted => proto::JujutsuChangeKind::Conflicted,
+    }
+}
+
+fn change_kind_from_proto(kind: proto::JujutsuChangeKind) -> ChangeKind {
+    match kind {
+        proto::JujutsuChangeKind::Added => ChangeKind::Added,
+        proto::JujutsuChangeKind::Modified => ChangeKind::Modified,
+        proto::JujutsuChangeKind::Removed => ChangeKind::Removed,
+        proto::JujutsuChangeKind::Conflicted => ChangeKind::Conflicted,
+    }
+}
+
+fn log_entry_to_proto(entry: LogEntry) -> proto::JujutsuLogEntry {
+    proto::JujutsuLogEntry {
+        commit_id: entry.commit_id.to_string(),
+        change_id: entry.change_id.to_string(),
+        description: entry.description.to_string(),
+        author_name: entry.author_name.to_string(),
+        author_email: entry.author_email.to_string(),
+        timestamp: entry.timestamp,
+        bookmarks: entry
+            .bookmarks
+            .into_iter()
+            .map(|bookmark| bookmark.to_string())
+            .collect(),
+        is_working_copy: entry.is_working_copy,
+        is_empty: entry.is_empty,
+        has_conflict: entry.has_conflict,
+        parents: entry
+            .parents
+            .into_iter()
+            .map(|edge| proto::JujutsuLogEdge {
+                target: edge.target.to_string(),
+                kind: match edge.kind {
+                    LogEdgeKind::Direct => proto::JujutsuLogEdgeKind::Direct,
+                    LogEdgeKind::Indirect => proto::JujutsuLogEdgeKind::Indirect,
+                    LogEdgeKind::Missing => proto::JujutsuLogEdgeKind::Missing,
+                } as i32,
+            })
+            .collect(),
+    }
+}
+
+fn log_entry_from_proto(entry: proto::JujutsuLogEntry) -> LogEntry {
+    LogEntry {
+        commit_id: entry.commit_id.into(),
+        change_id: entry.change_id.into(),
+        description: entry.description.into(),
+        author_name: entry.author_name.into(),
+        author_email: entry.author_email.into(),
+        timestamp: entry.timestamp,
+        bookmarks: entry.bookmarks.into_iter().map(Into::into).collect(),
+        is_working_copy: entry.is_working_copy,
+        is_empty: entry.is_empty,
+        has_conflict: entry.has_conflict,
+        parents: entry
+            .parents
+            .into_iter()
+            .map(|edge| LogEdge {
+                kind: match edge.kind() {
+                    proto::JujutsuLogEdgeKind::Direct => LogEdgeKind::Direct,
+                    proto::JujutsuLogEdgeKind::Indirect => LogEdgeKind::Indirect,
+                    proto::JujutsuLogEdgeKind::Missing => LogEdgeKind::Missing,
+                },
+                target: edge.target.into(),
+            })
+            .collect(),
+    }
+}
+
+fn mutation_to_proto(mutation: Mutation) -> proto::jujutsu_mutate::Variant {
+    use proto::jujutsu_mutate::{self as mutate, Variant};
+    match mutation {
+        Mutation::New { parents } => Variant::New(mutate::New { parents }),
+        Mutation::Describe {
+            revision,
+            description,
+        } => Variant::Describe(mutate::Describe {
+            revision,
+            description,
+        }),
+        Mutation::Squash { revision } => Variant::Squash(mutate::Squash { revision }),
+        Mutation::Split { revision, paths } => Variant::Split(mutate::Split {
+            revision,
+            paths: paths.iter().map(|path| path.to_proto()).collect(),
+        }),
+        Mutation::Edit { revision } => Variant::Edit(mutate::Edit { revision }),
+        Mutation::Abandon { revision } => Variant::Abandon(mutate::Abandon { revision }),
+        Mutation::CreateBookmark { name, revision } => {
+            Variant::CreateBookmark(mutate::CreateBookmark { name, revision })
+        }
+        Mutation::MoveBookmark { name, revision } => {
+            Variant::MoveBookmark(mutate::MoveBookmark { name, revision })
+        }
+        Mutation::DeleteBookmark { name } => {
+            Variant::DeleteBookmark(mutate::DeleteBookmark { name })
+        }
+        Mutation::TrackBookmark { name, remote } => {
+            Variant::TrackBookmark(mutate::TrackBookmark { name, remote })
+        }
+        Mutation::UndoOperation { operation_id } => {
+            Variant::UndoOperation(mutate::UndoOperation { operation_id })
+        }
+        Mutation::RestoreOperation { operation_id } => {
+            Variant::RestoreOperation(mutate::RestoreOperation { operation_id })
+        }
+    }
+}
+
+fn mutation_from_proto(mutation: proto::jujutsu_mutate::Variant) -> Mutation {
+    use proto::jujutsu_mutate::Variant;
+    match mutation {
+        Variant::New(new) => Mutation::New {
+            parents: new.parents,
+        },
+        Variant::Describe(describe) => Mutation::Describe {
+            revision: describe.revision,
+            description: describe.description,
+        },
+        Variant::Squash(squash) => Mutation::Squash {
+            revision: squash.revision,
+        },
+        Variant::Split(split) => Mutation::Split {
+            revision: split.revision,
+            paths: split
+                .paths
+                .into_iter()
+                .map(Arc::<Path>::from_proto)
+                .collect(),
+        },
+        Variant::Edit(edit) => Mutation::Edit {
+            revision: edit.revision,
+        },
+        Variant::Abandon(abandon) => Mutation::Abandon {
+            revision: abandon.revision,
+        },
+        Variant::CreateBookmark(bookmark) => Mutation::CreateBookmark {
+            name: bookmark.name,
+            revision: bookmark.revision,
+        },
+        Variant::MoveBookmark(bookmark) => Mutation::MoveBookmark {
+            name: bookmark.name,
+            revision: bookmark.revision,
+        },
+        Variant::DeleteBookmark(bookmark) => Mutation::DeleteBookmark {
+            name: bookmark.name,
+        },
+        Variant::TrackBookmark(bookmark) => Mutation::TrackBookmark {
+            name: bookmark.name,
+            remote: bookmark.remote,
+        },
+        Variant::UndoOperation(undo) => Mutation::UndoOperation {
+            operation_id: undo.operation_id,
+        },
+        Variant::RestoreOperation(restore) => Mutation::RestoreOperation {
+            operation_id: restore.operation_id,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_mutation_proto_round_trip() {
+        let mutations = vec![
+            Mutation::New {
+                parents: vec!["@".into(), "main".into()],
+            },
+            Mutation::Describe {
+                revision: "@".into(),
+                description: "Fix the frobnicator\n\nDetails.".into(),
+            },
+            Mutation::Squash {
+                revision: "@".into(),
+            },
+            Mutation::Split {
+                revision: "@-".into(),
+                paths: vec![Path::new("a.txt").into(), Path::new("dir/b.txt").into()],
+            },
+            Mutation::Edit {
+                revision: "qpvuntsm".into(),
+            },
+            Mutation::Abandon {
+                revision: "@-".into(),
+            },
+            Mutation::CreateBookmark {
+                name: "feature".into(),
+                revision: "@".into(),
+            },
+            Mutation::MoveBookmark {
+                name: "feature".into(),
+                revision: "@-".into(),
+            },
+            Mutation::DeleteBookmark {
+                name: "feature".into(),
+            },
+            Mutation::TrackBookmark {
+                name: "main".into(),
+                remote: "origin".into(),
+            },
+            Mutation::UndoOperation {
+                operation_id: "0123abcd".into(),
+            },
+            Mutation::RestoreOperation {
+                operation_id: "4567ef89".into(),
+            },
+        ];
+
+        for mutation in mutations {
+            assert_eq!(
+                mutation_from_proto(mutation_to_proto(mutation.clone())),
+                mutation
+            );
+        }
+    }
+}
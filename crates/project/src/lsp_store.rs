@@ -3695,6 +3695,9 @@ impl LspStore {
         }
         cx.observe_global::<SettingsStore>(Self::on_settings_changed)
             .detach();
+        // Schema descriptions are translated into the interface language.
+        cx.observe_global::<localization::LanguageEventChannel>(Self::reload_zed_json_schemas)
+            .detach();
         subscribe_to_binary_statuses(&languages, cx).detach();
 
         let _maintain_workspace_config = {
@@ -3980,6 +3983,12 @@ impl LspStore {
             | extension::Event::ConfigureExtensionRequested(_) => return,
             extension::Event::ExtensionsInstalledChanged => {}
         }
+        self.reload_zed_json_schemas(cx);
+    }
+
+    /// Regenerates the schemas of Zed's own JSON files, such as `settings.json`, and
+    /// sends them to the running JSON language servers.
+    fn reload_zed_json_schemas(&mut self, cx: &mut Context<Self>) {
         if self.as_local().is_none() {
             return;
         }
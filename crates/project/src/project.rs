@@ -7,6 +7,7 @@ pub mod debounced_delay;
 pub mod debugger;
 pub mod git_store;
 pub mod image_store;
+pub mod jj_store;
 pub mod lsp_command;
 pub mod lsp_store;
 mod manifest_tree;
@@ -29,6 +30,7 @@ use context_server_store::ContextServerStore;
 pub use environment::{EnvironmentErrorMessage, ProjectEnvironmentEvent};
 use git::repository::get_git_committer;
 use git_store::{Repository, RepositoryId};
+use jj_store::JujutsuStore;
 pub mod search_history;
 mod yarn;
 
@@ -77,7 +79,7 @@ use gpui::{
     Task, WeakEntity, Window,
 };
 use language::{
-    Buffer, BufferEvent, Capability, CodeLabel, CursorShape, Language, LanguageName,
+    Buffer, BufferEvent, Capability, CodeLabel, CursorShape, Encoding, Language, LanguageName,
     LanguageRegistry, PointUtf16, ToOffset, ToPointUtf16, Toolchain, ToolchainMetadata,
     ToolchainScope, Transaction, Unclipped, language_settings::InlayHintKind,
     proto::split_operations,
@@ -191,6 +193,7 @@ pub struct Project {
     remote_client: Option<Entity<RemoteClient>>,
     client_state: ProjectClientState,
     git_store: Entity<GitStore>,
+    jj_store: Entity<JujutsuStore>,
     collaborators: HashMap<proto::PeerId, Collaborator>,
     client_subscriptions: Vec<client::Subscription>,
     worktree_store: Entity<WorktreeStore>,
@@ -794,6 +797,7 @@ enum EntitySubscription {
     LspStore(PendingEntitySubscription<LspStore>),
     SettingsObserver(PendingEntitySubscription<SettingsObserver>),
     DapStore(PendingEntitySubscription<DapStore>),
+    JujutsuStore(PendingEntitySubscription<JujutsuStore>),
 }
 
 #[derive(Debug, Clone)]
@@ -1028,6 +1032,7 @@ impl Project {
         ToolchainStore::init(&client);
         DapStore::init(&client, cx);
         BreakpointStore::init(&client);
+        JujutsuStore::init(&client);
         context_server_store::init(cx);
     }
 
@@ -1140,10 +1145,13 @@ impl Project {
                 )
             });
 
+            let jj_store = cx.new(|cx| JujutsuStore::local(&worktree_store, fs.clone(), cx));
+
             let git_store = cx.new(|cx| {
                 GitStore::local(
                     &worktree_store,
                     buffer_store.clone(),
+                    jj_store.clone(),
                     environment.clone(),
                     fs.clone(),
                     cx,
@@ -1167,6 +1175,7 @@ impl Project {
                 join_project_response_message_id: 0,
                 client_state: ProjectClientState::Local,
                 git_store,
+                jj_store,
                 client_subscriptions: Vec::new(),
                 _subscriptions: vec![cx.on_release(Self::release)],
                 active_entry: None,
@@ -1307,10 +1316,20 @@ impl Project {
                 )
             });
 
+            let jj_store = cx.new(|cx| {
+                JujutsuStore::remote(
+                    &worktree_store,
+                    remote_proto.clone(),
+                    REMOTE_SERVER_PROJECT_ID,
+                    cx,
+                )
+            });
+
             let git_store = cx.new(|cx| {
                 GitStore::remote(
                     &worktree_store,
                     buffer_store.clone(),
+                    jj_store.clone(),
                     remote_proto.clone(),
                     REMOTE_SERVER_PROJECT_ID,
                     cx,
@@ -1335,6 +1354,7 @@ impl Project {
                 join_project_response_message_id: 0,
                 client_state: ProjectClientState::Local,
                 git_store,
+                jj_store,
                 agent_server_store,
                 client_subscriptions: Vec::new(),
                 _subscriptions: vec![
@@ -1390,6 +1410,7 @@ impl Project {
             remote_proto.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &this.dap_store);
             remote_proto.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &this.settings_observer);
             remote_proto.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &this.git_store);
+            remote_proto.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &this.jj_store);
             remote_proto.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &this.agent_server_store);
 
             remote_proto.add_entity_message_handler(Self::handle_create_buffer_for_peer);
@@ -1406,6 +1427,7 @@ impl Project {
             ToolchainStore::init(&remote_proto);
             DapStore::init(&remote_proto, cx);
             GitStore::init(&remote_proto);
+            JujutsuStore::init(&remote_proto);
             AgentServerStore::init_remote(&remote_proto);
 
             this
@@ -1434,6 +1456,9 @@ impl Project {
                 client.subscribe_to_entity::<SettingsObserver>(remote_id)?,
             ),
             EntitySubscription::DapStore(client.subscribe_to_entity::<DapStore>(remote_id)?),
+            EntitySubscription::JujutsuStore(
+                client.subscribe_to_entity::<JujutsuStore>(remote_id)?,
+            ),
         ];
         let committer = get_git_committer(&cx).await;
         let response = client
@@ -1536,11 +1561,16 @@ impl Project {
             )
         })?;
 
+        let jj_store = cx.new(|cx| {
+            JujutsuStore::remote(&worktree_store, client.clone().into(), remote_id, cx)
+        })?;
+
         let git_store = cx.new(|cx| {
             GitStore::remote(
                 // In this remote case we pass None for the environment
                 &worktree_store,
                 buffer_store.clone(),
+                jj_store.clone(),
                 client.clone().into(),
                 remote_id,
                 cx,
@@ -1609,6 +1639,7 @@ impl Project {
                 breakpoint_store,
                 dap_store: dap_store.clone(),
                 git_store: git_store.clone(),
+                jj_store: jj_store.clone(),
                 agent_server_store,
                 buffers_needing_diff: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
@@ -1665,6 +1696,9 @@ impl Project {
                 EntitySubscription::DapStore(subscription) => {
                     subscription.set_entity(&dap_store, &cx)
                 }
+                EntitySubscription::JujutsuStore(subscription) => {
+                    subscription.set_entity(&jj_store, &cx)
+                }
             })
             .collect::<Vec<_>>();
 
@@ -2279,6 +2313,9 @@ impl Project {
             self.collab_client
                 .subscribe_to_entity(project_id)?
                 .set_entity(&self.git_store, &cx.to_async()),
+            self.collab_client
+                .subscribe_to_entity(project_id)?
+                .set_entity(&self.jj_store, &cx.to_async()),
         ]);
 
         self.buffer_store.update(cx, |buffer_store, cx| {
@@ -2305,6 +2342,9 @@ impl Project {
         self.git_store.update(cx, |git_store, cx| {
             git_store.shared(project_id, self.collab_client.clone().into(), cx)
         });
+        self.jj_store.update(cx, |jj_store, cx| {
+            jj_store.shared(project_id, self.collab_client.clone().into(), cx)
+        });
 
         self.client_state = ProjectClientState::Shared {
             remote_id: project_id,
@@ -2408,6 +2448,9 @@ impl Project {
             self.git_store.update(cx, |git_store, cx| {
                 git_store.unshared(cx);
             });
+            self.jj_store.update(cx, |jj_store, cx| {
+                jj_store.unshared(cx);
+            });
 
             self.collab_client
                 .send(proto::UnshareProject {
@@ -3496,6 +3539,28 @@ impl Project {
         })
     }
 
+    /// Discards the buffer's contents and loads its file again, decoding it with the
+    /// given encoding, which is then also used to save it.
+    pub fn reopen_buffer_with_encoding(
+        &self,
+        buffer: Entity<Buffer>,
+        encoding: Encoding,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let reload = self.buffer_store.update(cx, |buffer_store, cx| {
+            buffer_store.reload_buffers_with_encoding(
+                HashSet::from_iter([buffer]),
+                false,
+                Some(encoding),
+                cx,
+            )
+        });
+        cx.background_spawn(async move {
+            reload.await?;
+            Ok(())
+        })
+    }
+
     pub fn reload_images(
         &self,
         images: HashSet<Entity<ImageItem>>,
@@ -4572,6 +4637,7 @@ impl Project {
                 buffer_store.forget_shared_buffers_for(&collaborator.peer_id);
             });
             this.breakpoint_store.read(cx).broadcast();
+            this.jj_store.read(cx).broadcast(cx);
             cx.emit(Event::CollaboratorJoined(collaborator.peer_id));
             this.collaborators
                 .insert(collaborator.peer_id, collaborator);
@@ -5184,6 +5250,10 @@ impl Project {
         &self.git_store
     }
 
+    pub fn jj_store(&self) -> &Entity<JujutsuStore> {
+        &self.jj_store
+    }
+
     pub fn agent_server_store(&self) -> &Entity<AgentServerStore> {
         &self.agent_server_store
     }
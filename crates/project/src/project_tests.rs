@@ -1,8 +1,8 @@
 #![allow(clippy::format_collect)]
 
 use crate::{
-    Event, git_store::StatusEntry, task_inventory::TaskContexts, task_store::TaskSettingsLocation,
-    *,
+    Event, git_store::StatusEntry, jj_store::JujutsuStoreEvent, task_inventory::TaskContexts,
+    task_store::TaskSettingsLocation, *,
 };
 use async_trait::async_trait;
 use buffer_diff::{
@@ -20,9 +20,10 @@ use git2::RepositoryInitOptions;
 use gpui::{App, BackgroundExecutor, SemanticVersion, UpdateGlobal};
 use itertools::Itertools;
 use language::{
-    Diagnostic, DiagnosticEntry, DiagnosticSet, DiagnosticSourceKind, DiskState, FakeLspAdapter,
-    LanguageConfig, LanguageMatcher, LanguageName, LineEnding, ManifestName, ManifestProvider,
-    ManifestQuery, OffsetRangeExt, Point, ToPoint, ToolchainList, ToolchainLister,
+    Diagnostic, DiagnosticEntry, DiagnosticSet, DiagnosticSourceKind, DiskState, Encoding,
+    FakeLspAdapter, LanguageConfig, LanguageMatcher, LanguageName, LineEnding, ManifestName,
+    ManifestProvider, ManifestQuery, OffsetRangeExt, Point, ToPoint, ToolchainList,
+    ToolchainLister,
     language_settings::{LanguageSettingsContent, language_settings},
     tree_sitter_rust, tree_sitter_typescript,
 };
@@ -4723,6 +4724,90 @@ async fn test_buffer_line_endings(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_buffer_encodings(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/dir"), json!({})).await;
+    fs.insert_file(
+        path!("/dir/gbk.txt"),
+        Encoding::GBK.encode("用户管理模块的说明\n").unwrap(),
+    )
+    .await;
+    fs.insert_file(path!("/dir/bom.txt"), b"\xEF\xBB\xBFbom\n".to_vec())
+        .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let gbk_buffer = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/gbk.txt"), cx))
+        .await
+        .unwrap();
+    let bom_buffer = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/bom.txt"), cx))
+        .await
+        .unwrap();
+
+    gbk_buffer.update(cx, |buffer, _| {
+        assert_eq!(buffer.text(), "用户管理模块的说明\n");
+        assert_eq!(buffer.encoding(), Encoding::GBK);
+        assert!(buffer.encoding_guessed());
+    });
+    bom_buffer.update(cx, |buffer, _| {
+        assert_eq!(buffer.text(), "bom\n");
+        assert_eq!(buffer.encoding(), Encoding::UTF_8_BOM);
+        assert!(!buffer.encoding_guessed());
+    });
+
+    // Saving keeps the encoding and byte order mark the file was loaded with.
+    gbk_buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "新")], None, cx));
+    project
+        .update(cx, |project, cx| {
+            project.save_buffer(gbk_buffer.clone(), cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        fs.read_file_sync(path!("/dir/gbk.txt")).unwrap(),
+        Encoding::GBK.encode("新用户管理模块的说明\n").unwrap(),
+    );
+    bom_buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "a ")], None, cx));
+    project
+        .update(cx, |project, cx| project.save_buffer(bom_buffer, cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        fs.read_file_sync(path!("/dir/bom.txt")).unwrap(),
+        b"\xEF\xBB\xBFa bom\n"
+    );
+
+    // Text that the encoding can't represent fails to save.
+    gbk_buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "😀")], None, cx));
+    project
+        .update(cx, |project, cx| {
+            project.save_buffer(gbk_buffer.clone(), cx)
+        })
+        .await
+        .unwrap_err();
+
+    // Reopening with another encoding decodes the file again and saves it that way.
+    project
+        .update(cx, |project, cx| {
+            project.reopen_buffer_with_encoding(gbk_buffer.clone(), Encoding::BIG5, cx)
+        })
+        .await
+        .unwrap();
+    gbk_buffer.update(cx, |buffer, _| {
+        assert_eq!(buffer.encoding(), Encoding::BIG5);
+        assert!(!buffer.encoding_guessed());
+        assert_eq!(
+            buffer.text(),
+            Encoding::BIG5.decode(&Encoding::GBK.encode("新用户管理模块的说明\n").unwrap())
+        );
+        assert!(!buffer.is_dirty());
+    });
+}
+
 #[gpui::test]
 async fn test_grouped_diagnostics(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -6943,6 +7028,373 @@ async fn test_unstaged_diff_for_buffer(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_jujutsu_diff_for_buffer(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let parent_contents = r#"
+        fn main() {
+            println!("hello world");
+        }
+    "#
+    .unindent();
+    let file_contents = r#"
+        // print goodbye
+        fn main() {
+            println!("goodbye world");
+        }
+    "#
+    .unindent();
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        "/dir",
+        json!({
+           "src": {
+               "main.rs": file_contents,
+               "lib.rs": "",
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    project.update(cx, |project, cx| {
+        let worktree_id = project.worktrees(cx).next().unwrap().read(cx).id();
+        project.jj_store().update(cx, |jj_store, cx| {
+            jj_store.add_local_repository(
+                worktree_id,
+                Arc::new(jj::FakeJujutsuRepository {
+                    workspace_root: PathBuf::from("/dir"),
+                    status: std::sync::Mutex::new(jj::WorkingCopyStatus {
+                        operation_id: "0".into(),
+                        changes: vec![jj::WorkingCopyChange {
+                            path: Path::new("src/main.rs").into(),
+                            kind: jj::ChangeKind::Modified,
+                        }],
+                    }),
+                    parent_texts: std::sync::Mutex::new(HashMap::from_iter([(
+                        Path::new("src/main.rs").into(),
+                        parent_contents.clone(),
+                    )])),
+                    ..Default::default()
+                }),
+                cx,
+            )
+        })
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer("/dir/src/main.rs", cx)
+        })
+        .await
+        .unwrap();
+    let uncommitted_diff = project
+        .update(cx, |project, cx| {
+            project.open_uncommitted_diff(buffer.clone(), cx)
+        })
+        .await
+        .unwrap();
+
+    cx.run_until_parked();
+    uncommitted_diff.update(cx, |uncommitted_diff, cx| {
+        let snapshot = buffer.read(cx).snapshot();
+        assert_hunks(
+            uncommitted_diff.hunks(&snapshot, cx),
+            &snapshot,
+            &uncommitted_diff.base_text_string().unwrap(),
+            &[
+                (
+                    0..1,
+                    "",
+                    "// print goodbye\n",
+                    DiffHunkStatus::added(DiffHunkSecondaryStatus::HasSecondaryHunk),
+                ),
+                (
+                    2..3,
+                    "    println!(\"hello world\");\n",
+                    "    println!(\"goodbye world\");\n",
+                    DiffHunkStatus::modified(DiffHunkSecondaryStatus::HasSecondaryHunk),
+                ),
+            ],
+        );
+    });
+
+    let worktree_id = project.read_with(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    project.read_with(cx, |project, cx| {
+        let status = |path: &str| {
+            project.project_path_git_status(
+                &ProjectPath {
+                    worktree_id,
+                    path: Path::new(path).into(),
+                },
+                cx,
+            )
+        };
+        assert_eq!(
+            status("src/main.rs"),
+            Some(
+                TrackedStatus {
+                    index_status: StatusCode::Unmodified,
+                    worktree_status: StatusCode::Modified,
+                }
+                .into()
+            )
+        );
+        assert_eq!(status("src/lib.rs"), None);
+    });
+}
+
+#[gpui::test]
+async fn test_jujutsu_diff_base_reloaded_by_every_operation(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree("/dir", json!({ "main.rs": "three\n" }))
+        .await;
+
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    let backend = Arc::new(jj::FakeJujutsuRepository {
+        workspace_root: PathBuf::from("/dir"),
+        status: std::sync::Mutex::new(jj::WorkingCopyStatus {
+            operation_id: "0".into(),
+            changes: vec![jj::WorkingCopyChange {
+                path: Path::new("main.rs").into(),
+                kind: jj::ChangeKind::Modified,
+            }],
+        }),
+        parent_texts: std::sync::Mutex::new(HashMap::from_iter([(
+            Path::new("main.rs").into(),
+            "one\n".to_string(),
+        )])),
+        ..Default::default()
+    });
+    project.update(cx, |project, cx| {
+        let worktree_id = project.worktrees(cx).next().unwrap().read(cx).id();
+        project.jj_store().update(cx, |jj_store, cx| {
+            jj_store.add_local_repository(worktree_id, backend.clone(), cx);
+        });
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer("/dir/main.rs", cx)
+        })
+        .await
+        .unwrap();
+    let uncommitted_diff = project
+        .update(cx, |project, cx| {
+            project.open_uncommitted_diff(buffer.clone(), cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    uncommitted_diff.read_with(cx, |uncommitted_diff, _| {
+        assert_eq!(uncommitted_diff.base_text_string().unwrap(), "one\n");
+    });
+
+    // Editing another commit moves `@-`, though the same file still differs from it.
+    *backend.parent_texts.lock().unwrap() =
+        HashMap::from_iter([(Path::new("main.rs").into(), "two\n".to_string())]);
+    backend.status.lock().unwrap().operation_id = "1".into();
+    fs.insert_tree("/dir/.jj/repo/op_heads/heads", json!({ "1": "" }))
+        .await;
+    cx.run_until_parked();
+
+    uncommitted_diff.read_with(cx, |uncommitted_diff, _| {
+        assert_eq!(uncommitted_diff.base_text_string().unwrap(), "two\n");
+    });
+}
+
+#[gpui::test]
+async fn test_jujutsu_repository_shared_by_worktrees(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            "a": { "a.rs": "" },
+            "b": { "b.rs": "" },
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/dir/a".as_ref(), "/dir/b".as_ref()], cx).await;
+    let worktree_ids = project.update(cx, |project, cx| {
+        let worktree_ids = project
+            .worktrees(cx)
+            .map(|worktree| worktree.read(cx).id())
+            .collect::<Vec<_>>();
+        project.jj_store().update(cx, |jj_store, cx| {
+            for &worktree_id in &worktree_ids {
+                jj_store.add_local_repository(
+                    worktree_id,
+                    Arc::new(jj::FakeJujutsuRepository {
+                        workspace_root: PathBuf::from("/dir"),
+                        ..Default::default()
+                    }),
+                    cx,
+                );
+            }
+        });
+        worktree_ids
+    });
+
+    project.read_with(cx, |project, cx| {
+        let jj_store = project.jj_store().read(cx);
+        assert_eq!(jj_store.repositories().len(), 1);
+        assert_eq!(
+            jj_store.repository_for_worktree(worktree_ids[0]),
+            jj_store.repository_for_worktree(worktree_ids[1])
+        );
+    });
+
+    project.update(cx, |project, cx| {
+        project.remove_worktree(worktree_ids[0], cx);
+    });
+    project.read_with(cx, |project, cx| {
+        let jj_store = project.jj_store().read(cx);
+        assert_eq!(jj_store.repositories().len(), 1);
+        assert!(jj_store.repository_for_worktree(worktree_ids[0]).is_none());
+        assert!(jj_store.repository_for_worktree(worktree_ids[1]).is_some());
+    });
+
+    project.update(cx, |project, cx| {
+        project.remove_worktree(worktree_ids[1], cx);
+    });
+    project.read_with(cx, |project, cx| {
+        assert!(project.jj_store().read(cx).repositories().is_empty());
+    });
+}
+
+#[gpui::test]
+async fn test_jujutsu_repository_updated_by_every_operation(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree("/dir", json!({ "a.rs": "" })).await;
+
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    let backend = Arc::new(jj::FakeJujutsuRepository {
+        workspace_root: PathBuf::from("/dir"),
+        status: std::sync::Mutex::new(jj::WorkingCopyStatus {
+            operation_id: "0".into(),
+            changes: vec![jj::WorkingCopyChange {
+                path: Path::new("a.rs").into(),
+                kind: jj::ChangeKind::Added,
+            }],
+        }),
+        ..Default::default()
+    });
+    let jj_store = project.read_with(cx, |project, _| project.jj_store().clone());
+    jj_store.update(cx, |jj_store, cx| {
+        let worktree_id = project.read(cx).worktrees(cx).next().unwrap().read(cx).id();
+        jj_store.add_local_repository(worktree_id, backend.clone(), cx);
+    });
+    cx.run_until_parked();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let _subscription = cx.update(|cx| {
+        let events = events.clone();
+        cx.subscribe(&jj_store, move |_, event: &JujutsuStoreEvent, _| {
+            events.lock().push(*event);
+        })
+    });
+
+    // Describing a commit creates an operation without changing any files.
+    backend.status.lock().unwrap().operation_id = "1".into();
+    fs.insert_tree("/dir/.jj/repo/op_heads/heads", json!({ "1": "" }))
+        .await;
+    cx.run_until_parked();
+
+    let repository = jj_store.read_with(cx, |jj_store, _| {
+        jj_store.repositories().values().next().unwrap().clone()
+    });
+    repository.read_with(cx, |repository, _| {
+        assert_eq!(repository.operation_id, "1");
+        assert_eq!(
+            *events.lock(),
+            [JujutsuStoreEvent::RepositoryUpdated(repository.id)]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_jujutsu_repository_operations(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.background_executor.clone());
+    fs.insert_tree("/dir", json!({ "a.rs": "" })).await;
+
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    let operation = |id: &str, description: &str| jj::OperationLogEntry {
+        id: id.to_string().into(),
+        description: description.to_string().into(),
+        timestamp: 0,
+        user: "user@host".into(),
+        is_root: id == "0",
+    };
+    let operation_diff = jj::OperationDiff {
+        bookmark_changes: vec![jj::BookmarkChange {
+            name: "main".into(),
+            old_target: None,
+            new_target: Some("abc".into()),
+        }],
+        ..Default::default()
+    };
+    let backend = Arc::new(jj::FakeJujutsuRepository {
+        workspace_root: PathBuf::from("/dir"),
+        operations: vec![
+            operation("1", "create bookmark main"),
+            operation("0", "root"),
+        ],
+        operation_diffs: HashMap::from_iter([("1".to_string(), operation_diff.clone())]),
+        ..Default::default()
+    });
+    let jj_store = project.read_with(cx, |project, _| project.jj_store().clone());
+    let repository = jj_store.update(cx, |jj_store, cx| {
+        let worktree_id = project.read(cx).worktrees(cx).next().unwrap().read(cx).id();
+        jj_store.add_local_repository(worktree_id, backend.clone(), cx);
+        jj_store.repositories().values().next().unwrap().clone()
+    });
+    cx.run_until_parked();
+
+    let operations = repository
+        .read_with(cx, |repository, cx| repository.operation_log(1, cx))
+        .await
+        .unwrap();
+    assert_eq!(operations, [operation("1", "create bookmark main")]);
+    let diff = repository
+        .read_with(cx, |repository, cx| {
+            repository.operation_diff("1".into(), 10, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(diff, operation_diff);
+
+    repository
+        .update(cx, |repository, cx| {
+            repository.mutate(
+                jj::Mutation::UndoOperation {
+                    operation_id: "1".into(),
+                },
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        *backend.mutations.lock().unwrap(),
+        [jj::Mutation::UndoOperation {
+            operation_id: "1".into(),
+        }]
+    );
+}
+
 #[gpui::test]
 async fn test_uncommitted_diff_for_buffer(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -5805,8 +5805,8 @@ impl Panel for ProjectPanel {
             .then_some(IconName::FileTree)
     }
 
-    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<&'static str> {
-        Some("Project Panel")
+    fn icon_tooltip(&self, _window: &Window, _cx: &App) -> Option<SharedString> {
+        Some(localization::t!("project_panel.tooltip", "Project Panel"))
     }
 
     fn toggle_action(&self) -> Box<dyn Action> {
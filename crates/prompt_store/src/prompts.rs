@@ -51,6 +51,8 @@ impl ProjectContext {
 #[derive(Debug, Clone, Serialize)]
 pub struct ModelContext {
     pub available_tools: Vec<String>,
+    /// The name of the language to respond in, if the user asked for one.
+    pub response_language: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -105,6 +107,7 @@ pub struct ContentPromptContext {
     pub user_prompt: String,
     pub rewrite_section: Option<String>,
     pub diagnostic_errors: Vec<ContentPromptDiagnosticContext>,
+    pub response_language: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -342,6 +345,7 @@ impl PromptBuilder {
         language_name: Option<&LanguageName>,
         buffer: BufferSnapshot,
         range: Range<usize>,
+        response_language: Option<String>,
     ) -> Result<String, RenderError> {
         let content_type = match language_name.as_ref().map(|l| l.as_ref()) {
             None | Some("Markdown" | "Plain Text") => "text",
@@ -417,6 +421,7 @@ impl PromptBuilder {
             user_prompt,
             rewrite_section,
             diagnostic_errors,
+            response_language,
         };
         self.handlebars.lock().render("content_prompt", &context)
     }
@@ -468,6 +473,7 @@ mod test {
         let project_context = ProjectContext::new(worktrees, default_user_rules);
         let model_context = ModelContext {
             available_tools: ["grep".into()].to_vec(),
+            response_language: None,
         };
         let prompt = PromptBuilder::new(None)
             .unwrap()
@@ -493,6 +499,7 @@ mod test {
         // When the `grep` tool is enabled, it should be mentioned in the prompt
         let model_context = ModelContext {
             available_tools: ["grep".into()].to_vec(),
+            response_language: None,
         };
         let prompt_with_grep = prompt_builder
             .generate_assistant_system_prompt(&project_context, &model_context)
@@ -505,6 +512,7 @@ mod test {
         // When the `grep` tool is disabled, it should not be mentioned in the prompt
         let model_context = ModelContext {
             available_tools: [].to_vec(),
+            response_language: None,
         };
         let prompt_without_grep = prompt_builder
             .generate_assistant_system_prompt(&project_context, &model_context)
@@ -515,6 +523,30 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_assistant_system_prompt_response_language() {
+        let project_context = ProjectContext::new(Vec::new(), Vec::new());
+        let prompt_builder = PromptBuilder::new(None).unwrap();
+
+        let model_context = ModelContext {
+            available_tools: Vec::new(),
+            response_language: Some("Japanese (日本語)".into()),
+        };
+        let prompt = prompt_builder
+            .generate_assistant_system_prompt(&project_context, &model_context)
+            .unwrap();
+        assert!(prompt.contains("Respond to the user in Japanese (日本語)"));
+
+        let model_context = ModelContext {
+            available_tools: Vec::new(),
+            response_language: None,
+        };
+        let prompt = prompt_builder
+            .generate_assistant_system_prompt(&project_context, &model_context)
+            .unwrap();
+        assert!(!prompt.contains("## Response Language"));
+    }
+
     #[test]
     fn test_has_tool_helper() {
         let mut handlebars = Handlebars::new();
@@ -324,6 +324,19 @@ messages!(
     (ExternalAgentsUpdated, Background),
     (ExternalAgentLoadingStatusUpdated, Background),
     (NewExternalAgentVersionAvailable, Background),
+    (UpdateJujutsuRepository, Foreground),
+    (RemoveJujutsuRepository, Foreground),
+    (JujutsuGetBookmarks, Background),
+    (JujutsuBookmarksResponse, Background),
+    (JujutsuLog, Background),
+    (JujutsuLogResponse, Background),
+    (JujutsuLoadCommitDiff, Background),
+    (JujutsuCommitDiffResponse, Background),
+    (JujutsuMutate, Background),
+    (JujutsuOperationLog, Background),
+    (JujutsuOperationLogResponse, Background),
+    (JujutsuLoadOperationDiff, Background),
+    (JujutsuOperationDiffResponse, Background),
 );
 
 request_messages!(
@@ -497,7 +510,13 @@ request_messages!(
     (GitClone, GitCloneResponse),
     (ToggleLspLogs, Ack),
     (GetProcesses, GetProcessesResponse),
-    (GetAgentServerCommand, AgentServerCommand)
+    (GetAgentServerCommand, AgentServerCommand),
+    (JujutsuGetBookmarks, JujutsuBookmarksResponse),
+    (JujutsuLog, JujutsuLogResponse),
+    (JujutsuLoadCommitDiff, JujutsuCommitDiffResponse),
+    (JujutsuMutate, Ack),
+    (JujutsuOperationLog, JujutsuOperationLogResponse),
+    (JujutsuLoadOperationDiff, JujutsuOperationDiffResponse),
 );
 
 lsp_messages!(
@@ -657,6 +676,14 @@ entity_messages!(
     ExternalAgentsUpdated,
     ExternalAgentLoadingStatusUpdated,
     NewExternalAgentVersionAvailable,
+    UpdateJujutsuRepository,
+    RemoveJujutsuRepository,
+    JujutsuGetBookmarks,
+    JujutsuLog,
+    JujutsuLoadCommitDiff,
+    JujutsuMutate,
+    JujutsuOperationLog,
+    JujutsuLoadOperationDiff,
 );
 
 entity_messages!(
@@ -16,6 +16,7 @@ use project::{
     buffer_store::{BufferStore, BufferStoreEvent},
     debugger::{breakpoint_store::BreakpointStore, dap_store::DapStore},
     git_store::GitStore,
+    jj_store::JujutsuStore,
     lsp_store::log_store::{self, GlobalLogStore, LanguageServerKind},
     project_settings::SettingsObserver,
     search::SearchQuery,
@@ -51,6 +52,7 @@ pub struct HeadlessProject {
     pub languages: Arc<LanguageRegistry>,
     pub extensions: Entity<HeadlessExtensionStore>,
     pub git_store: Entity<GitStore>,
+    pub jj_store: Entity<JujutsuStore>,
     // Used mostly to keep alive the toolchain store for RPC handlers.
     // Local variant is used within LSP store, but that's a separate entity.
     pub _toolchain_store: Entity<ToolchainStore>,
@@ -129,10 +131,17 @@ impl HeadlessProject {
             dap_store
         });
 
+        let jj_store = cx.new(|cx| {
+            let mut store = JujutsuStore::local(&worktree_store, fs.clone(), cx);
+            store.shared(REMOTE_SERVER_PROJECT_ID, session.clone(), cx);
+            store
+        });
+
         let git_store = cx.new(|cx| {
             let mut store = GitStore::local(
                 &worktree_store,
                 buffer_store.clone(),
+                jj_store.clone(),
                 environment.clone(),
                 fs.clone(),
                 cx,
@@ -234,6 +243,7 @@ impl HeadlessProject {
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &dap_store);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &settings_observer);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &git_store);
+        session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &jj_store);
         session.subscribe_to_entity(REMOTE_SERVER_PROJECT_ID, &agent_server_store);
 
         session.add_request_handler(cx.weak_entity(), Self::handle_list_remote_directory);
@@ -273,6 +283,7 @@ impl HeadlessProject {
         // todo(debugger): Re init breakpoint store when we set it up for collab
         // BreakpointStore::init(&client);
         GitStore::init(&session);
+        JujutsuStore::init(&session);
         AgentServerStore::init_headless(&session);
 
         HeadlessProject {
@@ -289,6 +300,7 @@ impl HeadlessProject {
             languages,
             extensions,
             git_store,
+            jj_store,
             _toolchain_store: toolchain_store,
         }
     }
@@ -14,7 +14,8 @@ use fs::{FakeFs, Fs};
 use gpui::{AppContext as _, Entity, SemanticVersion, TestAppContext};
 use http_client::{BlockedHttpClient, FakeHttpClient};
 use language::{
-    Buffer, FakeLspAdapter, LanguageConfig, LanguageMatcher, LanguageRegistry, LineEnding,
+    Buffer, Encoding, FakeLspAdapter, LanguageConfig, LanguageMatcher, LanguageRegistry,
+    LineEnding,
     language_settings::{AllLanguageSettings, language_settings},
 };
 use lsp::{CompletionContext, CompletionResponse, CompletionTriggerKind, LanguageServerName};
@@ -166,6 +167,82 @@ async fn test_basic_remote_editing(cx: &mut TestAppContext, server_cx: &mut Test
     });
 }
 
+#[gpui::test]
+async fn test_remote_buffer_encodings(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(path!("/code/project1"), json!({})).await;
+    fs.insert_file(
+        path!("/code/project1/readme.txt"),
+        Encoding::SHIFT_JIS
+            .encode("設定ファイルを読み込んで、ユーザーの一覧を表示します。\n")
+            .unwrap(),
+    )
+    .await;
+
+    let (project, headless) = init_test(&fs, cx, server_cx).await;
+    let (worktree, _) = project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(path!("/code/project1"), true, cx)
+        })
+        .await
+        .unwrap();
+    let worktree_id = worktree.read_with(cx, |worktree, _| worktree.id());
+
+    // The client sees the encoding that the server detected.
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, Path::new("readme.txt")), cx)
+        })
+        .await
+        .unwrap();
+    buffer.update(cx, |buffer, _| {
+        assert_eq!(
+            buffer.text(),
+            "設定ファイルを読み込んで、ユーザーの一覧を表示します。\n"
+        );
+        assert_eq!(buffer.encoding(), Encoding::SHIFT_JIS);
+    });
+
+    // Changing the encoding on the client changes how the server saves the file.
+    buffer.update(cx, |buffer, cx| {
+        buffer.set_encoding(Encoding::UTF_16LE, cx);
+    });
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        fs.read_file_sync(path!("/code/project1/readme.txt"))
+            .unwrap(),
+        Encoding::UTF_16LE
+            .encode("設定ファイルを読み込んで、ユーザーの一覧を表示します。\n")
+            .unwrap()
+    );
+
+    // Reopening the file with another encoding decodes it again on the server.
+    project
+        .update(cx, |project, cx| {
+            project.reopen_buffer_with_encoding(buffer.clone(), Encoding::UTF_16BE, cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    let server_buffer = headless.update(server_cx, |headless, cx| {
+        headless
+            .buffer_store
+            .read(cx)
+            .get_by_path(&(worktree_id, Path::new("readme.txt")).into())
+            .unwrap()
+    });
+    server_buffer.update(server_cx, |buffer, _| {
+        assert_eq!(buffer.encoding(), Encoding::UTF_16BE);
+    });
+    buffer.update(cx, |buffer, _| {
+        assert_eq!(buffer.encoding(), Encoding::UTF_16BE);
+        assert_eq!(buffer.text(), server_buffer.read(server_cx).text());
+    });
+}
+
 #[gpui::test]
 async fn test_remote_project_search(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
     let fs = FakeFs::new(server_cx.executor());
@@ -23,6 +23,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use settings_macros::MergeFrom;
+use std::borrow::Cow;
 use std::collections::BTreeSet;
 use std::env;
 use std::sync::Arc;
@@ -51,6 +52,12 @@ pub struct SettingsContent {
     /// Preferred language for Zed's user interface.
     pub ui_language: Option<UiLanguagePreference>,
 
+    /// Whether fuzzy finders also match Chinese text by its pinyin, spelled out or
+    /// by initials, e.g. `yhgl` or `yonghu` for `用户管理.rs`.
+    ///
+    /// Default: true
+    pub pinyin_matching: Option<bool>,
+
     #[serde(flatten)]
     pub remote: RemoteSettingsContent,
 
@@ -59,6 +66,9 @@ pub struct SettingsContent {
 
     pub git_panel: Option<GitPanelSettingsContent>,
 
+    /// Settings related to the Jujutsu log panel.
+    pub jj_panel: Option<JjPanelSettingsContent>,
+
     pub tabs: Option<ItemSettingsContent>,
     pub tab_bar: Option<TabBarSettingsContent>,
 
@@ -169,20 +179,70 @@ impl SettingsContent {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, MergeFrom)]
-#[serde(rename_all = "kebab-case")]
+/// Preferred language for Zed's user interface.
+///
+/// Either `"auto"` to follow the operating system, or a BCP-47 language tag such as
+/// `"zh-CN"`, `"zh-Hant"`, `"ja"` or `"en"`. Which languages are available depends on
+/// the installed translation catalogs.
+#[derive(Debug, Clone, PartialEq, Eq, MergeFrom)]
 pub enum UiLanguagePreference {
-    #[serde(alias = "auto")]
     Auto,
-    #[serde(alias = "en")]
-    #[serde(alias = "en-us")]
-    #[serde(alias = "en-US")]
-    English,
-    #[serde(alias = "zh")]
-    #[serde(alias = "zh-hans")]
-    #[serde(alias = "zh-cn")]
-    #[serde(alias = "zh-CN")]
-    SimplifiedChinese,
+    Locale(String),
+}
+
+impl UiLanguagePreference {
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        match value.to_ascii_lowercase().as_str() {
+            "" => None,
+            "auto" | "system" | "follow-system" => Some(Self::Auto),
+            // Names used before the interface language was keyed by language tag.
+            "english" => Some(Self::Locale("en".into())),
+            "simplified-chinese" => Some(Self::Locale("zh-CN".into())),
+            _ => Some(Self::Locale(value.to_string())),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Auto => "auto",
+            Self::Locale(tag) => tag,
+        }
+    }
+}
+
+impl Serialize for UiLanguagePreference {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for UiLanguagePreference {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(&value)
+            .ok_or_else(|| serde::de::Error::custom("expected \"auto\" or a language tag"))
+    }
+}
+
+impl JsonSchema for UiLanguagePreference {
+    fn schema_name() -> Cow<'static, str> {
+        "UiLanguagePreference".into()
+    }
+
+    /// This schema will be replaced with the installed languages in
+    /// `SettingsStore::json_schema`.
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "string"
+        })
+    }
 }
 
 #[skip_serializing_none]
@@ -479,6 +539,29 @@ pub struct GitPanelSettingsContent {
     pub collapse_untracked_diff: Option<bool>,
 }
 
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug)]
+pub struct JjPanelSettingsContent {
+    /// Whether to show the panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the panel.
+    ///
+    /// Default: left
+    pub dock: Option<DockPosition>,
+    /// Default width of the panel in pixels.
+    ///
+    /// Default: 360
+    pub default_width: Option<f32>,
+    /// The revset whose commits the panel lists, in jj's revset language.
+    /// When unset, it's the revset `jj log` shows: the repository's
+    /// `revsets.log` config, if set.
+    ///
+    /// Default: null
+    pub log_revset: Option<String>,
+}
+
 #[derive(
     Default, Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq,
 )]
@@ -594,6 +677,8 @@ pub struct VimSettingsContent {
     pub custom_digraphs: Option<HashMap<String, Arc<str>>>,
     pub highlight_on_yank_duration: Option<u64>,
     pub cursor_shape: Option<CursorShapeSettings>,
+    /// Switching of the system input method when leaving and re-entering insert mode.
+    pub input_method: Option<VimInputMethodSettingsContent>,
 }
 
 #[derive(Copy, Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Debug)]
@@ -639,6 +724,46 @@ pub struct CursorShapeSettings {
     pub insert: Option<CursorShape>,
 }
 
+/// Settings for switching the system input method as vim mode leaves and re-enters insert
+/// mode, so that keystrokes in normal mode don't go to an input method's candidate window.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema, MergeFrom)]
+pub struct VimInputMethodSettingsContent {
+    /// Which input method framework to control.
+    ///
+    /// Default: none
+    pub backend: Option<InputMethodBackendContent>,
+    /// The input method to activate outside insert mode. The one that was active when
+    /// leaving insert mode is restored when entering it again.
+    ///
+    /// Default: "keyboard-us" with fcitx5 and "xkb:us::eng" with IBus
+    pub normal_mode_input_method: Option<String>,
+    /// The command that prints the identifier of the active input method, for the
+    /// `command` backend, e.g. `["im-select"]` or `["fcitx5-remote", "-n"]`.
+    pub query_command: Option<Vec<String>>,
+    /// The command that activates an input method, for the `command` backend. The
+    /// identifier of the input method is passed as its last argument, e.g. `["im-select"]`
+    /// or `["fcitx5-remote", "-s"]`.
+    pub switch_command: Option<Vec<String>>,
+}
+
+/// The input method framework controlled by vim mode.
+#[derive(
+    Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema, MergeFrom,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum InputMethodBackendContent {
+    /// Leave the input method alone.
+    #[default]
+    None,
+    /// Fcitx 5, over D-Bus. Linux only.
+    Fcitx5,
+    /// IBus, over D-Bus. Linux only.
+    Ibus,
+    /// External commands given by `query_command` and `switch_command`, such as `im-select`.
+    Command,
+}
+
 /// Settings specific to journaling
 #[skip_serializing_none]
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
@@ -890,14 +1015,31 @@ mod tests {
     use super::*;
 
     #[test]
-    fn ui_language_accepts_uppercase_aliases() {
+    fn ui_language_accepts_language_tags() {
         assert_eq!(
             serde_json::from_str::<UiLanguagePreference>("\"zh-CN\"").unwrap(),
-            UiLanguagePreference::SimplifiedChinese
+            UiLanguagePreference::Locale("zh-CN".into())
         );
         assert_eq!(
             serde_json::from_str::<UiLanguagePreference>("\"en-US\"").unwrap(),
-            UiLanguagePreference::English
+            UiLanguagePreference::Locale("en-US".into())
+        );
+        assert_eq!(
+            serde_json::from_str::<UiLanguagePreference>("\"ja\"").unwrap(),
+            UiLanguagePreference::Locale("ja".into())
+        );
+        assert_eq!(
+            serde_json::from_str::<UiLanguagePreference>("\"Auto\"").unwrap(),
+            UiLanguagePreference::Auto
+        );
+        assert_eq!(
+            serde_json::from_str::<UiLanguagePreference>("\"simplified-chinese\"").unwrap(),
+            UiLanguagePreference::Locale("zh-CN".into())
+        );
+        assert!(serde_json::from_str::<UiLanguagePreference>("\"\"").is_err());
+        assert_eq!(
+            serde_json::to_string(&UiLanguagePreference::Auto).unwrap(),
+            "\"auto\""
         );
     }
 }
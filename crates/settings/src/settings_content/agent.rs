@@ -6,7 +6,7 @@ use serde_with::skip_serializing_none;
 use settings_macros::MergeFrom;
 use std::{borrow::Cow, path::PathBuf, sync::Arc};
 
-use crate::DockPosition;
+use crate::{DockPosition, UiLanguagePreference};
 
 #[skip_serializing_none]
 #[derive(Clone, PartialEq, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, Default)]
@@ -109,6 +109,13 @@ pub struct AgentSettingsContent {
     ///
     /// Default: 4
     pub message_editor_min_lines: Option<usize>,
+    /// The language the agent, the inline assistant and commit message generation respond in.
+    ///
+    /// Either `"auto"` to follow the interface language, or a BCP-47 language tag such
+    /// as `"zh-CN"`, `"ja"` or `"en"`. Profiles can override it.
+    ///
+    /// Default: auto
+    pub response_language: Option<UiLanguagePreference>,
 }
 
 impl AgentSettingsContent {
@@ -178,6 +185,9 @@ pub struct AgentProfileContent {
     pub enable_all_context_servers: Option<bool>,
     #[serde(default)]
     pub context_servers: IndexMap<Arc<str>, ContextServerPresetContent>,
+    /// The language the agent responds in when using this profile, overriding
+    /// `agent.response_language`.
+    pub response_language: Option<UiLanguagePreference>,
 }
 
 #[skip_serializing_none]
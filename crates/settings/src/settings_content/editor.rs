@@ -131,6 +131,11 @@ pub struct EditorSettingsContent {
     ///
     /// Default: select
     pub double_click_in_multibuffer: Option<DoubleClickInMultibuffer>,
+    /// Whether word movements, double-click and word selections split Chinese and
+    /// Japanese text into words, instead of treating a whole sentence as one word.
+    ///
+    /// Default: true
+    pub cjk_word_segmentation: Option<bool>,
     /// Whether the editor search results will loop
     ///
     /// Default: true
@@ -201,6 +206,10 @@ pub struct StatusBarContent {
     ///
     /// Default: true
     pub active_language_button: Option<bool>,
+    /// Whether to show the active buffer's encoding button in the status bar.
+    ///
+    /// Default: true
+    pub active_encoding_button: Option<bool>,
     /// Whether to show the cursor position button in the status bar.
     ///
     /// Default: true
@@ -12,6 +12,7 @@ pub struct SettingsJsonSchemaParams<'a> {
     pub font_names: &'a [String],
     pub theme_names: &'a [SharedString],
     pub icon_theme_names: &'a [SharedString],
+    pub ui_language_names: &'a [String],
 }
 
 pub fn update_value_in_json_text<'a>(
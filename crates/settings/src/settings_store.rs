@@ -31,8 +31,8 @@ pub type EditorconfigProperties = ec4rs::Properties;
 
 use crate::{
     ActiveSettingsProfileName, FontFamilyName, IconThemeName, LanguageSettingsContent,
-    LanguageToSettingsMap, SettingsJsonSchemaParams, SettingsUiEntry, ThemeName, VsCodeSettings,
-    WorktreeId,
+    LanguageToSettingsMap, SettingsJsonSchemaParams, SettingsUiEntry, ThemeName,
+    UiLanguagePreference, VsCodeSettings, WorktreeId,
     merge_from::MergeFrom,
     parse_json_with_comments, replace_value_in_json_text,
     settings_content::{
@@ -816,6 +816,24 @@ impl SettingsStore {
             })
         });
 
+        replace_subschema::<UiLanguagePreference>(&mut generator, || {
+            let mut language_names = vec!["auto".to_string()];
+            language_names.extend(params.ui_language_names.iter().cloned());
+            // List the installed languages as suggestions, while still
+            // accepting any language tag, like the parser does.
+            json_schema!({
+                "anyOf": [
+                    {
+                        "type": "string",
+                        "enum": language_names,
+                    },
+                    {
+                        "type": "string",
+                    }
+                ]
+            })
+        });
+
         generator
             .root_schema_for::<UserSettingsContent>()
             .to_value()
@@ -4,8 +4,10 @@ use client::telemetry;
 pub use gpui::GpuSpecs;
 use gpui::{App, AppContext as _, SemanticVersion, Task, Window, actions};
 use human_bytes::human_bytes;
+use localization::LocalizationSettings;
 use release_channel::{AppCommitSha, AppVersion, ReleaseChannel};
 use serde::Serialize;
+use settings::Settings as _;
 use std::{env, fmt::Display};
 use sysinfo::{MemoryRefreshKind, RefreshKind, System};
 
@@ -28,6 +30,7 @@ pub struct SystemSpecs {
     commit_sha: Option<String>,
     bundle_type: Option<String>,
     gpu_specs: Option<String>,
+    ui_language: Option<String>,
 }
 
 impl SystemSpecs {
@@ -47,6 +50,7 @@ impl SystemSpecs {
             _ => None,
         };
         let bundle_type = bundle_type();
+        let ui_language = Some(ui_language_description(cx));
 
         let gpu_specs = window.gpu_specs().map(|specs| {
             format!(
@@ -67,6 +71,7 @@ impl SystemSpecs {
                 architecture,
                 commit_sha,
                 gpu_specs,
+                ui_language,
             }
         })
     }
@@ -99,6 +104,7 @@ impl SystemSpecs {
             commit_sha,
             bundle_type,
             gpu_specs: try_determine_available_gpus(),
+            ui_language: None,
         }
     }
 }
@@ -131,6 +137,11 @@ impl Display for SystemSpecs {
             format!("Architecture: {}", self.architecture),
         ]
         .into_iter()
+        .chain(
+            self.ui_language
+                .as_ref()
+                .map(|language| format!("UI Language: {}", language)),
+        )
         .chain(
             self.gpu_specs
                 .as_ref()
@@ -143,6 +154,19 @@ impl Display for SystemSpecs {
     }
 }
 
+fn ui_language_description(cx: &App) -> String {
+    let settings = LocalizationSettings::get_global(cx);
+    let language = settings.language();
+    if settings.system_locales().is_empty() {
+        language.to_string()
+    } else {
+        format!(
+            "{language} (following system locale {})",
+            settings.system_locales().join(", ")
+        )
+    }
+}
+
 fn try_determine_available_gpus() -> Option<String> {
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     {
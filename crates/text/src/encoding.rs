@@ -0,0 +1,321 @@
+use anyhow::{Context as _, Result};
+use std::fmt;
+
+/// How many bytes are inspected when looking for UTF-16 without a byte order mark.
+const UTF16_SAMPLE_LEN: usize = 4096;
+
+/// The character encoding of a file on disk.
+///
+/// Buffers are always UTF-8 in memory; a buffer's encoding is used to decode its file
+/// when loading it and to encode its text again when saving it, so that files in
+/// legacy encodings round-trip unchanged.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Encoding {
+    encoding: &'static encoding_rs::Encoding,
+    bom: bool,
+}
+
+impl Encoding {
+    pub const UTF_8: Self = Self::new(&encoding_rs::UTF_8_INIT, false);
+    pub const UTF_8_BOM: Self = Self::new(&encoding_rs::UTF_8_INIT, true);
+    pub const UTF_16LE: Self = Self::new(&encoding_rs::UTF_16LE_INIT, true);
+    pub const UTF_16BE: Self = Self::new(&encoding_rs::UTF_16BE_INIT, true);
+    pub const UTF_16LE_NO_BOM: Self = Self::new(&encoding_rs::UTF_16LE_INIT, false);
+    pub const UTF_16BE_NO_BOM: Self = Self::new(&encoding_rs::UTF_16BE_INIT, false);
+    pub const GB18030: Self = Self::new(&encoding_rs::GB18030_INIT, false);
+    pub const GBK: Self = Self::new(&encoding_rs::GBK_INIT, false);
+    pub const BIG5: Self = Self::new(&encoding_rs::BIG5_INIT, false);
+    pub const SHIFT_JIS: Self = Self::new(&encoding_rs::SHIFT_JIS_INIT, false);
+    pub const EUC_JP: Self = Self::new(&encoding_rs::EUC_JP_INIT, false);
+    pub const EUC_KR: Self = Self::new(&encoding_rs::EUC_KR_INIT, false);
+    pub const WINDOWS_1251: Self = Self::new(&encoding_rs::WINDOWS_1251_INIT, false);
+    pub const WINDOWS_1252: Self = Self::new(&encoding_rs::WINDOWS_1252_INIT, false);
+
+    /// The encodings offered when choosing one by hand.
+    pub const ALL: &[Self] = &[
+        Self::UTF_8,
+        Self::UTF_8_BOM,
+        Self::UTF_16LE,
+        Self::UTF_16BE,
+        Self::UTF_16LE_NO_BOM,
+        Self::UTF_16BE_NO_BOM,
+        Self::GB18030,
+        Self::GBK,
+        Self::BIG5,
+        Self::SHIFT_JIS,
+        Self::EUC_JP,
+        Self::EUC_KR,
+        Self::WINDOWS_1251,
+        Self::WINDOWS_1252,
+    ];
+
+    const fn new(encoding: &'static encoding_rs::Encoding, bom: bool) -> Self {
+        Self { encoding, bom }
+    }
+
+    /// Looks up an encoding by its name or one of its [WHATWG labels](https://encoding.spec.whatwg.org/#names-and-labels),
+    /// e.g. `GBK` or `gb2312`.
+    ///
+    /// A byte order mark is only kept for the Unicode encodings.
+    pub fn for_name(name: &str, bom: bool) -> Option<Self> {
+        let encoding = encoding_rs::Encoding::for_label(name.trim().as_bytes())?;
+        let bom = bom && bom_bytes(encoding).is_some();
+        Some(Self { encoding, bom })
+    }
+
+    /// The canonical name of the encoding, e.g. `Shift_JIS`.
+    pub fn name(&self) -> &'static str {
+        self.encoding.name()
+    }
+
+    /// Whether files in this encoding start with a byte order mark.
+    pub fn has_bom(&self) -> bool {
+        self.bom
+    }
+
+    pub fn is_utf8(&self) -> bool {
+        self.encoding == encoding_rs::UTF_8
+    }
+
+    /// Whether this is UTF-8 or UTF-16. Only these are detected reliably by
+    /// [`Self::decode_detected`]; any other encoding it returns is a guess.
+    pub fn is_unicode(&self) -> bool {
+        bom_bytes(self.encoding).is_some()
+    }
+
+    /// Decodes the contents of a file, detecting their encoding.
+    ///
+    /// A byte order mark takes precedence. Otherwise the contents are UTF-16 if most of
+    /// their code units are ASCII, UTF-8 if they are valid as such, and guessed from the
+    /// frequency of their byte sequences in legacy encodings as a last resort. Contents
+    /// with NUL bytes that aren't UTF-16 are rejected as binary.
+    ///
+    /// A guessed encoding can be wrong, so callers should let the user know when the
+    /// returned encoding isn't [Unicode](Self::is_unicode).
+    pub fn decode_detected(bytes: Vec<u8>) -> Result<(String, Self)> {
+        if let Some((encoding, _)) = encoding_rs::Encoding::for_bom(&bytes) {
+            let encoding = Self::new(encoding, true);
+            return Ok((encoding.decode(&bytes), encoding));
+        }
+
+        if let Some(encoding) = detect_utf16(&bytes) {
+            let encoding = Self::new(encoding, false);
+            return Ok((encoding.decode(&bytes), encoding));
+        }
+
+        let bytes = match String::from_utf8(bytes) {
+            Ok(text) => return Ok((text, Self::UTF_8)),
+            Err(error) => error.into_bytes(),
+        };
+        anyhow::ensure!(
+            !bytes.contains(&0),
+            "file is neither valid UTF-8 nor text in a known encoding"
+        );
+
+        let mut detector = chardetng::EncodingDetector::new();
+        detector.feed(&bytes, true);
+        let encoding = Self::new(detector.guess(None, false), false);
+        Ok((encoding.decode(&bytes), encoding))
+    }
+
+    /// Decodes the contents of a file in this encoding, skipping its byte order mark.
+    /// Malformed sequences are replaced with U+FFFD.
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        let bytes = bom_bytes(self.encoding)
+            .and_then(|bom| bytes.strip_prefix(bom))
+            .unwrap_or(bytes);
+        self.encoding
+            .decode_without_bom_handling(bytes)
+            .0
+            .into_owned()
+    }
+
+    /// Encodes text to be written to a file in this encoding, failing if it contains
+    /// characters that the encoding can't represent.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        if self.bom {
+            bytes.extend_from_slice(bom_bytes(self.encoding).unwrap_or_default());
+        }
+
+        if self.encoding == encoding_rs::UTF_16LE {
+            bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+            return Ok(bytes);
+        }
+        if self.encoding == encoding_rs::UTF_16BE {
+            bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+            return Ok(bytes);
+        }
+
+        let mut encoder = self.encoding.new_encoder();
+        let mut remaining = text;
+        loop {
+            let capacity = encoder
+                .max_buffer_length_from_utf8_without_replacement(remaining.len())
+                .context("text is too long to encode")?;
+            bytes.reserve(capacity);
+            let (result, read) =
+                encoder.encode_from_utf8_to_vec_without_replacement(remaining, &mut bytes, true);
+            remaining = &remaining[read..];
+            match result {
+                encoding_rs::EncoderResult::InputEmpty => return Ok(bytes),
+                encoding_rs::EncoderResult::OutputFull => {}
+                encoding_rs::EncoderResult::Unmappable(ch) => {
+                    let offset = text.len() - remaining.len() - ch.len_utf8();
+                    let row = text[..offset].matches('\n').count() + 1;
+                    anyhow::bail!(
+                        "{ch:?} (U+{:04X}) on line {row} can't be encoded as {}",
+                        ch as u32,
+                        self.name()
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Self::UTF_8
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.bom && self.is_utf8() {
+            write!(f, "{} with BOM", self.name())
+        } else if !self.bom && self.is_unicode() && !self.is_utf8() {
+            write!(f, "{} without BOM", self.name())
+        } else {
+            write!(f, "{}", self.name())
+        }
+    }
+}
+
+impl fmt::Debug for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Encoding")
+            .field("name", &self.name())
+            .field("bom", &self.bom)
+            .finish()
+    }
+}
+
+fn bom_bytes(encoding: &'static encoding_rs::Encoding) -> Option<&'static [u8]> {
+    if encoding == encoding_rs::UTF_8 {
+        Some(b"\xEF\xBB\xBF")
+    } else if encoding == encoding_rs::UTF_16LE {
+        Some(b"\xFF\xFE")
+    } else if encoding == encoding_rs::UTF_16BE {
+        Some(b"\xFE\xFF")
+    } else {
+        None
+    }
+}
+
+/// Recognizes UTF-16 without a byte order mark by the zero high bytes of ASCII
+/// characters, which only works for text that is mostly ASCII.
+fn detect_utf16(bytes: &[u8]) -> Option<&'static encoding_rs::Encoding> {
+    if bytes.len() < 2 || !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let sample = &bytes[..bytes.len().min(UTF16_SAMPLE_LEN)];
+    let units = sample.len() / 2;
+    let (mut even_zeros, mut odd_zeros) = (0, 0);
+    for unit in sample.chunks_exact(2) {
+        even_zeros += usize::from(unit[0] == 0);
+        odd_zeros += usize::from(unit[1] == 0);
+    }
+
+    let encoding = if odd_zeros * 2 > units && even_zeros == 0 {
+        encoding_rs::UTF_16LE
+    } else if even_zeros * 2 > units && odd_zeros == 0 {
+        encoding_rs::UTF_16BE
+    } else {
+        return None;
+    };
+    encoding
+        .decode_without_bom_handling_and_without_replacement(bytes)
+        .is_some()
+        .then_some(encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(bytes: &[u8]) -> (String, Encoding) {
+        Encoding::decode_detected(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_detect_unicode() {
+        assert_eq!(detect(b""), ("".into(), Encoding::UTF_8));
+        assert_eq!(detect("中文".as_bytes()), ("中文".into(), Encoding::UTF_8));
+        assert_eq!(
+            detect(b"\xEF\xBB\xBFbom"),
+            ("bom".into(), Encoding::UTF_8_BOM)
+        );
+        assert_eq!(detect(b"\xFF\xFEa\0b\0"), ("ab".into(), Encoding::UTF_16LE));
+        assert_eq!(
+            detect(b"\0a\0b\0\n"),
+            (
+                "ab\n".into(),
+                Encoding::for_name("UTF-16BE", false).unwrap()
+            )
+        );
+        assert!(Encoding::decode_detected(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\xFF".to_vec()).is_err());
+    }
+
+    #[test]
+    fn test_detect_legacy_encodings() {
+        for (text, encoding) in [
+            (
+                "// 用户管理模块：负责创建、删除和查询用户的账号信息。\n",
+                Encoding::GBK,
+            ),
+            (
+                "// 使用者管理模組：負責建立、刪除與查詢使用者的帳號資訊。\n",
+                Encoding::BIG5,
+            ),
+            (
+                "# 設定ファイルを読み込んで、ユーザーの一覧を表示します。\n",
+                Encoding::SHIFT_JIS,
+            ),
+        ] {
+            let bytes = encoding.encode(text).unwrap();
+            assert_eq!(detect(&bytes).0, text);
+            assert_eq!(detect(&bytes).1.encode(text).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(Encoding::UTF_8_BOM.encode("a").unwrap(), b"\xEF\xBB\xBFa");
+        assert_eq!(Encoding::UTF_16BE.encode("a").unwrap(), b"\xFE\xFF\0a");
+        assert_eq!(Encoding::GBK.encode("中").unwrap(), b"\xD6\xD0");
+        assert_eq!(
+            Encoding::SHIFT_JIS
+                .encode("ok\n😀")
+                .unwrap_err()
+                .to_string(),
+            "'😀' (U+1F600) on line 2 can't be encoded as Shift_JIS"
+        );
+    }
+
+    #[test]
+    fn test_for_name() {
+        assert_eq!(Encoding::for_name("gb2312", false), Some(Encoding::GBK));
+        assert_eq!(Encoding::for_name("utf-8", true), Some(Encoding::UTF_8_BOM));
+        assert_eq!(Encoding::for_name("Big5", true), Some(Encoding::BIG5));
+        assert_eq!(Encoding::for_name("nonsense", false), None);
+        assert_eq!(Encoding::UTF_8_BOM.to_string(), "UTF-8 with BOM");
+        assert_eq!(Encoding::UTF_16LE.to_string(), "UTF-16LE");
+        assert_eq!(
+            Encoding::UTF_16LE_NO_BOM.to_string(),
+            "UTF-16LE without BOM"
+        );
+        assert!(Encoding::ALL.contains(&Encoding::for_name("UTF-16BE", false).unwrap()));
+    }
+}
@@ -1,3 +1,4 @@
+use localization::{Language, t};
 use time::{OffsetDateTime, UtcOffset};
 
 /// The formatting style for a timestamp.
@@ -56,6 +57,28 @@ pub fn format_time(timestamp: OffsetDateTime) -> String {
     format_absolute_time(timestamp)
 }
 
+/// Formats a time of day, e.g. "3:45 PM" or "15:45", with the interface language's
+/// names for AM and PM.
+pub fn format_time_of_day(hour: u8, minute: u8, is_12_hour_time: bool) -> String {
+    if !is_12_hour_time {
+        return format!("{:02}:{:02}", hour, minute);
+    }
+
+    let hour_12 = match hour {
+        0 => 12,              // Midnight
+        13..=23 => hour - 12, // PM hours
+        _ => hour,            // AM hours
+    };
+    t!(
+        "time_format.time_12_hour",
+        "{hour}:{minute} {period, select, am {AM} other {PM}}",
+        hour = u32::from(hour_12),
+        minute = format!("{:02}", minute),
+        period = if hour >= 12 { "pm" } else { "am" },
+    )
+    .to_string()
+}
+
 /// Formats the date component of a timestamp in medium style
 pub fn format_date_medium(
     timestamp: OffsetDateTime,
@@ -68,23 +91,22 @@ pub fn format_date_medium(
 fn format_absolute_date(
     timestamp: OffsetDateTime,
     reference: OffsetDateTime,
-    #[allow(unused_variables)] enhanced_date_formatting: bool,
+    enhanced_date_formatting: bool,
 ) -> String {
+    if uses_language_formats() {
+        if enhanced_date_formatting && let Some(day_name) = format_day_name(timestamp, reference) {
+            return day_name;
+        }
+        return format_language_date(timestamp);
+    }
+
     #[cfg(target_os = "macos")]
     {
         if !enhanced_date_formatting {
             return macos::format_date(&timestamp);
         }
 
-        let timestamp_date = timestamp.date();
-        let reference_date = reference.date();
-        if timestamp_date == reference_date {
-            "Today".to_string()
-        } else if reference_date.previous_day() == Some(timestamp_date) {
-            "Yesterday".to_string()
-        } else {
-            macos::format_date(&timestamp)
-        }
+        format_day_name(timestamp, reference).unwrap_or_else(|| macos::format_date(&timestamp))
     }
     #[cfg(not(target_os = "macos"))]
     {
@@ -101,6 +123,10 @@ fn format_absolute_date(
 }
 
 fn format_absolute_time(timestamp: OffsetDateTime) -> String {
+    if uses_language_formats() {
+        return format_time_of_day(timestamp.hour(), timestamp.minute(), false);
+    }
+
     #[cfg(target_os = "macos")]
     {
         macos::format_time(&timestamp)
@@ -121,8 +147,18 @@ fn format_absolute_time(timestamp: OffsetDateTime) -> String {
 fn format_absolute_timestamp(
     timestamp: OffsetDateTime,
     reference: OffsetDateTime,
-    #[allow(unused_variables)] enhanced_date_formatting: bool,
+    enhanced_date_formatting: bool,
 ) -> String {
+    if uses_language_formats() {
+        let time = format_absolute_time(timestamp);
+        if enhanced_date_formatting
+            && let Some(day_name) = format_day_name_at(timestamp, reference, &time)
+        {
+            return day_name;
+        }
+        return format!("{} {}", format_language_date(timestamp), time);
+    }
+
     #[cfg(target_os = "macos")]
     {
         if !enhanced_date_formatting {
@@ -133,12 +169,10 @@ fn format_absolute_timestamp(
             );
         }
 
-        let timestamp_date = timestamp.date();
-        let reference_date = reference.date();
-        if timestamp_date == reference_date {
-            format!("Today at {}", format_absolute_time(timestamp))
-        } else if reference_date.previous_day() == Some(timestamp_date) {
-            format!("Yesterday at {}", format_absolute_time(timestamp))
+        if let Some(day_name) =
+            format_day_name_at(timestamp, reference, &format_absolute_time(timestamp))
+        {
+            day_name
         } else {
             format!(
                 "{} {}",
@@ -160,21 +194,21 @@ fn format_absolute_date_medium(
     reference: OffsetDateTime,
     enhanced_formatting: bool,
 ) -> String {
+    if uses_language_formats() {
+        if enhanced_formatting && let Some(day_name) = format_day_name(timestamp, reference) {
+            return day_name;
+        }
+        return format_language_date_medium(timestamp);
+    }
+
     #[cfg(target_os = "macos")]
     {
         if !enhanced_formatting {
             return macos::format_date_medium(&timestamp);
         }
 
-        let timestamp_date = timestamp.date();
-        let reference_date = reference.date();
-        if timestamp_date == reference_date {
-            "Today".to_string()
-        } else if reference_date.previous_day() == Some(timestamp_date) {
-            "Yesterday".to_string()
-        } else {
-            macos::format_date_medium(&timestamp)
-        }
+        format_day_name(timestamp, reference)
+            .unwrap_or_else(|| macos::format_date_medium(&timestamp))
     }
     #[cfg(not(target_os = "macos"))]
     {
@@ -189,18 +223,12 @@ fn format_absolute_date_medium(
             );
         }
 
-        let timestamp_date = timestamp.date();
-        let reference_date = reference.date();
-        if timestamp_date == reference_date {
-            "Today".to_string()
-        } else if reference_date.previous_day() == Some(timestamp_date) {
-            "Yesterday".to_string()
-        } else {
+        format_day_name(timestamp, reference).unwrap_or_else(|| {
             format_timestamp_naive_date_medium(
                 timestamp,
                 is_12_hour_time_by_locale(current_locale.as_str()),
             )
-        }
+        })
     }
 }
 
@@ -208,6 +236,10 @@ fn format_absolute_timestamp_medium(
     timestamp: OffsetDateTime,
     reference: OffsetDateTime,
 ) -> String {
+    if uses_language_formats() {
+        return format_language_date_medium(timestamp);
+    }
+
     #[cfg(target_os = "macos")]
     {
         format_absolute_date_medium(timestamp, reference, false)
@@ -224,14 +256,26 @@ fn format_relative_time(timestamp: OffsetDateTime, reference: OffsetDateTime) ->
     let difference = reference - timestamp;
     let minutes = difference.whole_minutes();
     match minutes {
-        0 => Some("Just now".to_string()),
-        1 => Some("1 minute ago".to_string()),
-        2..=59 => Some(format!("{} minutes ago", minutes)),
+        0 => Some(t!("time_format.just_now", "Just now").to_string()),
+        1..=59 => Some(
+            t!(
+                "time_format.minutes_ago",
+                "{count, plural, one {# minute ago} other {# minutes ago}}",
+                count = minutes,
+            )
+            .to_string(),
+        ),
         _ => {
             let hours = difference.whole_hours();
             match hours {
-                1 => Some("1 hour ago".to_string()),
-                2..=23 => Some(format!("{} hours ago", hours)),
+                1..=23 => Some(
+                    t!(
+                        "time_format.hours_ago",
+                        "{count, plural, one {# hour ago} other {# hours ago}}",
+                        count = hours,
+                    )
+                    .to_string(),
+                ),
                 _ => None,
             }
         }
@@ -243,34 +287,46 @@ fn format_relative_date(timestamp: OffsetDateTime, reference: OffsetDateTime) ->
     let reference_date = reference.date();
     let difference = reference_date - timestamp_date;
     let days = difference.whole_days();
-    match days {
-        0 => "Today".to_string(),
-        1 => "Yesterday".to_string(),
-        2..=6 => format!("{} days ago", days),
+    let text = match days {
+        0 => t!("time_format.today", "Today"),
+        1 => t!("time_format.yesterday", "Yesterday"),
+        2..=6 => t!(
+            "time_format.days_ago",
+            "{count, plural, one {# day ago} other {# days ago}}",
+            count = days,
+        ),
         _ => {
             let weeks = difference.whole_weeks();
             match weeks {
-                1 => "1 week ago".to_string(),
-                2..=4 => format!("{} weeks ago", weeks),
+                1..=4 => t!(
+                    "time_format.weeks_ago",
+                    "{count, plural, one {# week ago} other {# weeks ago}}",
+                    count = weeks,
+                ),
                 _ => {
                     let month_diff = calculate_month_difference(timestamp, reference);
                     match month_diff {
-                        0..=1 => "1 month ago".to_string(),
-                        2..=11 => format!("{} months ago", month_diff),
+                        0..=11 => t!(
+                            "time_format.months_ago",
+                            "{count, plural, one {# month ago} other {# months ago}}",
+                            count = month_diff.max(1),
+                        ),
                         _ => {
                             let timestamp_year = timestamp_date.year();
                             let reference_year = reference_date.year();
                             let years = reference_year - timestamp_year;
-                            match years {
-                                1 => "1 year ago".to_string(),
-                                _ => format!("{} years ago", years),
-                            }
+                            t!(
+                                "time_format.years_ago",
+                                "{count, plural, one {# year ago} other {# years ago}}",
+                                count = years,
+                            )
                         }
                     }
                 }
             }
         }
-    }
+    };
+    text.to_string()
 }
 
 /// Calculates the difference in months between two timestamps.
@@ -304,31 +360,11 @@ fn calculate_month_difference(timestamp: OffsetDateTime, reference: OffsetDateTi
 /// This function does not respect the user's date and time preferences.
 /// This should only be used as a fallback mechanism when the OS time formatting fails.
 fn format_timestamp_naive_time(timestamp_local: OffsetDateTime, is_12_hour_time: bool) -> String {
-    let timestamp_local_hour = timestamp_local.hour();
-    let timestamp_local_minute = timestamp_local.minute();
-
-    let (hour, meridiem) = if is_12_hour_time {
-        let meridiem = if timestamp_local_hour >= 12 {
-            "PM"
-        } else {
-            "AM"
-        };
-
-        let hour_12 = match timestamp_local_hour {
-            0 => 12,                              // Midnight
-            13..=23 => timestamp_local_hour - 12, // PM hours
-            _ => timestamp_local_hour,            // AM hours
-        };
-
-        (hour_12, Some(meridiem))
-    } else {
-        (timestamp_local_hour, None)
-    };
-
-    match meridiem {
-        Some(meridiem) => format!("{}:{:02} {}", hour, timestamp_local_minute, meridiem),
-        None => format!("{:02}:{:02}", hour, timestamp_local_minute),
-    }
+    format_time_of_day(
+        timestamp_local.hour(),
+        timestamp_local.minute(),
+        is_12_hour_time,
+    )
 }
 
 #[cfg(not(target_os = "macos"))]
@@ -337,13 +373,10 @@ fn format_timestamp_naive_date(
     reference_local: OffsetDateTime,
     is_12_hour_time: bool,
 ) -> String {
-    let reference_local_date = reference_local.date();
     let timestamp_local_date = timestamp_local.date();
 
-    if timestamp_local_date == reference_local_date {
-        "Today".to_string()
-    } else if reference_local_date.previous_day() == Some(timestamp_local_date) {
-        "Yesterday".to_string()
+    if let Some(day_name) = format_day_name(timestamp_local, reference_local) {
+        day_name
     } else {
         match is_12_hour_time {
             true => format!(
@@ -391,13 +424,10 @@ pub fn format_timestamp_naive(
     is_12_hour_time: bool,
 ) -> String {
     let formatted_time = format_timestamp_naive_time(timestamp_local, is_12_hour_time);
-    let reference_local_date = reference_local.date();
     let timestamp_local_date = timestamp_local.date();
 
-    if timestamp_local_date == reference_local_date {
-        format!("Today at {}", formatted_time)
-    } else if reference_local_date.previous_day() == Some(timestamp_local_date) {
-        format!("Yesterday at {}", formatted_time)
+    if let Some(day_name) = format_day_name_at(timestamp_local, reference_local, &formatted_time) {
+        day_name
     } else {
         let formatted_date = match is_12_hour_time {
             true => format!(
@@ -417,6 +447,77 @@ pub fn format_timestamp_naive(
     }
 }
 
+/// Whether dates and times are formatted with the patterns of the interface language
+/// rather than following the system's preferences, which only apply to English.
+fn uses_language_formats() -> bool {
+    localization::current_language() != Language::ENGLISH
+}
+
+/// Returns "Today" or "Yesterday" if the timestamp is on the reference's date or the day
+/// before it.
+fn format_day_name(timestamp: OffsetDateTime, reference: OffsetDateTime) -> Option<String> {
+    let timestamp_date = timestamp.date();
+    let reference_date = reference.date();
+    if timestamp_date == reference_date {
+        Some(t!("time_format.today", "Today").to_string())
+    } else if reference_date.previous_day() == Some(timestamp_date) {
+        Some(t!("time_format.yesterday", "Yesterday").to_string())
+    } else {
+        None
+    }
+}
+
+/// Returns e.g. "Today at 3:45 PM" if the timestamp is on the reference's date or the day
+/// before it.
+fn format_day_name_at(
+    timestamp: OffsetDateTime,
+    reference: OffsetDateTime,
+    time: &str,
+) -> Option<String> {
+    let timestamp_date = timestamp.date();
+    let reference_date = reference.date();
+    if timestamp_date == reference_date {
+        Some(t!("time_format.today_at", "Today at {time}", time = time).to_string())
+    } else if reference_date.previous_day() == Some(timestamp_date) {
+        Some(
+            t!(
+                "time_format.yesterday_at",
+                "Yesterday at {time}",
+                time = time
+            )
+            .to_string(),
+        )
+    } else {
+        None
+    }
+}
+
+/// Formats a date with the interface language's short date pattern, e.g. "2024/02/24".
+fn format_language_date(timestamp: OffsetDateTime) -> String {
+    let date = timestamp.date();
+    t!(
+        "time_format.date",
+        "{month}/{day}/{year}",
+        year = date.year(),
+        month = format!("{:02}", date.month() as u8),
+        day = format!("{:02}", date.day()),
+    )
+    .to_string()
+}
+
+/// Formats a date with the interface language's medium date pattern, e.g. "2024年2月24日".
+fn format_language_date_medium(timestamp: OffsetDateTime) -> String {
+    let date = timestamp.date();
+    t!(
+        "time_format.date_medium",
+        "{month, select, 1 {Jan} 2 {Feb} 3 {Mar} 4 {Apr} 5 {May} 6 {Jun} 7 {Jul} 8 {Aug} 9 {Sep} 10 {Oct} 11 {Nov} other {Dec}} {day}, {year}",
+        year = date.year(),
+        month = (date.month() as u8).to_string(),
+        day = u32::from(date.day()),
+    )
+    .to_string()
+}
+
 #[cfg(not(target_os = "macos"))]
 static CURRENT_LOCALE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
 
@@ -530,6 +631,7 @@ mod tests {
 
     #[test]
     fn test_format_date() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 10, 30, 0);
 
         // Test with same date (today)
@@ -553,6 +655,7 @@ mod tests {
 
     #[test]
     fn test_format_time() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let timestamp = create_offset_datetime(1990, 4, 12, 9, 30, 0);
 
         // We can't assert the exact output as it depends on the platform and locale
@@ -563,6 +666,7 @@ mod tests {
 
     #[test]
     fn test_format_date_medium() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 10, 30, 0);
         let timestamp = create_offset_datetime(1990, 4, 12, 9, 30, 0);
 
@@ -589,6 +693,7 @@ mod tests {
 
     #[test]
     fn test_format_absolute_time() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let timestamp = create_offset_datetime(1990, 4, 12, 9, 30, 0);
 
         // We can't assert the exact output as it depends on the platform and locale
@@ -599,6 +704,7 @@ mod tests {
 
     #[test]
     fn test_format_absolute_date() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 10, 30, 0);
 
         // Test with same date (today)
@@ -625,6 +731,7 @@ mod tests {
 
     #[test]
     fn test_format_absolute_date_medium() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 10, 30, 0);
         let timestamp = create_offset_datetime(1990, 4, 12, 9, 30, 0);
 
@@ -644,6 +751,7 @@ mod tests {
 
     #[test]
     fn test_format_timestamp_naive_time() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let timestamp = create_offset_datetime(1990, 4, 12, 9, 30, 0);
         assert_eq!(format_timestamp_naive_time(timestamp, true), "9:30 AM");
         assert_eq!(format_timestamp_naive_time(timestamp, false), "09:30");
@@ -655,6 +763,7 @@ mod tests {
 
     #[test]
     fn test_format_24_hour_time() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 16, 45, 0);
         let timestamp = create_offset_datetime(1990, 4, 12, 15, 30, 0);
 
@@ -666,6 +775,7 @@ mod tests {
 
     #[test]
     fn test_format_today() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 16, 45, 0);
         let timestamp = create_offset_datetime(1990, 4, 12, 15, 30, 0);
 
@@ -677,6 +787,7 @@ mod tests {
 
     #[test]
     fn test_format_yesterday() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 10, 30, 0);
         let timestamp = create_offset_datetime(1990, 4, 11, 9, 0, 0);
 
@@ -688,6 +799,7 @@ mod tests {
 
     #[test]
     fn test_format_yesterday_less_than_24_hours_ago() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 19, 59, 0);
         let timestamp = create_offset_datetime(1990, 4, 11, 20, 0, 0);
 
@@ -699,6 +811,7 @@ mod tests {
 
     #[test]
     fn test_format_yesterday_more_than_24_hours_ago() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 19, 59, 0);
         let timestamp = create_offset_datetime(1990, 4, 11, 18, 0, 0);
 
@@ -710,6 +823,7 @@ mod tests {
 
     #[test]
     fn test_format_yesterday_over_midnight() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 0, 5, 0);
         let timestamp = create_offset_datetime(1990, 4, 11, 23, 55, 0);
 
@@ -721,6 +835,7 @@ mod tests {
 
     #[test]
     fn test_format_yesterday_over_month() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 2, 9, 0, 0);
         let timestamp = create_offset_datetime(1990, 4, 1, 20, 0, 0);
 
@@ -732,6 +847,7 @@ mod tests {
 
     #[test]
     fn test_format_before_yesterday() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 10, 30, 0);
         let timestamp = create_offset_datetime(1990, 4, 10, 20, 20, 0);
 
@@ -743,6 +859,7 @@ mod tests {
 
     #[test]
     fn test_relative_format_minutes() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 23, 0, 0);
         let mut current_timestamp = reference;
 
@@ -786,6 +903,7 @@ mod tests {
 
     #[test]
     fn test_relative_format_hours() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 23, 0, 0);
         let mut current_timestamp = reference;
 
@@ -818,6 +936,7 @@ mod tests {
 
     #[test]
     fn test_relative_format_days() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 23, 0, 0);
         let mut current_timestamp = reference;
 
@@ -849,6 +968,7 @@ mod tests {
 
     #[test]
     fn test_relative_format_weeks() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 23, 0, 0);
         let mut current_timestamp = reference;
 
@@ -877,6 +997,7 @@ mod tests {
 
     #[test]
     fn test_relative_format_months() {
+        let _language = localization::set_language_for_test(Language::ENGLISH);
         let reference = create_offset_datetime(1990, 4, 12, 23, 0, 0);
         let mut current_timestamp = reference;
 
@@ -952,6 +1073,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_in_interface_language() {
+        let _language = localization::set_language_for_test(Language::SIMPLIFIED_CHINESE);
+        let reference = create_offset_datetime(1990, 4, 12, 16, 45, 0);
+
+        assert_eq!(
+            format_relative_time(create_offset_datetime(1990, 4, 12, 16, 40, 0), reference),
+            Some("5 分钟前".to_string())
+        );
+        assert_eq!(
+            format_relative_date(create_offset_datetime(1990, 2, 1, 9, 0, 0), reference),
+            "2 个月前"
+        );
+        assert_eq!(
+            format_absolute_timestamp(
+                create_offset_datetime(1990, 4, 11, 15, 30, 0),
+                reference,
+                true
+            ),
+            "昨天 15:30"
+        );
+        assert_eq!(
+            format_absolute_timestamp(create_offset_datetime(1990, 4, 2, 9, 5, 0), reference, true),
+            "1990/04/02 09:05"
+        );
+        assert_eq!(
+            format_absolute_date_medium(
+                create_offset_datetime(1990, 4, 2, 9, 5, 0),
+                reference,
+                false
+            ),
+            "1990年4月2日"
+        );
+        assert_eq!(format_time_of_day(15, 5, true), "下午3:05");
+        assert_eq!(format_time_of_day(0, 5, true), "上午12:05");
+    }
+
     fn test_timezone() -> UtcOffset {
         UtcOffset::from_hms(0, 0, 0).expect("Valid timezone offset")
     }
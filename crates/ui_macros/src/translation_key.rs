@@ -0,0 +1,189 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::LazyLock;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, Ident, LitStr, Token, parse_macro_input};
+
+/// The catalog that keys are checked against. `cargo xtask translations` checks that
+/// every bundled language translates the keys used by `t!`, so one of them is enough
+/// to catch mistyped keys while compiling.
+const REFERENCE_CATALOG: &str = include_str!("../../../assets/locales/zh-CN/ui.json");
+const REFERENCE_CATALOG_PATH: &str = "assets/locales/zh-CN/ui.json";
+
+static REFERENCE_KEYS: LazyLock<Result<BTreeSet<String>, String>> = LazyLock::new(|| {
+    // Parsed the same way as the runtime loads catalogs, which allows comments and
+    // trailing commas.
+    serde_json_lenient::from_str::<HashMap<String, String>>(REFERENCE_CATALOG)
+        .map(|catalog| catalog.into_keys().collect())
+        .map_err(|error| format!("failed to parse {REFERENCE_CATALOG_PATH}: {error}"))
+});
+
+struct Translation {
+    key: LitStr,
+    default: LitStr,
+    args: Vec<(Ident, Expr)>,
+}
+
+impl Parse for Translation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let default = input.parse()?;
+        let mut args = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let name = input.parse()?;
+            input.parse::<Token![=]>()?;
+            args.push((name, input.parse()?));
+        }
+        Ok(Self { key, default, args })
+    }
+}
+
+pub fn t(input: TokenStream) -> TokenStream {
+    let translation = parse_macro_input!(input as Translation);
+    if let Err(error) = validate(&translation) {
+        return error.to_compile_error().into();
+    }
+
+    let Translation { key, default, args } = translation;
+    let text = if args.is_empty() {
+        quote! { ::localization::shared(#key, #default) }
+    } else {
+        let names = args.iter().map(|(name, _)| name.to_string());
+        let values = args.iter().map(|(_, value)| value);
+        quote! {
+            ::localization::format_shared(
+                #key,
+                #default,
+                &[#((#names, ::localization::MessageArg::from(#values))),*],
+            )
+        }
+    };
+
+    quote! {
+        {
+            ::localization::__private::inventory::submit! {
+                ::localization::DefaultText {
+                    key: #key,
+                    default: #default,
+                    file: file!(),
+                    line: line!(),
+                }
+            }
+            #text
+        }
+    }
+    .into()
+}
+
+fn validate(translation: &Translation) -> syn::Result<()> {
+    let key = translation.key.value();
+    let default = translation.default.value();
+
+    if key.is_empty()
+        || !key.split('.').all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_')
+        })
+    {
+        return Err(syn::Error::new(
+            translation.key.span(),
+            format!(
+                "invalid translation key {key:?}: keys are dot-separated segments of lowercase letters, digits and underscores"
+            ),
+        ));
+    }
+
+    let reference_keys = REFERENCE_KEYS
+        .as_ref()
+        .map_err(|error| syn::Error::new(translation.key.span(), error))?;
+    if !reference_keys.contains(&key) {
+        let mut message = format!("unknown translation key {key:?}");
+        if let Some(suggestion) = closest_key(&key, reference_keys) {
+            message.push_str(&format!(", did you mean {suggestion:?}?"));
+        } else {
+            message.push_str(&format!(", add it to {REFERENCE_CATALOG_PATH}"));
+        }
+        return Err(syn::Error::new(translation.key.span(), message));
+    }
+
+    // Parsed by the same parser as the runtime uses, so that a default text that
+    // compiles also formats.
+    let referenced = message_format::parse(&default)
+        .map(|parts| message_format::argument_names(&parts))
+        .map_err(|error| {
+            syn::Error::new(
+                translation.default.span(),
+                format!("invalid default text: {error}"),
+            )
+        })?;
+    for name in &referenced {
+        if !translation.args.iter().any(|(arg, _)| arg == name) {
+            return Err(syn::Error::new(
+                translation.default.span(),
+                format!("missing argument `{name}` referenced by the default text"),
+            ));
+        }
+    }
+    for (arg, _) in &translation.args {
+        if !referenced.contains(&arg.to_string()) {
+            return Err(syn::Error::new(
+                arg.span(),
+                format!("argument `{arg}` is not referenced by the default text"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Suggests the known key closest to a mistyped one.
+fn closest_key<'a>(key: &str, keys: &'a BTreeSet<String>) -> Option<&'a String> {
+    keys.iter()
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 3)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_ch) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_ch) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_ch != *b_ch);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_close_keys() {
+        let keys = BTreeSet::from(["menu.file.save".to_string(), "menu.file.open".to_string()]);
+        assert_eq!(
+            closest_key("menu.file.svae", &keys),
+            Some(&"menu.file.save".to_string())
+        );
+        assert_eq!(closest_key("editor.copy", &keys), None);
+    }
+
+    #[test]
+    fn reference_catalog_parses() {
+        assert!(REFERENCE_KEYS.as_ref().unwrap().contains("menu.file.save"));
+    }
+}
@@ -1,5 +1,6 @@
 mod derive_register_component;
 mod dynamic_spacing;
+mod translation_key;
 
 use proc_macro::TokenStream;
 
@@ -35,3 +36,27 @@ pub fn derive_dynamic_spacing(input: TokenStream) -> TokenStream {
 pub fn derive_register_component(input: TokenStream) -> TokenStream {
     derive_register_component::derive_register_component(input)
 }
+
+/// Translates interface text, checking the key at compile time.
+///
+/// Expands to `localization::shared(key, default)`, or to `localization::format_shared`
+/// when arguments are given. Compilation fails if the key is not in the bundled
+/// catalog, or if the default text doesn't parse or doesn't match the arguments. Keys
+/// missing from other catalogs and keys used with different default text are caught by
+/// `cargo xtask translations`. Each use also registers its default text, so the English
+/// catalog can be generated with `zed --dump-default-translations`.
+///
+/// # Example
+///
+/// ```ignore
+/// let save = localization::t!("menu.file.save", "Save");
+/// let changes = localization::t!(
+///     "git_panel.changes",
+///     "{count, plural, =0 {No Changes} one {# Change} other {# Changes}}",
+///     count = entry_count,
+/// );
+/// ```
+#[proc_macro]
+pub fn t(input: TokenStream) -> TokenStream {
+    translation_key::t(input)
+}
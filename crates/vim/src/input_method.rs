@@ -0,0 +1,502 @@
+//! Switching of the system input method as vim mode leaves and re-enters insert mode.
+//!
+//! With a Chinese or Japanese input method active, normal mode keystrokes such as `jj` or
+//! `:w` go to the input method's candidate window instead of Zed. Like `im-select`, leaving
+//! insert mode records the active input method and activates the one configured for normal
+//! mode, and entering insert mode again restores the recorded one.
+
+use anyhow::{Context as _, Result, bail};
+use futures::{FutureExt as _, StreamExt as _, channel::mpsc, future::BoxFuture};
+use gpui::{App, Global, Task};
+use settings::{
+    InputMethodBackendContent, Settings as _, SettingsStore, VimInputMethodSettingsContent,
+};
+use std::sync::Arc;
+use util::ResultExt as _;
+
+use crate::{VimSettings, state::Mode};
+
+/// Queries and activates the input methods of the system.
+pub(crate) trait InputMethodBackend: Send + Sync {
+    /// Returns the identifier of the active input method.
+    fn current_input_method(&self) -> BoxFuture<'_, Result<String>>;
+
+    /// Activates the input method with the given identifier.
+    fn activate_input_method<'a>(&'a self, input_method: &'a str) -> BoxFuture<'a, Result<()>>;
+}
+
+pub(crate) fn init(cx: &mut App) {
+    let mut settings = VimSettings::get_global(cx).input_method.clone();
+    InputMethodSwitcher::configure(&settings, cx);
+
+    cx.observe_global::<SettingsStore>(move |cx| {
+        let new_settings = &VimSettings::get_global(cx).input_method;
+        if *new_settings != settings {
+            settings = new_settings.clone();
+            InputMethodSwitcher::configure(&settings, cx);
+        }
+    })
+    .detach();
+}
+
+/// Switches the input method when vim mode changes from `from` to `to`.
+pub(crate) fn mode_changed(from: Mode, to: Mode, cx: &mut App) {
+    let request = match (is_insert(from), is_insert(to)) {
+        (true, false) => Request::LeaveInsert,
+        (false, true) => Request::EnterInsert,
+        _ => return,
+    };
+    if let Some(requests) = cx
+        .try_global::<InputMethodSwitcher>()
+        .and_then(|switcher| switcher.requests.as_ref())
+    {
+        requests.unbounded_send(request).ok();
+    }
+}
+
+fn is_insert(mode: Mode) -> bool {
+    matches!(mode, Mode::Insert | Mode::Replace)
+}
+
+enum Request {
+    LeaveInsert,
+    EnterInsert,
+}
+
+/// Sends mode changes to a background task, which talks to the backend one request at a
+/// time so that quick successive mode changes are applied in order.
+#[derive(Default)]
+struct InputMethodSwitcher {
+    requests: Option<mpsc::UnboundedSender<Request>>,
+    _task: Option<Task<()>>,
+}
+
+impl Global for InputMethodSwitcher {}
+
+impl InputMethodSwitcher {
+    fn configure(settings: &VimInputMethodSettingsContent, cx: &mut App) {
+        match backend_for_settings(settings) {
+            Ok(Some((backend, normal_mode_input_method))) => {
+                Self::set_backend(backend, normal_mode_input_method, cx)
+            }
+            Ok(None) => cx.set_global(Self::default()),
+            Err(error) => {
+                log::error!("failed to set up input method switching: {error:#}");
+                cx.set_global(Self::default());
+            }
+        }
+    }
+
+    /// Switches input methods through `backend`, activating `normal_mode_input_method`
+    /// outside insert mode.
+    fn set_backend(
+        backend: Arc<dyn InputMethodBackend>,
+        normal_mode_input_method: String,
+        cx: &mut App,
+    ) {
+        let (requests_tx, requests_rx) = mpsc::unbounded();
+        let task = cx.background_spawn(process_requests(
+            backend,
+            normal_mode_input_method,
+            requests_rx,
+        ));
+        cx.set_global(Self {
+            requests: Some(requests_tx),
+            _task: Some(task),
+        });
+    }
+}
+
+async fn process_requests(
+    backend: Arc<dyn InputMethodBackend>,
+    normal_mode_input_method: String,
+    mut requests: mpsc::UnboundedReceiver<Request>,
+) {
+    // The input method that was active when insert mode was last left.
+    let mut insert_mode_input_method = None;
+    while let Some(request) = requests.next().await {
+        let result = match request {
+            Request::LeaveInsert => {
+                async {
+                    let input_method = backend.current_input_method().await?;
+                    if input_method != normal_mode_input_method {
+                        backend
+                            .activate_input_method(&normal_mode_input_method)
+                            .await?;
+                    }
+                    insert_mode_input_method = Some(input_method);
+                    anyhow::Ok(())
+                }
+                .await
+            }
+            Request::EnterInsert => match insert_mode_input_method.take() {
+                Some(input_method) if input_method != normal_mode_input_method => {
+                    backend.activate_input_method(&input_method).await
+                }
+                _ => Ok(()),
+            },
+        };
+        result.context("failed to switch input method").log_err();
+    }
+}
+
+fn backend_for_settings(
+    settings: &VimInputMethodSettingsContent,
+) -> Result<Option<(Arc<dyn InputMethodBackend>, String)>> {
+    let (backend, default_input_method): (Arc<dyn InputMethodBackend>, Option<&str>) =
+        match settings.backend.unwrap_or_default() {
+            InputMethodBackendContent::None => return Ok(None),
+            #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+            InputMethodBackendContent::Fcitx5 => (
+                Arc::new(dbus::Fcitx5Backend::default()),
+                Some("keyboard-us"),
+            ),
+            #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+            InputMethodBackendContent::Ibus => {
+                (Arc::new(dbus::IbusBackend::default()), Some("xkb:us::eng"))
+            }
+            #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+            InputMethodBackendContent::Fcitx5 | InputMethodBackendContent::Ibus => {
+                bail!("the fcitx5 and ibus input method backends are only available on Linux")
+            }
+            InputMethodBackendContent::Command => (
+                Arc::new(CommandBackend::new(
+                    settings.query_command.clone(),
+                    settings.switch_command.clone(),
+                )?),
+                None,
+            ),
+        };
+    let normal_mode_input_method = settings
+        .normal_mode_input_method
+        .clone()
+        .or_else(|| default_input_method.map(ToOwned::to_owned))
+        .context("`normal_mode_input_method` is required with the command backend")?;
+    Ok(Some((backend, normal_mode_input_method)))
+}
+
+/// Runs external commands such as `im-select` or `fcitx5-remote`.
+struct CommandBackend {
+    query_command: Vec<String>,
+    switch_command: Vec<String>,
+}
+
+impl CommandBackend {
+    fn new(
+        query_command: Option<Vec<String>>,
+        switch_command: Option<Vec<String>>,
+    ) -> Result<Self> {
+        Ok(Self {
+            query_command: query_command
+                .filter(|command| !command.is_empty())
+                .context("`query_command` is required with the command backend")?,
+            switch_command: switch_command
+                .filter(|command| !command.is_empty())
+                .context("`switch_command` is required with the command backend")?,
+        })
+    }
+
+    async fn run(command: &[String], input_method: Option<&str>) -> Result<String> {
+        let Some((program, args)) = command.split_first() else {
+            bail!("empty input method command");
+        };
+        let output = util::command::new_smol_command(program)
+            .args(args)
+            .args(input_method)
+            .output()
+            .await
+            .with_context(|| format!("failed to run {program}"))?;
+        if !output.status.success() {
+            bail!(
+                "{program} failed with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+impl InputMethodBackend for CommandBackend {
+    fn current_input_method(&self) -> BoxFuture<'_, Result<String>> {
+        Self::run(&self.query_command, None).boxed()
+    }
+
+    fn activate_input_method<'a>(&'a self, input_method: &'a str) -> BoxFuture<'a, Result<()>> {
+        async move {
+            Self::run(&self.switch_command, Some(input_method)).await?;
+            Ok(())
+        }
+        .boxed()
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+mod dbus {
+    use super::InputMethodBackend;
+    use anyhow::{Context as _, Result};
+    use futures::{FutureExt as _, future::BoxFuture, lock::Mutex};
+    use std::path::PathBuf;
+    use zbus::{
+        Connection,
+        zvariant::{OwnedValue, Value},
+    };
+
+    const FCITX5_SERVICE: &str = "org.fcitx.Fcitx5";
+    const FCITX5_PATH: &str = "/controller";
+    const FCITX5_INTERFACE: &str = "org.fcitx.Fcitx.Controller1";
+
+    const IBUS_SERVICE: &str = "org.freedesktop.IBus";
+    const IBUS_PATH: &str = "/org/freedesktop/IBus";
+    const IBUS_INTERFACE: &str = "org.freedesktop.IBus";
+
+    /// A bus connection that is opened on first use, and again after a failed call in
+    /// case the input method framework was restarted.
+    #[derive(Default)]
+    struct CachedConnection(Mutex<Option<Connection>>);
+
+    impl CachedConnection {
+        async fn call<T>(
+            &self,
+            connect: impl Future<Output = Result<Connection>>,
+            call: impl AsyncFnOnce(Connection) -> Result<T>,
+        ) -> Result<T> {
+            let mut cached = self.0.lock().await;
+            let connection = match cached.as_ref() {
+                Some(connection) => connection.clone(),
+                None => cached.insert(connect.await?).clone(),
+            };
+            let result = call(connection).await;
+            if result.is_err() {
+                cached.take();
+            }
+            result
+        }
+    }
+
+    /// Fcitx 5, through its controller on the session bus.
+    #[derive(Default)]
+    pub(super) struct Fcitx5Backend {
+        connection: CachedConnection,
+    }
+
+    async fn connect_session_bus() -> Result<Connection> {
+        Connection::session()
+            .await
+            .context("failed to connect to the session bus")
+    }
+
+    impl InputMethodBackend for Fcitx5Backend {
+        fn current_input_method(&self) -> BoxFuture<'_, Result<String>> {
+            self.connection
+                .call(connect_session_bus(), async |connection| {
+                    let reply = connection
+                        .call_method(
+                            Some(FCITX5_SERVICE),
+                            FCITX5_PATH,
+                            Some(FCITX5_INTERFACE),
+                            "CurrentInputMethod",
+                            &(),
+                        )
+                        .await?;
+                    Ok(reply.body().deserialize::<String>()?)
+                })
+                .boxed()
+        }
+
+        fn activate_input_method<'a>(&'a self, input_method: &'a str) -> BoxFuture<'a, Result<()>> {
+            self.connection
+                .call(connect_session_bus(), async move |connection| {
+                    connection
+                        .call_method(
+                            Some(FCITX5_SERVICE),
+                            FCITX5_PATH,
+                            Some(FCITX5_INTERFACE),
+                            "SetCurrentIM",
+                            &(input_method,),
+                        )
+                        .await?;
+                    Ok(())
+                })
+                .boxed()
+        }
+    }
+
+    /// IBus, through the global engine of the IBus daemon's own bus.
+    #[derive(Default)]
+    pub(super) struct IbusBackend {
+        connection: CachedConnection,
+    }
+
+    async fn connect_ibus() -> Result<Connection> {
+        let address = ibus_address()?;
+        Ok(zbus::connection::Builder::address(address.as_str())?
+            .build()
+            .await
+            .with_context(|| format!("failed to connect to IBus at {address}"))?)
+    }
+
+    /// Returns the address of the IBus daemon's bus, which is not the session bus.
+    fn ibus_address() -> Result<String> {
+        if let Some(address) = std::env::var("IBUS_ADDRESS")
+            .ok()
+            .filter(|address| !address.is_empty())
+        {
+            return Ok(address);
+        }
+
+        // The daemon writes its address to a file named after the machine and display it
+        // serves. The most recently written one belongs to the running daemon.
+        let bus_dir = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| util::paths::home_dir().join(".config"))
+            .join("ibus")
+            .join("bus");
+        let (_, address_path) = std::fs::read_dir(&bus_dir)
+            .with_context(|| format!("failed to read {}", bus_dir.display()))?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                Some((entry.metadata().ok()?.modified().ok()?, entry.path()))
+            })
+            .max_by_key(|(modified, _)| *modified)
+            .with_context(|| format!("no IBus address in {}", bus_dir.display()))?;
+        std::fs::read_to_string(&address_path)?
+            .lines()
+            .find_map(|line| line.strip_prefix("IBUS_ADDRESS="))
+            .map(ToOwned::to_owned)
+            .with_context(|| format!("no IBus address in {}", address_path.display()))
+    }
+
+    /// Returns the name of the engine in a serialized `IBusEngineDesc`, whose fields are
+    /// the type name, attachments, then the engine's name.
+    fn engine_name(description: &Value) -> Option<String> {
+        match description {
+            Value::Value(description) => engine_name(description),
+            Value::Structure(description) => match description.fields().get(2)? {
+                Value::Str(name) => Some(name.to_string()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    impl InputMethodBackend for IbusBackend {
+        fn current_input_method(&self) -> BoxFuture<'_, Result<String>> {
+            self.connection
+                .call(connect_ibus(), async |connection| {
+                    let reply = connection
+                        .call_method(
+                            Some(IBUS_SERVICE),
+                            IBUS_PATH,
+                            Some(IBUS_INTERFACE),
+                            "GetGlobalEngine",
+                            &(),
+                        )
+                        .await?;
+                    let description = reply.body().deserialize::<OwnedValue>()?;
+                    engine_name(&description).context("unexpected IBus engine description")
+                })
+                .boxed()
+        }
+
+        fn activate_input_method<'a>(&'a self, input_method: &'a str) -> BoxFuture<'a, Result<()>> {
+            self.connection
+                .call(connect_ibus(), async move |connection| {
+                    connection
+                        .call_method(
+                            Some(IBUS_SERVICE),
+                            IBUS_PATH,
+                            Some(IBUS_INTERFACE),
+                            "SetGlobalEngine",
+                            &(input_method,),
+                        )
+                        .await?;
+                    Ok(())
+                })
+                .boxed()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::VimTestContext;
+    use parking_lot::Mutex;
+
+    struct FakeInputMethodBackend {
+        active: Mutex<String>,
+    }
+
+    impl FakeInputMethodBackend {
+        fn new(active: &str) -> Arc<Self> {
+            Arc::new(Self {
+                active: Mutex::new(active.to_string()),
+            })
+        }
+
+        fn active(&self) -> String {
+            self.active.lock().clone()
+        }
+
+        fn set_active(&self, input_method: &str) {
+            *self.active.lock() = input_method.to_string();
+        }
+    }
+
+    impl InputMethodBackend for FakeInputMethodBackend {
+        fn current_input_method(&self) -> BoxFuture<'_, Result<String>> {
+            futures::future::ready(Ok(self.active())).boxed()
+        }
+
+        fn activate_input_method<'a>(&'a self, input_method: &'a str) -> BoxFuture<'a, Result<()>> {
+            self.set_active(input_method);
+            futures::future::ready(Ok(())).boxed()
+        }
+    }
+
+    #[gpui::test]
+    async fn test_input_method_switching(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.set_state("ˇhello", Mode::Normal);
+
+        let backend = FakeInputMethodBackend::new("pinyin");
+        cx.update(|_, cx| {
+            InputMethodSwitcher::set_backend(backend.clone(), "keyboard-us".into(), cx)
+        });
+
+        // Nothing was recorded yet, so entering insert mode leaves the input method alone.
+        cx.simulate_keystrokes("i");
+        cx.run_until_parked();
+        assert_eq!(backend.active(), "pinyin");
+
+        cx.simulate_keystrokes("escape");
+        cx.run_until_parked();
+        assert_eq!(backend.active(), "keyboard-us");
+
+        cx.simulate_keystrokes("a");
+        cx.run_until_parked();
+        assert_eq!(backend.active(), "pinyin");
+
+        // The input method chosen while inserting is the one restored next time.
+        backend.set_active("mozc");
+        cx.simulate_keystrokes("escape");
+        cx.run_until_parked();
+        assert_eq!(backend.active(), "keyboard-us");
+        cx.simulate_keystrokes("o");
+        cx.run_until_parked();
+        assert_eq!(backend.active(), "mozc");
+
+        // Moving between normal and visual mode doesn't touch the input method.
+        cx.simulate_keystrokes("escape v");
+        cx.run_until_parked();
+        backend.set_active("anthy");
+        cx.simulate_keystrokes("escape");
+        cx.run_until_parked();
+        assert_eq!(backend.active(), "anthy");
+
+        cx.simulate_keystrokes("i");
+        cx.run_until_parked();
+        assert_eq!(backend.active(), "mozc");
+    }
+}
@@ -2,7 +2,8 @@ use editor::{
     Anchor, Bias, DisplayPoint, Editor, RowExt, ToOffset, ToPoint,
     display_map::{DisplayRow, DisplaySnapshot, FoldPoint, ToDisplayPoint},
     movement::{
-        self, FindRange, TextLayoutDetails, find_boundary, find_preceding_boundary_display_point,
+        self, CjkWordBoundaries, FindRange, TextLayoutDetails, find_boundary,
+        find_preceding_boundary_display_point,
     },
 };
 use gpui::{Action, Context, Window, actions, px};
@@ -1697,12 +1698,15 @@ pub(crate) fn next_word_start(
         .ignore_punctuation(ignore_punctuation);
     for _ in 0..times {
         let mut crossed_newline = false;
+        let mut cjk_words = CjkWordBoundaries::forward(map, point.to_offset(map, Bias::Right));
         let new_point = movement::find_boundary(map, point, FindRange::MultiLine, |left, right| {
+            let at_cjk_word_start = cjk_words.step(left, right) && !ignore_punctuation;
             let left_kind = classifier.kind(left);
             let right_kind = classifier.kind(right);
             let at_newline = right == '\n';
 
-            let found = (left_kind != right_kind && right_kind != CharKind::Whitespace)
+            let found = at_cjk_word_start
+                || (left_kind != right_kind && right_kind != CharKind::Whitespace)
                 || at_newline && crossed_newline
                 || at_newline && left == '\n'; // Prevents skipping repeated empty lines
 
@@ -1736,11 +1740,13 @@ pub(crate) fn next_word_end(
         } else {
             point
         };
+        let mut cjk_words = CjkWordBoundaries::forward(map, new_point.to_offset(map, Bias::Right));
         let new_point = movement::find_boundary_exclusive(
             map,
             new_point,
             FindRange::MultiLine,
             |left, right| {
+                let at_cjk_word_end = cjk_words.step(left, right) && !ignore_punctuation;
                 let left_kind = classifier.kind(left);
                 let right_kind = classifier.kind(right);
                 let at_newline = right == '\n';
@@ -1750,7 +1756,7 @@ pub(crate) fn next_word_end(
                     return true;
                 }
 
-                left_kind != right_kind && left_kind != CharKind::Whitespace
+                at_cjk_word_end || (left_kind != right_kind && left_kind != CharKind::Whitespace)
             },
         );
         let new_point = if need_next_char {
@@ -1780,15 +1786,20 @@ fn previous_word_start(
     for _ in 0..times {
         // This works even though find_preceding_boundary is called for every character in the line containing
         // cursor because the newline is checked only once.
+        let mut cjk_words =
+            CjkWordBoundaries::backward(map, point.to_point(map).to_offset(&map.buffer_snapshot));
         let new_point = movement::find_preceding_boundary_display_point(
             map,
             point,
             FindRange::MultiLine,
             |left, right| {
+                let at_cjk_word_start = cjk_words.step(left, right) && !ignore_punctuation;
                 let left_kind = classifier.kind(left);
                 let right_kind = classifier.kind(right);
 
-                (left_kind != right_kind && !right.is_whitespace()) || left == '\n'
+                at_cjk_word_start
+                    || (left_kind != right_kind && !right.is_whitespace())
+                    || left == '\n'
             },
         );
         if point == new_point {
@@ -1817,11 +1828,15 @@ fn previous_word_end(
         point.column += ch.len_utf8() as u32;
     }
     for _ in 0..times {
+        let mut cjk_words = CjkWordBoundaries::backward(map, point.to_offset(&map.buffer_snapshot));
         let new_point = movement::find_preceding_boundary_point(
             &map.buffer_snapshot,
             point,
             FindRange::MultiLine,
             |left, right| {
+                if cjk_words.step(left, right) && !ignore_punctuation {
+                    return true;
+                }
                 let left_kind = classifier.kind(left);
                 let right_kind = classifier.kind(right);
                 match (left_kind, right_kind) {
@@ -4360,4 +4375,27 @@ mod test {
         the quick brown foˇd over the lazy dog"});
         assert!(!cx.cx.forced_motion());
     }
+
+    #[gpui::test]
+    async fn test_cjk_word_motions(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇ我们使用中文编辑器 test", Mode::Normal);
+        cx.simulate_keystrokes("w");
+        cx.assert_state("我们ˇ使用中文编辑器 test", Mode::Normal);
+        cx.simulate_keystrokes("e");
+        cx.assert_state("我们使ˇ用中文编辑器 test", Mode::Normal);
+        cx.simulate_keystrokes("e");
+        cx.assert_state("我们使用中ˇ文编辑器 test", Mode::Normal);
+        cx.simulate_keystrokes("b");
+        cx.assert_state("我们使用ˇ中文编辑器 test", Mode::Normal);
+        cx.simulate_keystrokes("g e");
+        cx.assert_state("我们使ˇ用中文编辑器 test", Mode::Normal);
+        cx.simulate_keystrokes("W");
+        cx.assert_state("我们使用中文编辑器 ˇtest", Mode::Normal);
+
+        cx.set_state("我们使用中文编ˇ辑器", Mode::Normal);
+        cx.simulate_keystrokes("d i w");
+        cx.assert_state("我们使用中ˇ文", Mode::Normal);
+    }
 }
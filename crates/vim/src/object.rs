@@ -8,7 +8,7 @@ use crate::{
 use editor::{
     Bias, DisplayPoint, Editor, ToOffset,
     display_map::{DisplaySnapshot, ToDisplayPoint},
-    movement::{self, FindRange},
+    movement::{self, CjkWordBoundaries, FindRange},
 };
 use gpui::{Action, Window, actions};
 use itertools::Itertools;
@@ -743,15 +743,27 @@ fn in_word(
         .buffer_snapshot
         .char_classifier_at(relative_to.to_point(map))
         .ignore_punctuation(ignore_punctuation);
+    let start_search_point = right(map, relative_to, 1);
+    let mut cjk_words = CjkWordBoundaries::backward(
+        map,
+        start_search_point
+            .to_point(map)
+            .to_offset(&map.buffer_snapshot),
+    );
     let start = movement::find_preceding_boundary_display_point(
         map,
-        right(map, relative_to, 1),
+        start_search_point,
         movement::FindRange::SingleLine,
-        |left, right| classifier.kind(left) != classifier.kind(right),
+        |left, right| {
+            (cjk_words.step(left, right) && !ignore_punctuation)
+                || classifier.kind(left) != classifier.kind(right)
+        },
     );
 
+    let mut cjk_words = CjkWordBoundaries::forward(map, relative_to.to_offset(map, Bias::Right));
     let end = movement::find_boundary(map, relative_to, FindRange::SingleLine, |left, right| {
-        classifier.kind(left) != classifier.kind(right)
+        (cjk_words.step(left, right) && !ignore_punctuation)
+            || classifier.kind(left) != classifier.kind(right)
     });
 
     Some(start..end)
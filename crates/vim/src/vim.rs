@@ -8,6 +8,7 @@ mod command;
 mod digraph;
 mod helix;
 mod indent;
+mod input_method;
 mod insert;
 mod mode_indicator;
 mod motion;
@@ -259,6 +260,7 @@ pub fn init(cx: &mut App) {
     vim_mode_setting::init(cx);
     VimSettings::register(cx);
     VimGlobals::register(cx);
+    input_method::init(cx);
 
     cx.observe_new(Vim::register).detach();
 
@@ -1033,6 +1035,7 @@ impl Vim {
                 self.mode = Mode::HelixSelect
             }
         }
+        input_method::mode_changed(last_mode, self.mode, cx);
 
         if leave_selections {
             return;
@@ -1804,6 +1807,7 @@ struct VimSettings {
     pub custom_digraphs: HashMap<String, Arc<str>>,
     pub highlight_on_yank_duration: u64,
     pub cursor_shape: CursorShapeSettings,
+    pub input_method: settings::VimInputMethodSettingsContent,
 }
 
 /// The settings for cursor shape.
@@ -1859,6 +1863,7 @@ impl Settings for VimSettings {
             custom_digraphs: vim.custom_digraphs.unwrap(),
             highlight_on_yank_duration: vim.highlight_on_yank_duration.unwrap(),
             cursor_shape: vim.cursor_shape.unwrap().into(),
+            input_method: vim.input_method.unwrap(),
         }
     }
 }
@@ -714,6 +714,7 @@ async fn test_write_file(cx: &mut TestAppContext) {
                 Path::new("tracked-dir/file.txt"),
                 "hello".into(),
                 Default::default(),
+                Default::default(),
                 cx,
             )
         })
@@ -725,6 +726,7 @@ async fn test_write_file(cx: &mut TestAppContext) {
                 Path::new("ignored-dir/file.txt"),
                 "world".into(),
                 Default::default(),
+                Default::default(),
                 cx,
             )
         })
@@ -1745,8 +1747,13 @@ fn randomly_mutate_worktree(
                 })
             } else {
                 log::info!("overwriting file {:?} ({})", entry.path, entry.id.0);
-                let task =
-                    worktree.write_file(entry.path.clone(), "".into(), Default::default(), cx);
+                let task = worktree.write_file(
+                    entry.path.clone(),
+                    "".into(),
+                    Default::default(),
+                    Default::default(),
+                    cx,
+                );
                 cx.background_spawn(async move {
                     task.await?;
                     Ok(())
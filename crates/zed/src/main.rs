@@ -118,6 +118,19 @@ fn files_not_created_on_launch(errors: HashMap<io::ErrorKind, Vec<&Path>>) {
     })
 }
 
+/// Applies the `pinyin_matching` setting to the fuzzy matcher whenever settings
+/// change. It's done here, rather than by localization, which owns the setting, so
+/// that localization doesn't depend on the fuzzy matcher.
+fn init_pinyin_matching(cx: &mut App) {
+    fuzzy::set_pinyin_matching(localization::LocalizationSettings::get_global(cx).pinyin_matching);
+    cx.observe_global::<SettingsStore>(|cx| {
+        fuzzy::set_pinyin_matching(
+            localization::LocalizationSettings::get_global(cx).pinyin_matching,
+        );
+    })
+    .detach();
+}
+
 fn fail_to_open_window_async(e: anyhow::Error, cx: &mut AsyncApp) {
     cx.update(|cx| fail_to_open_window(e, cx)).log_err();
 }
@@ -204,6 +217,11 @@ pub fn main() {
         return;
     }
 
+    if args.dump_default_translations {
+        dump_default_translations();
+        return;
+    }
+
     // Set custom data directory.
     if let Some(dir) = &args.user_data_dir {
         paths::set_custom_data_dir(dir);
@@ -383,6 +401,7 @@ pub fn main() {
         }
         settings::init(cx);
         localization::init(cx);
+        init_pinyin_matching(cx);
         zlog_settings::init(cx);
         handle_settings_file_changes(
             user_settings_file_rx,
@@ -536,6 +555,10 @@ pub fn main() {
             ThemeRegistry::global(cx),
             cx.background_executor().clone(),
         );
+        localization_extension::init(
+            extension_host_proxy.clone(),
+            cx.background_executor().clone(),
+        );
         command_palette::init(cx);
         let copilot_language_server_id = app_state.languages.next_language_server_id();
         copilot::init(
@@ -603,10 +626,12 @@ pub fn main() {
         journal::init(app_state.clone(), cx);
         language_selector::init(cx);
         line_ending_selector::init(cx);
+        encoding_selector::init(cx);
         toolchain_selector::init(cx);
         theme_selector::init(cx);
         settings_profile_selector::init(cx);
         language_tools::init(cx);
+        performance_monitor::init(cx);
         call::init(app_state.client.clone(), app_state.user_store.clone(), cx);
         notifications::init(app_state.client.clone(), app_state.user_store.clone(), cx);
         collab_ui::init(&app_state, cx);
@@ -664,9 +689,12 @@ pub fn main() {
         let fs = app_state.fs.clone();
         load_user_themes_in_background(fs.clone(), cx);
         watch_themes(fs.clone(), cx);
+        localization::watch_user_catalogs(fs.clone(), cx);
         watch_languages(fs.clone(), app_state.languages.clone(), cx);
 
         cx.set_menus(app_menus());
+        cx.observe_global::<localization::LanguageEventChannel>(|cx| cx.set_menus(app_menus()))
+            .detach();
         initialize_workspace(app_state.clone(), prompt_builder, cx);
 
         cx.activate(true);
@@ -1222,6 +1250,10 @@ struct Args {
     #[arg(long, hide = true)]
     dump_all_actions: bool,
 
+    /// Output the default English text of every translated string as JSON to stdout
+    #[arg(long, hide = true)]
+    dump_default_translations: bool,
+
     /// Output current environment variables as JSON to stdout
     #[arg(long, hide = true)]
     printenv: bool,
@@ -1460,3 +1492,19 @@ fn dump_all_gpui_actions() {
     )
     .unwrap();
 }
+
+fn dump_default_translations() {
+    let catalog = match localization::default_catalog() {
+        Ok(catalog) => catalog,
+        Err(error) => {
+            eprintln!("{error:#}");
+            process::exit(1);
+        }
+    };
+
+    io::Write::write(
+        &mut std::io::stdout(),
+        serde_json::to_string_pretty(&catalog).unwrap().as_bytes(),
+    )
+    .unwrap();
+}
@@ -26,17 +26,17 @@ use futures::{StreamExt, channel::mpsc, select_biased};
 use git_ui::git_panel::GitPanel;
 use git_ui::project_diff::ProjectDiffToolbar;
 use gpui::{
-    Action, App, AppContext as _, AsyncApp, Context, DismissEvent, Element, Entity, Focusable,
-    KeyBinding, ParentElement, PathPromptOptions, PromptLevel, ReadGlobal, SharedString, Styled,
-    Task, TitlebarOptions, UpdateGlobal, Window, WindowKind, WindowOptions, actions, image_cache,
-    point, px, retain_all,
+    Action, App, AppContext as _, Context, DismissEvent, Element, Entity, Focusable, KeyBinding,
+    ParentElement, PathPromptOptions, PromptLevel, ReadGlobal, SharedString, Styled, Task,
+    TitlebarOptions, UpdateGlobal, Window, WindowKind, WindowOptions, actions, image_cache, point,
+    px, retain_all,
 };
 use image_viewer::ImageInfo;
+use jj_ui::jj_panel::JjPanel;
 use language::Capability;
 use language_onboarding::BasedPyrightBanner;
 use language_tools::lsp_button::{self, LspButton};
 use language_tools::lsp_log_view::LspLogToolbarItemView;
-use localization::{self, Language};
 use migrate::{MigrationBanner, MigrationEvent, MigrationNotification, MigrationType};
 use migrator::{migrate_keymap, migrate_settings};
 use onboarding::DOCS_URL;
@@ -65,10 +65,7 @@ use settings::{
 use std::time::Duration;
 use std::{
     borrow::Cow,
-    env,
-    ffi::OsString,
     path::{Path, PathBuf},
-    process::Command,
     sync::Arc,
     sync::atomic::{self, AtomicBool},
 };
@@ -191,6 +188,20 @@ pub fn init(cx: &mut App) {
             );
         });
     });
+    cx.on_action(
+        |_: &zed_actions::localization::ShowMissingTranslations, cx| {
+            with_active_or_new_workspace(cx, |workspace, window, cx| {
+                open_bundled_file(
+                    workspace,
+                    localization::missing_translations_report().into(),
+                    "Missing Translations",
+                    "Markdown",
+                    window,
+                    cx,
+                );
+            });
+        },
+    );
     cx.on_action(|_: &zed_actions::OpenTelemetryLog, cx| {
         with_active_or_new_workspace(cx, |workspace, window, cx| {
             open_telemetry_log_file(workspace, window, cx);
@@ -402,6 +413,7 @@ pub fn initialize_workspace(
         );
         let active_buffer_language =
             cx.new(|_| language_selector::ActiveBufferLanguage::new(workspace));
+        let active_buffer_encoding = cx.new(|_| encoding_selector::ActiveBufferEncoding::new());
         let active_toolchain_language =
             cx.new(|cx| toolchain_selector::ActiveToolchain::new(workspace, window, cx));
         let vim_mode_indicator = cx.new(|cx| vim::ModeIndicator::new(window, cx));
@@ -425,6 +437,7 @@ pub fn initialize_workspace(
             status_bar.add_left_item(diagnostic_summary, window, cx);
             status_bar.add_left_item(activity_indicator, window, cx);
             status_bar.add_right_item(edit_prediction_button, window, cx);
+            status_bar.add_right_item(active_buffer_encoding, window, cx);
             status_bar.add_right_item(active_buffer_language, window, cx);
             status_bar.add_right_item(active_toolchain_language, window, cx);
             status_bar.add_right_item(vim_mode_indicator, window, cx);
@@ -575,6 +588,7 @@ fn initialize_panels(
         let outline_panel = OutlinePanel::load(workspace_handle.clone(), cx.clone());
         let terminal_panel = TerminalPanel::load(workspace_handle.clone(), cx.clone());
         let git_panel = GitPanel::load(workspace_handle.clone(), cx.clone());
+        let jj_panel = JjPanel::load(workspace_handle.clone(), cx.clone());
         let channels_panel =
             collab_ui::collab_panel::CollabPanel::load(workspace_handle.clone(), cx.clone());
         let notification_panel = collab_ui::notification_panel::NotificationPanel::load(
@@ -588,6 +602,7 @@ fn initialize_panels(
             outline_panel,
             terminal_panel,
             git_panel,
+            jj_panel,
             channels_panel,
             notification_panel,
             debug_panel,
@@ -596,6 +611,7 @@ fn initialize_panels(
             outline_panel,
             git_panel,
             terminal_panel,
+            jj_panel,
             channels_panel,
             notification_panel,
             debug_panel,
@@ -606,6 +622,7 @@ fn initialize_panels(
             workspace.add_panel(outline_panel, window, cx);
             workspace.add_panel(terminal_panel, window, cx);
             workspace.add_panel(git_panel, window, cx);
+            workspace.add_panel(jj_panel, window, cx);
             workspace.add_panel(channels_panel, window, cx);
             workspace.add_panel(notification_panel, window, cx);
             workspace.add_panel(debug_panel, window, cx);
@@ -790,96 +807,31 @@ fn register_actions(
         })
         .register_action({
             let fs = app_state.fs.clone();
-            move |_, action: &SetUiLanguage, window, cx| {
-                let normalized = action.language.trim().to_ascii_lowercase();
-                let preference = match normalized.as_str() {
-                    "auto" | "system" | "follow-system" => UiLanguagePreference::Auto,
-                    "en" | "en-us" | "english" => UiLanguagePreference::English,
-                    "zh" | "zh-cn" | "zh-hans" | "simplified-chinese" => {
-                        UiLanguagePreference::SimplifiedChinese
-                    }
-                    other => {
-                        log::warn!("unrecognized ui language '{}'", other);
-                        return;
-                    }
-                };
-
-                let language = match preference {
-                    UiLanguagePreference::English => Language::English,
-                    UiLanguagePreference::SimplifiedChinese | UiLanguagePreference::Auto => {
-                        Language::SimplifiedChinese
-                    }
+            move |_, action: &SetUiLanguage, _window, cx| {
+                let preference =
+                    UiLanguagePreference::parse(&action.language).filter(|preference| {
+                        match preference {
+                            UiLanguagePreference::Auto => true,
+                            UiLanguagePreference::Locale(tag) => {
+                                localization::Language::from_code(tag).is_some()
+                            }
+                        }
+                    });
+                let Some(preference) = preference else {
+                    log::warn!("unrecognized ui language '{}'", action.language);
+                    return;
                 };
 
-                let previous_language = localization::current_language();
-                let language_changed = previous_language != language;
-
-                localization::set_language(language);
-                cx.refresh_windows();
+                localization::switch_language(
+                    localization::language_for_preference(&preference),
+                    cx,
+                );
 
-                let preference_for_file = preference;
+                let value = preference.as_str().to_string();
                 update_settings_file(fs.clone(), cx, move |settings, _| {
-                    settings.ui_language = Some(preference_for_file);
+                    settings.ui_language = Some(preference);
                 });
-
-                let value = match preference_for_file {
-                    UiLanguagePreference::Auto => "auto",
-                    UiLanguagePreference::English => "en-US",
-                    UiLanguagePreference::SimplifiedChinese => "zh-CN",
-                };
                 telemetry::event!("Settings Changed", setting = "ui_language", value = value);
-
-                if !language_changed {
-                    return;
-                }
-
-                let title = localization::translate_owned(
-                    "prompt.restart_required.title",
-                    "Restart Required",
-                );
-                let message = localization::translate_owned(
-                    "prompt.restart_required.message",
-                    "Please restart Zed to fully apply the language change.",
-                );
-                let restart_now = localization::translate_owned(
-                    "prompt.restart_required.restart_now",
-                    "Restart Now",
-                );
-                let later = localization::translate_owned("prompt.restart_required.later", "Later");
-
-                let prompt = window.prompt(
-                    PromptLevel::Info,
-                    title.as_str(),
-                    Some(message.as_str()),
-                    &[restart_now.as_str(), later.as_str()],
-                    cx,
-                );
-
-                let restart_args: Vec<OsString> = env::args_os().skip(1).collect();
-                let restart_exe = match env::current_exe() {
-                    Ok(path) => path,
-                    Err(err) => {
-                        log::error!("failed to determine current executable for restart: {err:?}");
-                        return;
-                    }
-                };
-
-                cx.spawn(move |_, cx: &mut AsyncApp| {
-                    let async_cx = cx.clone();
-                    async move {
-                        if prompt.await == Ok(0) {
-                            match Command::new(&restart_exe).args(&restart_args).spawn() {
-                                Ok(_) => {
-                                    async_cx.update(|cx| cx.quit()).ok();
-                                }
-                                Err(err) => {
-                                    log::error!("failed to restart Zed: {err:?}");
-                                }
-                            }
-                        }
-                    }
-                })
-                .detach();
             }
         })
         .register_action({
@@ -4587,6 +4539,7 @@ mod tests {
                 "diagnostics",
                 "edit_prediction",
                 "editor",
+                "encoding_selector",
                 "feedback",
                 "file_finder",
                 "git",
@@ -4595,6 +4548,7 @@ mod tests {
                 "go_to_line",
                 "icon_theme_selector",
                 "jj",
+                "jj_panel",
                 "journal",
                 "keymap_editor",
                 "keystroke_input",
@@ -4610,6 +4564,7 @@ mod tests {
                 "outline_panel",
                 "pane",
                 "panel",
+                "performance_monitor",
                 "picker",
                 "project_panel",
                 "project_search",
@@ -4769,6 +4724,7 @@ mod tests {
             editor::init(cx);
             collab_ui::init(&app_state, cx);
             git_ui::init(cx);
+            jj_ui::init(cx);
             project_panel::init(cx);
             outline_panel::init(cx);
             terminal_view::init(cx);
@@ -48,32 +48,25 @@ pub fn app_menus() -> Vec<Menu> {
             ),
             MenuItem::submenu(Menu {
                 name: localization::shared("menu.interface_language", "Interface Language"),
-                items: vec![
-                    MenuItem::action(
-                        localization::shared(
-                            "menu.interface_language.follow_system",
-                            "Follow System",
-                        ),
-                        zed_actions::SetUiLanguage {
-                            language: "auto".into(),
-                        },
-                    ),
-                    MenuItem::action(
-                        localization::shared(
-                            "menu.interface_language.simplified_chinese",
-                            "Simplified Chinese",
-                        ),
-                        zed_actions::SetUiLanguage {
-                            language: "zh-CN".into(),
-                        },
-                    ),
-                    MenuItem::action(
-                        localization::shared("menu.interface_language.english", "English"),
-                        zed_actions::SetUiLanguage {
-                            language: "en-US".into(),
-                        },
-                    ),
-                ],
+                items: std::iter::once(MenuItem::action(
+                    localization::shared("menu.interface_language.follow_system", "Follow System"),
+                    zed_actions::SetUiLanguage {
+                        language: "auto".into(),
+                    },
+                ))
+                .chain(
+                    localization::available_languages()
+                        .into_iter()
+                        .map(|language| {
+                            MenuItem::action(
+                                localization::display_name(&language),
+                                zed_actions::SetUiLanguage {
+                                    language: language.code().to_string(),
+                                },
+                            )
+                        }),
+                )
+                .collect(),
             }),
         ]
     };
@@ -32,7 +32,7 @@ pub struct OpenZedUrl {
 #[action(namespace = zed)]
 #[serde(deny_unknown_fields)]
 pub struct SetUiLanguage {
-    /// Language identifier such as `auto`, `zh-CN`, `en-US`.
+    /// `auto` to follow the system, or a language tag such as `zh-CN`, `ja` or `en`.
     pub language: String,
 }
 
@@ -158,6 +158,18 @@ pub mod dev {
     );
 }
 
+pub mod localization {
+    use gpui::actions;
+
+    actions!(
+        localization,
+        [
+            /// Lists the interface strings that fell back to English, with their call sites.
+            ShowMissingTranslations
+        ]
+    );
+}
+
 pub mod workspace {
     use gpui::actions;
 
@@ -203,7 +215,35 @@ pub mod jj {
         jj,
         [
             /// Opens the Jujutsu bookmark list.
-            BookmarkList
+            BookmarkList,
+            /// Creates a new, empty commit on top of the selected commit and edits it.
+            New,
+            /// Edits the description of the selected commit.
+            Describe,
+            /// Confirms the description being edited.
+            ConfirmDescription,
+            /// Moves the changes in the selected commit into its parent.
+            Squash,
+            /// Splits the changes to a file out of the selected commit.
+            Split,
+            /// Makes the selected commit the working-copy commit.
+            Edit,
+            /// Abandons the selected commit, rebasing its descendants onto its parents.
+            Abandon,
+            /// Creates a bookmark pointing to the selected commit.
+            CreateBookmark,
+            /// Moves a bookmark to the selected commit.
+            MoveBookmark,
+            /// Deletes a local bookmark.
+            DeleteBookmark,
+            /// Starts tracking a remote bookmark.
+            TrackBookmark,
+            /// Opens the Jujutsu operation log.
+            OperationLog,
+            /// Reverts the changes made by the selected operation.
+            UndoOperation,
+            /// Returns the repository to its state after the selected operation.
+            RestoreOperation
         ]
     );
 }
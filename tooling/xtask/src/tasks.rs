@@ -1,3 +1,4 @@
 pub mod clippy;
 pub mod licenses;
 pub mod package_conformity;
+pub mod translations;
@@ -0,0 +1,243 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Result, bail};
+use clap::Parser;
+use walkdir::WalkDir;
+
+use crate::workspace::load_workspace;
+
+#[derive(Parser)]
+pub struct TranslationsArgs {}
+
+/// A `t!` call whose key and default text are string literals.
+#[derive(Debug, PartialEq)]
+struct CallSite {
+    key: String,
+    default: String,
+    line: usize,
+}
+
+pub fn run_translations(_args: TranslationsArgs) -> Result<()> {
+    let workspace = load_workspace()?;
+    let root = workspace.workspace_root.as_std_path();
+
+    let mut uses = BTreeMap::<String, Vec<(String, CallSite)>>::new();
+    for package in workspace.workspace_packages() {
+        if !package
+            .dependencies
+            .iter()
+            .any(|dependency| dependency.name == "localization")
+        {
+            continue;
+        }
+        let Some(package_dir) = package.manifest_path.parent() else {
+            continue;
+        };
+        for entry in WalkDir::new(package_dir) {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().is_none_or(|extension| extension != "rs") {
+                continue;
+            }
+            let source = fs::read_to_string(path).with_context(|| format!("reading {path:?}"))?;
+            let relative_path = path.strip_prefix(root).unwrap_or(path).display();
+            for call_site in call_sites(&source) {
+                uses.entry(call_site.key.clone())
+                    .or_default()
+                    .push((relative_path.to_string(), call_site));
+            }
+        }
+    }
+
+    let mut problems = Vec::new();
+    for (key, uses) in &uses {
+        let defaults = uses
+            .iter()
+            .map(|(_, call_site)| call_site.default.as_str())
+            .collect::<BTreeSet<_>>();
+        if defaults.len() > 1 {
+            let mut problem = format!("{key:?} is used with different default text:");
+            for (path, call_site) in uses {
+                problem.push_str(&format!(
+                    "\n  {path}:{}: {:?}",
+                    call_site.line, call_site.default
+                ));
+            }
+            problems.push(problem);
+        }
+    }
+
+    for catalog_path in catalog_paths(root)? {
+        let catalog = read_catalog(&catalog_path)?;
+        let relative_path = catalog_path.strip_prefix(root).unwrap_or(&catalog_path);
+        for (key, uses) in &uses {
+            if !catalog.contains_key(key) {
+                let (path, call_site) = &uses[0];
+                problems.push(format!(
+                    "{key:?} used at {path}:{} is missing from {}",
+                    call_site.line,
+                    relative_path.display()
+                ));
+            }
+        }
+    }
+
+    for problem in &problems {
+        eprintln!("{problem}");
+    }
+    if !problems.is_empty() {
+        bail!("found {} translation problems", problems.len());
+    }
+
+    Ok(())
+}
+
+/// Returns the bundled `ui.json` catalog of every interface language.
+fn catalog_paths(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(root.join("assets/locales"))? {
+        let path = entry?.path().join("ui.json");
+        if path.exists() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Reads a catalog leniently, like the runtime does, so comments and trailing commas
+/// are allowed.
+fn read_catalog(path: &Path) -> Result<BTreeMap<String, String>> {
+    let content = fs::read_to_string(path).with_context(|| format!("reading {path:?}"))?;
+    serde_json_lenient::from_str(&content).with_context(|| format!("parsing {path:?}"))
+}
+
+/// Finds the `t!` calls in Rust source, skipping those in line comments and those
+/// whose key or default text isn't a string literal.
+fn call_sites(source: &str) -> Vec<CallSite> {
+    let mut call_sites = Vec::new();
+    let mut offset = 0;
+    while let Some(ix) = source[offset..].find("t!(") {
+        let start = offset + ix;
+        offset = start + "t!(".len();
+
+        let preceding = source[..start].chars().next_back();
+        if preceding.is_some_and(|ch| ch.is_alphanumeric() || ch == '_') {
+            continue;
+        }
+        let line_start = source[..start].rfind('\n').map_or(0, |ix| ix + 1);
+        if source[line_start..start].contains("//") {
+            continue;
+        }
+
+        let mut rest = &source[offset..];
+        let Some(key) = string_literal(&mut rest) else {
+            continue;
+        };
+        rest = rest.trim_start();
+        let Some(after_comma) = rest.strip_prefix(',') else {
+            continue;
+        };
+        rest = after_comma;
+        let Some(default) = string_literal(&mut rest) else {
+            continue;
+        };
+        call_sites.push(CallSite {
+            key,
+            default,
+            line: source[..start].matches('\n').count() + 1,
+        });
+    }
+    call_sites
+}
+
+/// Parses a string literal at the start of `input`, after any whitespace, and
+/// advances past it.
+fn string_literal(input: &mut &str) -> Option<String> {
+    let source = input.trim_start();
+    if let Some(raw) = source.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let body = raw[hashes..].strip_prefix('"')?;
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        let end = body.find(&terminator)?;
+        *input = &body[end + terminator.len()..];
+        return Some(body[..end].to_string());
+    }
+
+    let mut chars = source.strip_prefix('"')?.char_indices();
+    let mut value = String::new();
+    while let Some((ix, ch)) = chars.next() {
+        match ch {
+            '"' => {
+                *input = &source[ix + 2..];
+                return Some(value);
+            }
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                '0' => value.push('\0'),
+                '\n' => {
+                    while chars
+                        .clone()
+                        .next()
+                        .is_some_and(|(_, ch)| ch.is_whitespace())
+                    {
+                        chars.next();
+                    }
+                }
+                escaped => value.push(escaped),
+            },
+            ch => value.push(ch),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_call_sites() {
+        let source = r##"
+            let a = t!("menu.file.save", "Save");
+            let b = localization::t!(
+                "git_panel.changes",
+                "{count, plural, one {# \"Change\"} other {# Changes}}",
+                count = count,
+            );
+            let c = t!(r#"raw.key"#, "Same"); let d = t!("raw.key", "Line");
+            // t!("commented.out", "Ignored")
+            let e = format!("{}", t!(key, "Not a literal"));
+            let f = shift!("not.a.translation", "Ignored");
+        "##;
+        assert_eq!(
+            call_sites(source),
+            vec![
+                CallSite {
+                    key: "menu.file.save".into(),
+                    default: "Save".into(),
+                    line: 2,
+                },
+                CallSite {
+                    key: "git_panel.changes".into(),
+                    default: "{count, plural, one {# \"Change\"} other {# Changes}}".into(),
+                    line: 3,
+                },
+                CallSite {
+                    key: "raw.key".into(),
+                    default: "Same".into(),
+                    line: 8,
+                },
+                CallSite {
+                    key: "raw.key".into(),
+                    default: "Line".into(),
+                    line: 8,
+                },
+            ]
+        );
+    }
+}